@@ -0,0 +1,135 @@
+// Copyright (c) ZeroC, Inc.
+
+//! A library for writing Slice code-generators in Rust, which slicec runs as plugins (ex: '-G my-generator').
+//!
+//! slicec communicates with code-generators by writing requests to their stdin, and reading responses from their
+//! stdout, as defined in 'slice/Compiler/CodeGenerator.slice'. This library handles all of this communication, so
+//! a code-generator only needs to pass a function to [run], which is called with the decoded [Request], and returns a
+//! [Response] holding the files it generated, and any diagnostics it reported:
+//!
+//! ```no_run
+//! use slicec_plugin::{DiagnosticBuilder, Response};
+//! use std::process::ExitCode;
+//!
+//! fn main() -> ExitCode {
+//!     slicec_plugin::run(|request| {
+//!         let mut response = Response::default();
+//!         for (file, interface) in request.interfaces() {
+//!             let interface_id = format!(
+//!                 "{}::{}",
+//!                 file.module_declaration.identifier, interface.entity_info.identifier
+//!             );
+//!             let mut contents = format!("interface {interface_id}\n");
+//!             for operation in request.all_operations(interface) {
+//!                 contents += &format!("    operation {}\n", operation.entity_info.identifier);
+//!             }
+//!             if interface.operations.is_empty() {
+//!                 DiagnosticBuilder::warning("this interface doesn't define any operations")
+//!                     .source(&interface_id)
+//!                     .push_into(&mut response.diagnostics);
+//!             }
+//!             response.add_file(
+//!                 format!("{}.txt", interface.entity_info.identifier),
+//!                 contents,
+//!             );
+//!         }
+//!         response
+//!     })
+//! }
+//! ```
+//!
+//! The definitions of the Slice files are re-exported in [definition_types].
+
+mod request;
+mod response;
+
+pub use request::{Request, ResolvedType};
+pub use response::{DiagnosticBuilder, Response};
+pub use slicec::code_generator::PROTOCOL_VERSION;
+pub use slicec::definition_types;
+
+use slice_codec::decoder::Decoder;
+use slice_codec::encoder::Encoder;
+use slicec::definition_types::{Arguments, CompilationContext, GeneratorInfo, SliceFile};
+use std::io::{Read, Write};
+use std::path::Path;
+use std::process::ExitCode;
+
+/// Runs a code-generator: this reads a request from stdin, and writes the response to it to stdout.
+///
+/// 'describe' requests are answered automatically, with the code-generator's name being the name of its executable.
+/// For 'generateCode' requests, the provided `generate_code` function is called with the decoded request, and the
+/// [Response] it returns is sent back to slicec.
+///
+/// If the request couldn't be read, or the response couldn't be written, an error is printed to stderr, and this
+/// returns [ExitCode::FAILURE]. Otherwise this returns [ExitCode::SUCCESS].
+pub fn run(generate_code: impl FnOnce(&Request) -> Response) -> ExitCode {
+    let name = generator_name();
+    let mut request = Vec::new();
+    let result = std::io::stdin()
+        .read_to_end(&mut request)
+        .and_then(|_| handle_request(&request, &name, generate_code))
+        .and_then(|response| std::io::stdout().write_all(&response));
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("{name}: {error}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Decodes a request from slicec, and returns the encoded response to it.
+///
+/// This is what [run] uses to handle the request it reads from stdin. It's exposed so that code-generators can be
+/// tested without having to spawn a process.
+pub fn handle_request(
+    request: &[u8],
+    name: &str,
+    generate_code: impl FnOnce(&Request) -> Response,
+) -> std::io::Result<Vec<u8>> {
+    let mut decoder = Decoder::from(request);
+    let mut response: Vec<u8> = Vec::new();
+    let mut encoder = Encoder::from(&mut response);
+
+    let operation = decoder.decode::<String>()?;
+    match operation.as_str() {
+        "describe" => {
+            let generator_info = GeneratorInfo {
+                name: name.to_owned(),
+                protocol_version: PROTOCOL_VERSION,
+            };
+            encoder.encode(&generator_info)?;
+        }
+
+        "generateCode" => {
+            let source_files = decoder.decode::<Vec<SliceFile>>()?;
+            let reference_files = decoder.decode::<Vec<SliceFile>>()?;
+            let context = decoder.decode::<CompilationContext>()?;
+            let Arguments(args) = decoder.decode::<Arguments>()?;
+
+            let request = Request::new(source_files, reference_files, context, args);
+            let Response {
+                generated_files,
+                diagnostics,
+            } = generate_code(&request);
+            encoder.encode(&generated_files)?;
+            encoder.encode(&diagnostics)?;
+        }
+
+        _ => {
+            let message = format!("unknown request '{operation}'");
+            return Err(std::io::Error::new(std::io::ErrorKind::InvalidData, message));
+        }
+    }
+    Ok(response)
+}
+
+/// Returns the name of this code-generator, which is the name of its executable (without any extension).
+fn generator_name() -> String {
+    let executable = std::env::args_os().next();
+    let file_stem = executable.as_deref().map(Path::new).and_then(Path::file_stem);
+    let name = file_stem.and_then(|stem| stem.to_str());
+    name.unwrap_or("slicec-plugin").to_owned()
+}
@@ -0,0 +1,258 @@
+// Copyright (c) ZeroC, Inc.
+
+use slicec::definition_types::{
+    BasicEnum, CompilationContext, CustomType, DictionaryType, Interface, Operation, ResultType, SequenceType,
+    SliceFile, Struct, Symbol, TypeRef, VariantEnum,
+};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// The keywords of Slice's primitive types, which are used as their type IDs.
+const PRIMITIVE_KEYWORDS: &[&str] = &[
+    "bool",
+    "int8",
+    "uint8",
+    "int16",
+    "uint16",
+    "int32",
+    "uint32",
+    "varint32",
+    "varuint32",
+    "int64",
+    "uint64",
+    "varint62",
+    "varuint62",
+    "float32",
+    "float64",
+    "string",
+];
+
+/// A 'generateCode' request sent by slicec, holding the Slice files to generate code for.
+///
+/// Besides the decoded files, this provides functions for resolving the IDs that the files use to reference each
+/// other's definitions (type IDs and base interfaces), and for walking the operations of interfaces.
+#[derive(Debug)]
+pub struct Request {
+    /// The Slice files that code should be generated for.
+    pub source_files: Vec<SliceFile>,
+
+    /// The Slice files that the source files reference, but that code shouldn't be generated for.
+    pub reference_files: Vec<SliceFile>,
+
+    /// Information about the compilation that these files are from.
+    pub context: CompilationContext,
+
+    /// The arguments which were passed to this code-generator on the command line (ex: '-G name,arg1=value1').
+    pub args: Vec<(String, String)>,
+
+    /// Map of the fully scoped identifiers of all the top-level definitions to where they're defined.
+    symbols: BTreeMap<String, SymbolLocation>,
+
+    /// Map of the paths of all the files to where they're stored.
+    files: BTreeMap<String, FileLocation>,
+}
+
+/// Where a file is stored in a [Request]: whether it's a reference file, and its index.
+#[derive(Clone, Copy, Debug)]
+struct FileLocation {
+    is_reference: bool,
+    index: usize,
+}
+
+/// Where a top-level definition is stored in a [Request]: the file it's in, and its index in that file's contents.
+#[derive(Clone, Copy, Debug)]
+struct SymbolLocation {
+    file: FileLocation,
+    index: usize,
+}
+
+/// A Slice type which a type ID has been resolved to.
+#[derive(Clone, Copy, Debug)]
+pub enum ResolvedType<'a> {
+    /// A primitive type, holding its keyword (ex: `int32`).
+    Primitive(&'a str),
+    Struct(&'a Struct),
+    BasicEnum(&'a BasicEnum),
+    VariantEnum(&'a VariantEnum),
+    CustomType(&'a CustomType),
+    Sequence(&'a SequenceType),
+    Dictionary(&'a DictionaryType),
+    Result(&'a ResultType),
+}
+
+impl Request {
+    /// Creates a request holding the provided Slice files, and indexes their definitions.
+    pub fn new(
+        source_files: Vec<SliceFile>,
+        reference_files: Vec<SliceFile>,
+        context: CompilationContext,
+        args: Vec<(String, String)>,
+    ) -> Self {
+        let mut symbols = BTreeMap::new();
+        let mut files = BTreeMap::new();
+
+        let file_sets = [(false, &source_files), (true, &reference_files)];
+        for (is_reference, file_set) in file_sets {
+            for (file_index, file) in file_set.iter().enumerate() {
+                let file_location = FileLocation {
+                    is_reference,
+                    index: file_index,
+                };
+                files.insert(file.path.clone(), file_location);
+
+                let module = &file.module_declaration.identifier;
+                for (symbol_index, symbol) in file.contents.iter().enumerate() {
+                    if let Some(identifier) = symbol_identifier(symbol) {
+                        let location = SymbolLocation {
+                            file: file_location,
+                            index: symbol_index,
+                        };
+                        symbols.insert(format!("{module}::{identifier}"), location);
+                    }
+                }
+            }
+        }
+
+        Request {
+            source_files,
+            reference_files,
+            context,
+            args,
+            symbols,
+            files,
+        }
+    }
+
+    /// Returns the value of the argument with the provided key, if it was passed to this code-generator.
+    /// If it was passed multiple times, the last value is returned.
+    pub fn arg(&self, key: &str) -> Option<&str> {
+        let arg = self.args.iter().rev().find(|(arg_key, _)| arg_key == key);
+        arg.map(|(_, value)| value.as_str())
+    }
+
+    /// Returns the file with the provided path, which can either be a source file or a reference file.
+    pub fn find_file(&self, path: &str) -> Option<&SliceFile> {
+        self.files.get(path).map(|location| self.file_at(*location))
+    }
+
+    /// Returns the top-level definition with the provided fully scoped identifier (ex: `MyModule::MyStruct`).
+    pub fn find_symbol(&self, entity_id: &str) -> Option<&Symbol> {
+        let location = self.symbols.get(entity_id)?;
+        Some(&self.file_at(location.file).contents[location.index])
+    }
+
+    /// Returns the interface with the provided fully scoped identifier.
+    pub fn find_interface(&self, entity_id: &str) -> Option<&Interface> {
+        match self.find_symbol(entity_id)? {
+            Symbol::Interface(interface) => Some(interface),
+            _ => None,
+        }
+    }
+
+    /// Returns the type that the provided type reference refers to, or `None` if it doesn't refer to any type.
+    ///
+    /// Anonymous types (sequences, dictionaries, and results) are referenced by their index in the contents of the file
+    /// containing the type reference, which is found through the type reference's span.
+    pub fn resolve_type(&self, type_ref: &TypeRef) -> Option<ResolvedType<'_>> {
+        if let Ok(index) = type_ref.type_id.parse::<usize>() {
+            let file = self.find_file(&type_ref.span.file)?;
+            return match file.contents.get(index)? {
+                Symbol::SequenceType(v) => Some(ResolvedType::Sequence(v)),
+                Symbol::DictionaryType(v) => Some(ResolvedType::Dictionary(v)),
+                Symbol::ResultType(v) => Some(ResolvedType::Result(v)),
+                _ => None,
+            };
+        }
+        self.find_type(&type_ref.type_id)
+    }
+
+    /// Returns the primitive type or user-defined type with the provided type ID, or `None` if there isn't one.
+    /// Anonymous types can't be found by this function, since their IDs are relative to a file; use
+    /// [resolve_type](Self::resolve_type) to find them instead.
+    pub fn find_type(&self, type_id: &str) -> Option<ResolvedType<'_>> {
+        if let Some(keyword) = PRIMITIVE_KEYWORDS.iter().find(|keyword| **keyword == type_id) {
+            return Some(ResolvedType::Primitive(keyword));
+        }
+
+        match self.find_symbol(type_id)? {
+            Symbol::Struct(v) => Some(ResolvedType::Struct(v)),
+            Symbol::BasicEnum(v) => Some(ResolvedType::BasicEnum(v)),
+            Symbol::VariantEnum(v) => Some(ResolvedType::VariantEnum(v)),
+            Symbol::CustomType(v) => Some(ResolvedType::CustomType(v)),
+            _ => None,
+        }
+    }
+
+    /// Returns all the interfaces defined in the source files, along with the file that each one is defined in.
+    pub fn interfaces(&self) -> impl Iterator<Item = (&SliceFile, &Interface)> {
+        self.source_files.iter().flat_map(|file| {
+            file.contents.iter().filter_map(move |symbol| match symbol {
+                Symbol::Interface(interface) => Some((file, interface)),
+                _ => None,
+            })
+        })
+    }
+
+    /// Returns the interfaces that the provided interface directly inherits from.
+    /// Any bases which can't be found are skipped.
+    pub fn base_interfaces(&self, interface: &Interface) -> Vec<&Interface> {
+        let bases = interface.bases.iter();
+        bases.filter_map(|base| self.find_interface(base)).collect()
+    }
+
+    /// Returns all the interfaces that the provided interface inherits from, either directly or indirectly.
+    /// Each interface is only returned once, even if it's inherited multiple times (through diamond inheritance).
+    pub fn all_base_interfaces(&self, interface: &Interface) -> Vec<&Interface> {
+        let mut all_bases = Vec::new();
+        let mut seen_identifiers = BTreeSet::new();
+        let mut pending_bases = interface.bases.iter().collect::<Vec<_>>();
+        pending_bases.reverse();
+
+        // Walk the inheritance graph depth-first, so bases are returned in the same order slicec visits them in.
+        while let Some(base_id) = pending_bases.pop() {
+            if !seen_identifiers.insert(base_id) {
+                continue;
+            }
+            if let Some(base) = self.find_interface(base_id) {
+                all_bases.push(base);
+                pending_bases.extend(base.bases.iter().rev());
+            }
+        }
+        all_bases
+    }
+
+    /// Returns all the operations that the provided interface inherits from its bases.
+    pub fn all_inherited_operations(&self, interface: &Interface) -> Vec<&Operation> {
+        let bases = self.all_base_interfaces(interface).into_iter();
+        bases.flat_map(|base| &base.operations).collect()
+    }
+
+    /// Returns all the operations of the provided interface, starting with the operations it defines, followed by the
+    /// operations it inherits from its bases.
+    pub fn all_operations<'a>(&'a self, interface: &'a Interface) -> Vec<&'a Operation> {
+        let mut operations = interface.operations.iter().collect::<Vec<_>>();
+        operations.extend(self.all_inherited_operations(interface));
+        operations
+    }
+
+    fn file_at(&self, location: FileLocation) -> &SliceFile {
+        match location.is_reference {
+            false => &self.source_files[location.index],
+            true => &self.reference_files[location.index],
+        }
+    }
+}
+
+/// Returns the identifier of a top-level definition, or `None` for anonymous types, which don't have identifiers.
+fn symbol_identifier(symbol: &Symbol) -> Option<&str> {
+    let entity_info = match symbol {
+        Symbol::Interface(v) => &v.entity_info,
+        Symbol::BasicEnum(v) => &v.entity_info,
+        Symbol::VariantEnum(v) => &v.entity_info,
+        Symbol::Struct(v) => &v.entity_info,
+        Symbol::CustomType(v) => &v.entity_info,
+        Symbol::TypeAlias(v) => &v.entity_info,
+        Symbol::Constant(v) => &v.entity_info,
+        Symbol::SequenceType(_) | Symbol::DictionaryType(_) | Symbol::ResultType(_) => return None,
+    };
+    Some(&entity_info.identifier)
+}
@@ -0,0 +1,95 @@
+// Copyright (c) ZeroC, Inc.
+
+use slicec::definition_types::{Diagnostic, DiagnosticLevel, EntityId, GeneratedFile};
+
+/// The response to a 'generateCode' request, holding the files that were generated, and any diagnostics that were
+/// reported while generating them. slicec writes these files into its output directory, and reports these diagnostics
+/// alongside its own.
+#[derive(Debug, Default)]
+pub struct Response {
+    /// The files that were generated, with paths relative to slicec's output directory.
+    pub generated_files: Vec<GeneratedFile>,
+
+    /// The diagnostics that were reported. If any of these are errors, slicec will exit with a failure code.
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+impl Response {
+    /// Adds a generated file to this response.
+    pub fn add_file(&mut self, path: impl Into<String>, contents: impl Into<String>) {
+        self.generated_files.push(GeneratedFile {
+            path: path.into(),
+            contents: contents.into(),
+        });
+    }
+
+    /// Returns true if any error diagnostics have been added to this response.
+    pub fn has_errors(&self) -> bool {
+        let mut levels = self.diagnostics.iter().map(|diagnostic| diagnostic.level);
+        levels.any(|level| matches!(level, DiagnosticLevel::Error))
+    }
+}
+
+/// A builder for [Diagnostic]s, which are reported to slicec through [Response::diagnostics].
+///
+/// ```
+/// # use slicec_plugin::{DiagnosticBuilder, Response};
+/// let mut response = Response::default();
+/// DiagnosticBuilder::error("operations named 'reset' are not supported")
+///     .source("MyModule::MyInterface::reset")
+///     .push_into(&mut response.diagnostics);
+/// ```
+#[derive(Debug)]
+#[must_use]
+pub struct DiagnosticBuilder {
+    level: DiagnosticLevel,
+    message: String,
+    source: Option<EntityId>,
+}
+
+impl DiagnosticBuilder {
+    /// Creates a builder for a diagnostic with the provided level and message.
+    pub fn new(level: DiagnosticLevel, message: impl Into<String>) -> Self {
+        DiagnosticBuilder {
+            level,
+            message: message.into(),
+            source: None,
+        }
+    }
+
+    /// Creates a builder for an error with the provided message.
+    pub fn error(message: impl Into<String>) -> Self {
+        Self::new(DiagnosticLevel::Error, message)
+    }
+
+    /// Creates a builder for a warning with the provided message.
+    pub fn warning(message: impl Into<String>) -> Self {
+        Self::new(DiagnosticLevel::Warning, message)
+    }
+
+    /// Creates a builder for an informational diagnostic with the provided message.
+    pub fn info(message: impl Into<String>) -> Self {
+        Self::new(DiagnosticLevel::Info, message)
+    }
+
+    /// Sets the Slice element that this diagnostic is about, which slicec uses to show where it occurred.
+    /// The element is identified by its fully scoped identifier (ex: `MyModule::MyInterface::myOperation::myParam`).
+    pub fn source(mut self, entity_id: impl Into<EntityId>) -> Self {
+        self.source = Some(entity_id.into());
+        self
+    }
+
+    /// Builds the diagnostic.
+    pub fn build(self) -> Diagnostic {
+        Diagnostic {
+            level: self.level,
+            message: self.message,
+            source: self.source,
+        }
+    }
+
+    /// Builds the diagnostic, and pushes it into the provided list of diagnostics.
+    pub fn push_into(self, diagnostics: &mut Vec<Diagnostic>) {
+        diagnostics.push(self.build());
+    }
+}
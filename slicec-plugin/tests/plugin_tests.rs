@@ -0,0 +1,187 @@
+// Copyright (c) ZeroC, Inc.
+
+use slice_codec::decoder::Decoder;
+use slice_codec::encoder::Encoder;
+use slicec::compile_from_strings;
+use slicec_plugin::definition_types::{
+    Arguments, CompilationContext, Diagnostic, DiagnosticLevel, GeneratedFile, GeneratorInfo, SliceFile, Symbol,
+};
+use slicec_plugin::{handle_request, DiagnosticBuilder, Request, ResolvedType, Response, PROTOCOL_VERSION};
+
+/// Compiles the provided Slice files, and returns the converted files.
+fn compile(slice: &[&str]) -> Vec<SliceFile> {
+    let compilation_state = compile_from_strings(slice, None);
+    assert!(!compilation_state.diagnostics.has_errors());
+    compilation_state.files.iter().map(SliceFile::from).collect()
+}
+
+fn empty_context() -> CompilationContext {
+    CompilationContext {
+        compiler_version: String::new(),
+        defined_symbols: Vec::new(),
+        absolute_paths: Default::default(),
+        output_dir: String::new(),
+    }
+}
+
+/// Returns the interface with the provided identifier, which must be defined in the source files of the request.
+fn interface_named<'a>(request: &'a Request, identifier: &str) -> &'a slicec_plugin::definition_types::Interface {
+    let mut interfaces = request.interfaces().map(|(_, interface)| interface);
+    interfaces.find(|i| i.entity_info.identifier == identifier).unwrap()
+}
+
+#[test]
+fn describe_requests_are_answered_with_the_generator_info() {
+    // Arrange
+    let mut request: Vec<u8> = Vec::new();
+    let mut encoder = Encoder::from(&mut request);
+    encoder.encode("describe").unwrap();
+    encoder.encode(PROTOCOL_VERSION).unwrap();
+
+    // Act
+    let response = handle_request(&request, "my-generator", |_| unreachable!()).unwrap();
+
+    // Assert
+    let generator_info = Decoder::from(response.as_slice()).decode::<GeneratorInfo>().unwrap();
+    assert_eq!(generator_info.name, "my-generator");
+    assert_eq!(generator_info.protocol_version, PROTOCOL_VERSION);
+}
+
+#[test]
+fn generate_code_requests_are_decoded_and_their_responses_are_encoded() {
+    // Arrange
+    let source_files = compile(&["module Test"]);
+    let args = vec![("key".to_owned(), "value".to_owned())];
+
+    let mut request: Vec<u8> = Vec::new();
+    let mut encoder = Encoder::from(&mut request);
+    encoder.encode("generateCode").unwrap();
+    encoder.encode(&source_files).unwrap();
+    encoder.encode(&Vec::<SliceFile>::new()).unwrap();
+    encoder.encode(&empty_context()).unwrap();
+    encoder.encode(Arguments(args)).unwrap();
+
+    // Act
+    let response = handle_request(&request, "my-generator", |request| {
+        let mut response = Response::default();
+        let module = &request.source_files[0].module_declaration.identifier;
+        response.add_file("test.txt", format!("{module}: {}", request.arg("key").unwrap()));
+        DiagnosticBuilder::warning("something happened")
+            .source("Test")
+            .push_into(&mut response.diagnostics);
+        response
+    })
+    .unwrap();
+
+    // Assert
+    let mut decoder = Decoder::from(response.as_slice());
+    let generated_files = decoder.decode::<Vec<GeneratedFile>>().unwrap();
+    let diagnostics = decoder.decode::<Vec<Diagnostic>>().unwrap();
+
+    assert_eq!(generated_files.len(), 1);
+    assert_eq!(generated_files[0].path, "test.txt");
+    assert_eq!(generated_files[0].contents, "Test: value");
+
+    assert_eq!(diagnostics.len(), 1);
+    assert!(matches!(diagnostics[0].level, DiagnosticLevel::Warning));
+    assert_eq!(diagnostics[0].message, "something happened");
+    assert_eq!(diagnostics[0].source.as_deref(), Some("Test"));
+}
+
+#[test]
+fn unknown_requests_are_rejected() {
+    // Arrange
+    let mut request: Vec<u8> = Vec::new();
+    Encoder::from(&mut request).encode("foo").unwrap();
+
+    // Act
+    let result = handle_request(&request, "my-generator", |_| unreachable!());
+
+    // Assert
+    assert_eq!(result.unwrap_err().to_string(), "unknown request 'foo'");
+}
+
+#[test]
+fn types_are_resolved_across_files() {
+    // Arrange
+    let slice1 = "
+        module Test
+        struct S { f: Sequence<Other::E> }
+    ";
+    let slice2 = "
+        module Other
+        enum E : uint8 { A }
+    ";
+    let mut files = compile(&[slice1, slice2]);
+    let reference_files = files.split_off(1);
+    let request = Request::new(files, reference_files, empty_context(), Vec::new());
+
+    // Act
+    let Some(Symbol::Struct(s)) = request.find_symbol("Test::S") else { panic!() };
+    let field_type = request.resolve_type(&s.fields[0].data_type);
+
+    // Assert
+    let Some(ResolvedType::Sequence(sequence)) = field_type else { panic!("{field_type:?}") };
+    let element_type = request.resolve_type(&sequence.element_type);
+    let Some(ResolvedType::BasicEnum(e)) = element_type else { panic!("{element_type:?}") };
+    assert_eq!(e.entity_info.identifier, "E");
+    assert!(matches!(
+        request.find_type("varuint62"),
+        Some(ResolvedType::Primitive("varuint62"))
+    ));
+    assert!(request.find_type("Test::Missing").is_none());
+}
+
+#[test]
+fn all_operations_includes_each_inherited_operation_once() {
+    // Arrange
+    let slice = "
+        module Test
+        interface A { a() }
+        interface B : A { b() }
+        interface C : A { c() }
+        interface D : B, C { d() }
+    ";
+    let request = Request::new(compile(&[slice]), Vec::new(), empty_context(), Vec::new());
+    let interface = interface_named(&request, "D");
+
+    // Act
+    let base_interfaces = request.base_interfaces(interface);
+    let all_base_interfaces = request.all_base_interfaces(interface);
+    let all_operations = request.all_operations(interface);
+
+    // Assert
+    let base_ids = base_interfaces
+        .iter()
+        .map(|i| &i.entity_info.identifier)
+        .collect::<Vec<_>>();
+    let all_base_ids = all_base_interfaces
+        .iter()
+        .map(|i| &i.entity_info.identifier)
+        .collect::<Vec<_>>();
+    let operation_ids = all_operations
+        .iter()
+        .map(|o| &o.entity_info.identifier)
+        .collect::<Vec<_>>();
+    assert_eq!(base_ids, ["B", "C"]);
+    assert_eq!(all_base_ids, ["B", "A", "C"]);
+    assert_eq!(operation_ids, ["d", "b", "a", "c"]);
+}
+
+#[test]
+fn diagnostic_builders_set_the_level_and_source() {
+    // Arrange
+    let mut response = Response::default();
+
+    // Act
+    DiagnosticBuilder::info("info").push_into(&mut response.diagnostics);
+    let error = DiagnosticBuilder::error("error").source("Test::S::f").build();
+    response.diagnostics.push(error);
+
+    // Assert
+    assert!(matches!(response.diagnostics[0].level, DiagnosticLevel::Info));
+    assert_eq!(response.diagnostics[0].source, None);
+    assert!(matches!(response.diagnostics[1].level, DiagnosticLevel::Error));
+    assert_eq!(response.diagnostics[1].source.as_deref(), Some("Test::S::f"));
+    assert!(response.has_errors());
+}
@@ -0,0 +1,349 @@
+// Copyright (c) ZeroC, Inc.
+
+//! This module generates the Rust mapping of each kind of Slice definition, along with its `EncodeInto` and
+//! `DecodeFrom` implementations.
+
+use crate::encoding::{decode_fields, encode_fields, illegal_value_error, FieldMapping};
+use crate::type_mapping::{
+    constant_name, type_name, PrimitiveEncoding, PrimitiveMapping, TypeIndex, TypeKind, TypeUsage,
+};
+use slicec::definition_types::{
+    BasicEnum, Constant, ConstantValue, EntityInfo, Field, MessageComponent, SliceFile, Struct, Symbol, TypeAlias,
+    VariantEnum,
+};
+
+/// Generates a Rust struct for the provided Slice struct.
+pub fn generate_struct(struct_def: &Struct, file: &SliceFile, types: &TypeIndex) -> Result<String, String> {
+    let name = type_name(&struct_def.entity_info.identifier);
+    let fields = map_fields(&struct_def.fields, file, types)?;
+    let is_totally_ordered = types.are_fields_totally_ordered(&struct_def.fields, file);
+
+    let mut code = doc_comment(&struct_def.entity_info);
+    code += &derive_attribute(is_totally_ordered);
+    code += &format!("pub struct {name} {{\n");
+    for (field, field_def) in fields.iter().zip(&struct_def.fields) {
+        code += &doc_comment(&field_def.entity_info);
+        code += &format!("pub {}: {},\n", field.name, field.usage.rust_type());
+    }
+    code += "}\n\n";
+
+    let bindings = fields.iter().map(FieldMapping::binding).collect::<Vec<_>>().join(", ");
+    let mut encode_body = String::new();
+    if !fields.is_empty() {
+        encode_body += &format!("let {name} {{ {bindings} }} = self;\n");
+    }
+    encode_body += &encode_fields(&fields, struct_def.is_compact);
+    encode_body += "Ok(())\n";
+    code += &encode_into_impl(&name, encode_body);
+    code += "\n";
+
+    let mut decode_body = decode_fields(&fields, struct_def.is_compact);
+    decode_body += &format!("Ok({name} {{ {bindings} }})\n");
+    code += &decode_from_impl(&name, decode_body);
+
+    if let Some(default_impl) = default_impl(&name, struct_def, &fields, types)? {
+        code += "\n";
+        code += &default_impl;
+    }
+    Ok(code)
+}
+
+/// Generates a Rust enum for the provided Slice enum. Checked enums are mapped to Rust enums, but unchecked enums can
+/// hold values that don't correspond to any of their enumerators, so they're mapped to a struct which wraps their
+/// underlying type instead, with an associated constant for each enumerator.
+pub fn generate_basic_enum(enum_def: &BasicEnum) -> Result<String, String> {
+    let name = type_name(&enum_def.entity_info.identifier);
+    let Some(underlying) = PrimitiveMapping::from_keyword(&enum_def.underlying) else {
+        return Err(format!("'{}' isn't a valid underlying type", enum_def.underlying));
+    };
+    let underlying_type = underlying.rust_type;
+    let enumerator_value = |absolute_value: u64, has_negative_value: bool| match has_negative_value {
+        true => format!("-{absolute_value}"),
+        false => absolute_value.to_string(),
+    };
+
+    let mut code = doc_comment(&enum_def.entity_info);
+    code += "#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]\n";
+    if enum_def.is_unchecked {
+        code += &format!("pub struct {name}(pub {underlying_type});\n\n");
+        code += &format!("impl {name} {{\n");
+        for enumerator in &enum_def.enumerators {
+            let value = enumerator_value(enumerator.absolute_value, enumerator.has_negative_value);
+            code += &doc_comment(&enumerator.entity_info);
+            code += &format!(
+                "pub const {}: {name} = {name}({value});\n",
+                constant_name(&enumerator.entity_info.identifier),
+            );
+        }
+        code += "}\n\n";
+    } else {
+        code += &format!("#[repr({underlying_type})]\n");
+        code += &format!("pub enum {name} {{\n");
+        for enumerator in &enum_def.enumerators {
+            let value = enumerator_value(enumerator.absolute_value, enumerator.has_negative_value);
+            code += &doc_comment(&enumerator.entity_info);
+            code += &format!("{} = {value},\n", type_name(&enumerator.entity_info.identifier));
+        }
+        code += "}\n\n";
+    }
+
+    let (encode_method, decode_method) = match underlying.encoding {
+        PrimitiveEncoding::Default => ("encode".to_owned(), format!("decode::<{underlying_type}>")),
+        PrimitiveEncoding::VarInt => (
+            "encode_varint".to_owned(),
+            format!("decode_varint::<{underlying_type}>"),
+        ),
+        PrimitiveEncoding::VarUInt => (
+            "encode_varuint".to_owned(),
+            format!("decode_varuint::<{underlying_type}>"),
+        ),
+    };
+
+    let encode_body = match enum_def.is_unchecked {
+        true => format!("encoder.{encode_method}(self.0)\n"),
+        false => format!("encoder.{encode_method}(*self as {underlying_type})\n"),
+    };
+    code += &encode_into_impl(&name, encode_body);
+    code += "\n";
+
+    let mut decode_body = String::new();
+    if enum_def.is_unchecked {
+        decode_body += &format!("Ok({name}(decoder.{decode_method}()?))\n");
+    } else {
+        decode_body += &format!("match decoder.{decode_method}()? {{\n");
+        for enumerator in &enum_def.enumerators {
+            let value = enumerator_value(enumerator.absolute_value, enumerator.has_negative_value);
+            let enumerator_name = type_name(&enumerator.entity_info.identifier);
+            decode_body += &format!("{value} => Ok({name}::{enumerator_name}),\n");
+        }
+        decode_body += &format!("value => Err({}),\n", illegal_value_error(&name));
+        decode_body += "}\n";
+    }
+    code += &decode_from_impl(&name, decode_body);
+    Ok(code)
+}
+
+/// Generates a Rust enum for the provided Slice enum with fields. Each of its variants is mapped to a struct-like
+/// variant, with a field for each of the variant's fields.
+pub fn generate_variant_enum(enum_def: &VariantEnum, file: &SliceFile, types: &TypeIndex) -> Result<String, String> {
+    let name = type_name(&enum_def.entity_info.identifier);
+    let fields = enum_def.variants.iter().flat_map(|variant| &variant.fields);
+    let is_totally_ordered = types.are_fields_totally_ordered(fields, file);
+    let variants = enum_def
+        .variants
+        .iter()
+        .map(|variant| {
+            let variant_name = type_name(&variant.entity_info.identifier);
+            let fields = map_fields(&variant.fields, file, types)?;
+            let bindings = fields.iter().map(FieldMapping::binding).collect::<Vec<_>>().join(", ");
+            let pattern = match fields.is_empty() {
+                true => format!("{name}::{variant_name}"),
+                false => format!("{name}::{variant_name} {{ {bindings} }}"),
+            };
+            Ok((variant, variant_name, fields, pattern))
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+
+    let mut code = doc_comment(&enum_def.entity_info);
+    code += &derive_attribute(is_totally_ordered);
+    code += &format!("pub enum {name} {{\n");
+    for (variant, variant_name, fields, _) in &variants {
+        code += &doc_comment(&variant.entity_info);
+        if fields.is_empty() {
+            code += &format!("{variant_name},\n");
+        } else {
+            code += &format!("{variant_name} {{\n");
+            for (field, field_def) in fields.iter().zip(&variant.fields) {
+                code += &doc_comment(&field_def.entity_info);
+                code += &format!("{}: {},\n", field.name, field.usage.rust_type());
+            }
+            code += "},\n";
+        }
+    }
+    code += "}\n\n";
+
+    let mut encode_body = "match self {\n".to_owned();
+    for (variant, _, fields, pattern) in &variants {
+        encode_body += &format!("{pattern} => {{\n");
+        encode_body += &format!("encoder.encode_varint({})?;\n", variant.discriminant);
+        encode_body += &encode_fields(fields, enum_def.is_compact);
+        encode_body += "}\n";
+    }
+    encode_body += "}\n";
+    encode_body += "Ok(())\n";
+    code += &encode_into_impl(&name, encode_body);
+    code += "\n";
+
+    let mut decode_body = "match decoder.decode_varint::<i32>()? {\n".to_owned();
+    for (variant, _, fields, pattern) in &variants {
+        decode_body += &format!("{} => {{\n", variant.discriminant);
+        decode_body += &decode_fields(fields, enum_def.is_compact);
+        decode_body += &format!("Ok({pattern})\n");
+        decode_body += "}\n";
+    }
+    decode_body += &format!("value => Err({}),\n", illegal_value_error(&name));
+    decode_body += "}\n";
+    code += &decode_from_impl(&name, decode_body);
+    Ok(code)
+}
+
+/// Generates a Rust type alias for the provided Slice type alias.
+pub fn generate_type_alias(type_alias: &TypeAlias, file: &SliceFile, types: &TypeIndex) -> Result<String, String> {
+    let name = type_name(&type_alias.entity_info.identifier);
+    let underlying_type = types.resolve(&type_alias.underlying_type, file)?.rust_type();
+
+    let mut code = doc_comment(&type_alias.entity_info);
+    code += &format!("pub type {name} = {underlying_type};\n");
+    Ok(code)
+}
+
+/// Generates a Rust constant for the provided Slice constant. String constants are mapped to `&str`, since a `String`
+/// can't be created in a constant expression.
+pub fn generate_constant(constant: &Constant, file: &SliceFile, types: &TypeIndex) -> Result<String, String> {
+    let name = constant_name(&constant.entity_info.identifier);
+    let usage = types.resolve(&constant.data_type, file)?;
+    let rust_type = match &constant.value {
+        ConstantValue::String(_) => "&str".to_owned(),
+        _ => usage.rust_type(),
+    };
+    let value = value_expression(&constant.value, &usage, &constant.data_type.type_id, types)?;
+
+    let mut code = doc_comment(&constant.entity_info);
+    code += &format!("pub const {name}: {rust_type} = {value};\n");
+    Ok(code)
+}
+
+/// Returns the Rust expression for a constant value, which is used with the provided type.
+/// String values are mapped to string literals, which are `&str`s.
+fn value_expression(
+    value: &ConstantValue,
+    usage: &TypeUsage,
+    type_id: &str,
+    types: &TypeIndex,
+) -> Result<String, String> {
+    let expression = match value {
+        ConstantValue::Bool(v) => v.to_string(),
+        ConstantValue::Integer {
+            absolute_value,
+            has_negative_value,
+        } => match has_negative_value {
+            true => format!("-{absolute_value}"),
+            false => absolute_value.to_string(),
+        },
+        ConstantValue::String(v) => format!("{v:?}"),
+        ConstantValue::Enumerator(enumerator_id) => {
+            let TypeKind::UserDefined { type_id, path } = &usage.kind else {
+                return Err(format!("'{type_id}' isn't an enum"));
+            };
+            let enumerator = enumerator_id.rsplit("::").next().unwrap();
+            match types.definition(type_id) {
+                // Unchecked enums are mapped to structs, with an associated constant for each enumerator.
+                Some(Symbol::BasicEnum(enum_def)) if enum_def.is_unchecked => {
+                    format!("{path}::{}", constant_name(enumerator))
+                }
+                _ => format!("{path}::{}", type_name(enumerator)),
+            }
+        }
+    };
+    Ok(expression)
+}
+
+/// Returns an implementation of `Default` for the provided struct, if all of its fields have default values.
+/// Otherwise, there's no way to choose a default for the fields without one, so this returns `None`.
+fn default_impl(
+    name: &str,
+    struct_def: &Struct,
+    fields: &[FieldMapping],
+    types: &TypeIndex,
+) -> Result<Option<String>, String> {
+    if struct_def.fields.is_empty() || struct_def.fields.iter().any(|field| field.default_value.is_none()) {
+        return Ok(None);
+    }
+
+    let mut code = format!("impl Default for {name} {{\n");
+    code += "fn default() -> Self {\n";
+    code += &format!("{name} {{\n");
+    for (field, field_def) in fields.iter().zip(&struct_def.fields) {
+        let default_value = field_def.default_value.as_ref().unwrap(); // We checked that every field has one above.
+        let mut value = value_expression(default_value, &field.usage, &field_def.data_type.type_id, types)?;
+        if let ConstantValue::String(_) = default_value {
+            value += ".to_owned()";
+        }
+        code += &format!("{}: {value},\n", field.name);
+    }
+    code += "}\n";
+    code += "}\n";
+    code += "}\n";
+    Ok(Some(code))
+}
+
+/// Returns an implementation of `EncodeInto` for references to the provided type, with the provided body.
+///
+/// Generated code uses fully qualified paths instead of importing names, since multiple generated files can be included
+/// into the same module (when they're generated from Slice files that declare the same module).
+fn encode_into_impl(type_name: &str, body: String) -> String {
+    let mut code = format!("impl slice_codec::encode_into::EncodeInto for &{type_name} {{\n");
+    code += "fn encode_into(\n";
+    code += "self,\n";
+    code += "encoder: &mut slice_codec::encoder::Encoder<impl slice_codec::buffer::OutputTarget>,\n";
+    code += ") -> slice_codec::Result<()> {\n";
+    code += &body;
+    code += "}\n";
+    code += "}\n";
+    code
+}
+
+/// Returns an implementation of `DecodeFrom` for the provided type, with the provided body.
+fn decode_from_impl(type_name: &str, body: String) -> String {
+    let mut code = format!("impl slice_codec::decode_from::DecodeFrom for {type_name} {{\n");
+    code += "fn decode_from(\n";
+    code += "decoder: &mut slice_codec::decoder::Decoder<impl slice_codec::buffer::InputSource>,\n";
+    code += ") -> slice_codec::Result<Self> {\n";
+    code += &body;
+    code += "}\n";
+    code += "}\n";
+    code
+}
+
+fn map_fields(fields: &[Field], file: &SliceFile, types: &TypeIndex) -> Result<Vec<FieldMapping>, String> {
+    fields
+        .iter()
+        .map(|field| {
+            let usage = types.resolve(&field.data_type, file)?;
+            Ok(FieldMapping::new(&field.entity_info.identifier, usage, field.tag))
+        })
+        .collect()
+}
+
+/// Returns the derive attribute for a generated type. Every type derives `Clone`, `Debug`, and `PartialEq`, and types
+/// that only hold totally ordered values (see [TypeIndex::are_fields_totally_ordered]) also derive `Eq`, `Hash`,
+/// `PartialOrd`, and `Ord`.
+fn derive_attribute(is_totally_ordered: bool) -> String {
+    let mut traits = vec!["Clone", "Debug", "PartialEq"];
+    if is_totally_ordered {
+        traits.extend(["Eq", "Hash", "PartialOrd", "Ord"]);
+    }
+    format!("#[derive({})]\n", traits.join(", "))
+}
+
+/// Returns the doc comment for an entity, generated from the overview of its Slice doc comment.
+/// Links to other Slice entities are written as code, since they might not have been mapped to Rust.
+fn doc_comment(entity_info: &EntityInfo) -> String {
+    let Some(comment) = &entity_info.comment else {
+        return String::new();
+    };
+
+    let mut overview = String::new();
+    for component in &comment.overview {
+        match component {
+            MessageComponent::Text(text) => overview += text,
+            MessageComponent::Link(entity_id) => overview += &format!("`{entity_id}`"),
+        }
+    }
+
+    let lines = overview.trim().lines().map(|line| match line.trim_end() {
+        "" => "///\n".to_owned(),
+        line => format!("/// {}\n", line.trim_start()),
+    });
+    lines.collect()
+}
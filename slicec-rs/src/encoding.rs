@@ -0,0 +1,359 @@
+// Copyright (c) ZeroC, Inc.
+
+//! This module generates the code which encodes and decodes values with `slice-codec`.
+//!
+//! The generated code follows the same conventions as slicec's hand-written definition types:
+//! - The optional (non-tagged) fields of a type are tracked by a bit-sequence, which is encoded before its fields.
+//! - Tagged fields are encoded after the other fields (in order of increasing tag), each prefixed by its tag and size.
+//! - Non-compact types end with the tag end marker (`-1` encoded as a var-int). Compact types have no end marker.
+//! - Enums with fields encode their variant's discriminant (as a var-int), followed by the variant's fields.
+//!
+//! All the generated code is formatted without indentation, which is added afterwards by [crate::indent].
+
+use crate::type_mapping::{field_name, PrimitiveEncoding, TypeKind, TypeUsage};
+
+/// The tag end marker, which must be encoded at the end of every non-compact type.
+const TAG_END_MARKER: &str = "-1";
+
+/// The names of the local variables that generated code can use in the same scope as the fields being encoded or
+/// decoded. Fields with these names are bound to local variables with an underscore appended to their names instead.
+const RESERVED_LOCALS: &[&str] = &["encoder", "decoder", "bit_sequence"];
+
+/// The Rust mapping of a field, which holds everything needed to encode and decode it.
+pub struct FieldMapping {
+    /// The name of the field in the generated Rust type.
+    pub name: String,
+    /// The name of the local variable that the field is bound to in generated encoding and decoding code.
+    pub local: String,
+    pub usage: TypeUsage,
+    pub tag: Option<i32>,
+}
+
+impl FieldMapping {
+    pub fn new(identifier: &str, usage: TypeUsage, tag: Option<i32>) -> Self {
+        let name = field_name(identifier);
+        let local = match RESERVED_LOCALS.contains(&name.as_str()) {
+            true => format!("{name}_"),
+            false => name.clone(),
+        };
+        FieldMapping { name, local, usage, tag }
+    }
+
+    /// Returns the pattern that binds this field to its local variable (ex: `value` or `encoder: encoder_`).
+    pub fn binding(&self) -> String {
+        match self.name == self.local {
+            true => self.name.clone(),
+            false => format!("{}: {}", self.name, self.local),
+        }
+    }
+}
+
+/// Returns the statements which encode the provided fields, in the order they're encoded on the wire.
+/// Each field must be bound to a local variable (holding a reference to the field's value) before these statements.
+pub fn encode_fields(fields: &[FieldMapping], is_compact: bool) -> String {
+    let mut code = String::new();
+    let (tagged_fields, untagged_fields) = partition_fields(fields);
+
+    // Encode the bit-sequence, with a bit for each optional field, that's set if the field has a value.
+    let optional_fields = untagged_fields.iter().filter(|field| field.usage.is_optional);
+    let bit_sequence_size = optional_fields.clone().count().div_ceil(8);
+    if bit_sequence_size > 0 {
+        code += &format!("let mut bit_sequence = [0u8; {bit_sequence_size}];\n");
+        for (index, field) in optional_fields.enumerate() {
+            let (byte, bit) = (index / 8, index % 8);
+            code += &format!(
+                "if {}.is_some() {{ bit_sequence[{byte}] |= 1 << {bit}; }}\n",
+                field.local
+            );
+        }
+        code += "encoder.write_bytes_exact(&bit_sequence)?;\n";
+    }
+
+    for field in untagged_fields {
+        let local = &field.local;
+        match field.usage.is_optional {
+            true => {
+                code += &format!(
+                    "if let Some(value) = {local} {{\n{}}}\n",
+                    encode_required_value(&field.usage, "value")
+                )
+            }
+            false => code += &encode_value(&field.usage, local),
+        }
+    }
+
+    // Each tagged field is encoded into a separate buffer first, so that its size can be encoded before it.
+    for field in tagged_fields {
+        let tag = field.tag.unwrap();
+        code += &format!("if let Some(value) = {} {{\n", field.local);
+        code += &format!("encoder.encode_varint({tag})?;\n");
+        code += "let mut buffer: Vec<u8> = Vec::new();\n";
+        code += "{\n";
+        code += "let mut encoder = slice_codec::encoder::Encoder::from(&mut buffer);\n";
+        let encode_value = encode_required_value(&field.usage, "value");
+        // Unlike the encoder that's passed to `encode_into`, this encoder's output target is a concrete type, so its
+        // trait must be in scope for its methods to be called.
+        if encode_value.contains("encoder.write_bytes_exact(") {
+            code += "use slice_codec::buffer::OutputTarget as _;\n";
+        }
+        code += &encode_value;
+        code += "}\n";
+        code += "encoder.encode_size(buffer.len())?;\n";
+        code += "encoder.write_bytes_exact(&buffer)?;\n";
+        code += "}\n";
+    }
+
+    if !is_compact {
+        code += &format!("encoder.encode_varint({TAG_END_MARKER})?;\n");
+    }
+    code
+}
+
+/// Returns the statements which decode the provided fields, binding each of them to its local variable.
+pub fn decode_fields(fields: &[FieldMapping], is_compact: bool) -> String {
+    let mut code = String::new();
+    let (tagged_fields, untagged_fields) = partition_fields(fields);
+
+    let optional_count = untagged_fields.iter().filter(|field| field.usage.is_optional).count();
+    let bit_sequence_size = optional_count.div_ceil(8);
+    if bit_sequence_size > 0 {
+        code += &format!("let bit_sequence = *decoder.read_bytes_exact::<{bit_sequence_size}>()?;\n");
+    }
+
+    let mut optional_index = 0;
+    for field in untagged_fields {
+        let local = &field.local;
+        if field.usage.is_optional {
+            let (byte, bit) = (optional_index / 8, optional_index % 8);
+            let value = decode_required_value(&field.usage);
+            code += &format!(
+                "let {local} = if bit_sequence[{byte}] & (1 << {bit}) != 0 {{ Some({value}) }} else {{ None }};\n"
+            );
+            optional_index += 1;
+        } else {
+            code += &format!("let {local} = {};\n", decode_value(&field.usage));
+        }
+    }
+
+    // Tagged fields can appear in any order, and any tagged fields that we don't know about are skipped.
+    for field in &tagged_fields {
+        code += &format!("let mut {} = None;\n", field.local);
+    }
+    if !is_compact {
+        if tagged_fields.is_empty() {
+            code += "decoder.skip_tagged_fields()?;\n";
+        } else {
+            code += "loop {\n";
+            code += "let tag = decoder.decode_varint::<i32>()?;\n";
+            code += &format!("if tag == {TAG_END_MARKER} {{ break; }}\n");
+            code += "let size = decoder.decode_size()?;\n";
+            code += "match tag {\n";
+            for field in &tagged_fields {
+                let (tag, local) = (field.tag.unwrap(), &field.local);
+                code += &format!("{tag} => {local} = Some({}),\n", decode_required_value(&field.usage));
+            }
+            code += "_ => { decoder.read_byte_slice_exact(size)?; }\n";
+            code += "}\n";
+            code += "}\n";
+        }
+    }
+    code
+}
+
+/// Splits the provided fields into tagged fields (sorted by tag) and untagged fields (in definition order).
+fn partition_fields(fields: &[FieldMapping]) -> (Vec<&FieldMapping>, Vec<&FieldMapping>) {
+    let (mut tagged_fields, untagged_fields): (Vec<_>, Vec<_>) = fields.iter().partition(|field| field.tag.is_some());
+    tagged_fields.sort_by_key(|field| field.tag);
+    (tagged_fields, untagged_fields)
+}
+
+/// Returns the statements which encode the value of `expression` (a reference to a value of the provided type).
+/// Optional values which aren't part of a bit-sequence are encoded as a bool, followed by the value (if it's set).
+pub fn encode_value(usage: &TypeUsage, expression: &str) -> String {
+    match usage.is_optional {
+        true => format!(
+            "encoder.encode({expression}.is_some())?;\nif let Some(value) = {expression} {{\n{}}}\n",
+            encode_required_value(usage, "value"),
+        ),
+        false => encode_required_value(usage, expression),
+    }
+}
+
+/// Returns the statements which encode the value of `expression`, ignoring whether the provided type is optional
+/// (`expression` must be a reference to the type's non-optional value).
+fn encode_required_value(usage: &TypeUsage, expression: &str) -> String {
+    let required_usage = TypeUsage {
+        is_optional: false,
+        ..usage.clone()
+    };
+    if required_usage.is_natively_encodable() {
+        return format!("encoder.encode({expression})?;\n");
+    }
+
+    match &usage.kind {
+        TypeKind::Primitive(mapping) => match mapping.encoding {
+            PrimitiveEncoding::VarInt => format!("encoder.encode_varint(*{expression})?;\n"),
+            PrimitiveEncoding::VarUInt => format!("encoder.encode_varuint(*{expression})?;\n"),
+            PrimitiveEncoding::Default => unreachable!("primitives with default encodings are natively encodable"),
+        },
+
+        TypeKind::Sequence(element) => {
+            let mut code = format!("encoder.encode_size({expression}.len())?;\n");
+            if element.is_optional {
+                code += &encode_bit_sequence(&format!("{expression}.iter()"), expression, "element");
+                code += &format!(
+                    "for element in {expression}.iter().flatten() {{\n{}}}\n",
+                    encode_required_value(element, "element"),
+                );
+            } else {
+                code += &format!(
+                    "for element in {expression} {{\n{}}}\n",
+                    encode_required_value(element, "element"),
+                );
+            }
+            code
+        }
+
+        TypeKind::Dictionary(key, value) => {
+            let mut code = format!("encoder.encode_size({expression}.len())?;\n");
+            if value.is_optional {
+                code += &encode_bit_sequence(&format!("{expression}.values()"), expression, "value");
+            }
+            code += &format!("for (key, value) in {expression} {{\n");
+            code += &encode_required_value(key, "key");
+            match value.is_optional {
+                true => code += &format!("if let Some(value) = value {{\n{}}}\n", encode_required_value(value, "value")),
+                false => code += &encode_required_value(value, "value"),
+            }
+            code += "}\n";
+            code
+        }
+
+        TypeKind::Result(success, failure) => format!(
+            "match {expression} {{\nOk(value) => {{\nencoder.encode_varuint(0u32)?;\n{}}}\nErr(value) => {{\nencoder.encode_varuint(1u32)?;\n{}}}\n}}\n",
+            encode_value(success, "value"),
+            encode_value(failure, "value"),
+        ),
+
+        TypeKind::UserDefined { .. } => unreachable!("user-defined types are natively encodable"),
+    }
+}
+
+/// Returns the statements which encode a bit-sequence for a collection of optional values, with a bit for each value,
+/// that's set if the value is set. `iterator` must be an iterator over the optional values in the collection.
+fn encode_bit_sequence(iterator: &str, collection: &str, item: &str) -> String {
+    let mut code = format!("let mut bit_sequence = vec![0u8; {collection}.len().div_ceil(8)];\n");
+    code += &format!("for (index, {item}) in {iterator}.enumerate() {{\n");
+    code += &format!("if {item}.is_some() {{ bit_sequence[index / 8] |= 1 << (index % 8); }}\n");
+    code += "}\n";
+    code += "encoder.write_bytes_exact(&bit_sequence)?;\n";
+    code
+}
+
+/// Returns an expression which decodes a value of the provided type.
+/// Optional values which aren't part of a bit-sequence are decoded as a bool, followed by the value (if it's set).
+pub fn decode_value(usage: &TypeUsage) -> String {
+    match usage.is_optional {
+        true => format!(
+            "if decoder.decode::<bool>()? {{ Some({}) }} else {{ None }}",
+            decode_required_value(usage),
+        ),
+        false => decode_required_value(usage),
+    }
+}
+
+/// Returns an expression which decodes a value of the provided type, ignoring whether the type is optional.
+fn decode_required_value(usage: &TypeUsage) -> String {
+    let required_usage = TypeUsage {
+        is_optional: false,
+        ..usage.clone()
+    };
+    if required_usage.is_natively_encodable() {
+        return format!("decoder.decode::<{}>()?", required_usage.rust_type());
+    }
+
+    match &usage.kind {
+        TypeKind::Primitive(mapping) => match mapping.encoding {
+            PrimitiveEncoding::VarInt => format!("decoder.decode_varint::<{}>()?", mapping.rust_type),
+            PrimitiveEncoding::VarUInt => format!("decoder.decode_varuint::<{}>()?", mapping.rust_type),
+            PrimitiveEncoding::Default => unreachable!("primitives with default encodings are natively encodable"),
+        },
+
+        TypeKind::Sequence(element) => {
+            let mut code = "{\n".to_owned();
+            code += "let length = decoder.decode_size()?;\n";
+            code += &decode_bit_sequence(element);
+            code += "let mut elements = Vec::new();\n";
+            code += &format!("for {} in 0..length {{\n", loop_variable(element));
+            code += &format!("elements.push({});\n", decode_bit_sequence_value(element));
+            code += "}\n";
+            code += "elements\n";
+            code += "}";
+            code
+        }
+
+        TypeKind::Dictionary(key, value) => {
+            let mut code = "{\n".to_owned();
+            code += "let length = decoder.decode_size()?;\n";
+            code += &decode_bit_sequence(value);
+            code += "let mut entries = std::collections::BTreeMap::new();\n";
+            code += &format!("for {} in 0..length {{\n", loop_variable(value));
+            code += &format!("let key = {};\n", decode_required_value(key));
+            code += &format!("let value = {};\n", decode_bit_sequence_value(value));
+            code += "entries.insert(key, value);\n";
+            code += "}\n";
+            code += "entries\n";
+            code += "}";
+            code
+        }
+
+        TypeKind::Result(success, failure) => {
+            let mut code = "match decoder.decode_varuint::<u32>()? {\n".to_owned();
+            code += &format!("0 => Ok({}),\n", decode_value(success));
+            code += &format!("1 => Err({}),\n", decode_value(failure));
+            code += &format!("value => return Err({}),\n", illegal_value_error("Result"));
+            code += "}";
+            code
+        }
+
+        TypeKind::UserDefined { .. } => unreachable!("user-defined types are natively encodable"),
+    }
+}
+
+/// Returns the statement which decodes the bit-sequence for a collection, if its items are optional.
+/// This must be preceded by a statement which decodes the collection's length into `length`.
+fn decode_bit_sequence(item: &TypeUsage) -> String {
+    match item.is_optional {
+        true => "let bit_sequence = decoder.read_byte_slice_exact(length.div_ceil(8))?.to_vec();\n".to_owned(),
+        false => String::new(),
+    }
+}
+
+/// Returns the variable to use for the loop which decodes a collection's items. The index is only needed to check the
+/// collection's bit-sequence, so it's only declared if the items are optional.
+fn loop_variable(item: &TypeUsage) -> &'static str {
+    match item.is_optional {
+        true => "index",
+        false => "_",
+    }
+}
+
+/// Returns an expression which decodes an item of a collection. If the item is optional, its value is only decoded
+/// if its bit is set in the collection's bit-sequence (at the position of the current `index`).
+fn decode_bit_sequence_value(item: &TypeUsage) -> String {
+    match item.is_optional {
+        true => format!(
+            "if bit_sequence[index / 8] & (1 << (index % 8)) != 0 {{ Some({}) }} else {{ None }}",
+            decode_required_value(item),
+        ),
+        false => decode_required_value(item),
+    }
+}
+
+/// Returns an expression which creates an `IllegalValue` error for the type with the provided name.
+/// The illegal value must be bound to a local variable named `value`.
+pub fn illegal_value_error(type_name: &str) -> String {
+    format!(
+        "slice_codec::InvalidDataErrorKind::IllegalValue {{ desc: \"{type_name}\", value: Some(value.into()) }}.into()"
+    )
+}
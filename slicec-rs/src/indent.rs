@@ -0,0 +1,34 @@
+// Copyright (c) ZeroC, Inc.
+
+//! This module indents generated code. Code is generated without any indentation, which is then added by
+//! [indent_code], based on how deeply nested within braces and parentheses each line is.
+
+/// Indents each line of the provided code by 4 spaces for each brace or parenthesis that it's nested in.
+///
+/// This only works for code where a line only closes delimiters that it opened, except for delimiters at the very start
+/// of the line, which is true of all the code that we generate. Comments aren't checked for delimiters.
+pub fn indent_code(code: &str) -> String {
+    let mut indented = String::with_capacity(code.len());
+    let mut depth: usize = 0;
+
+    for line in code.lines() {
+        let line = line.trim();
+        if line.is_empty() {
+            indented.push('\n');
+            continue;
+        }
+        if line.starts_with("//") {
+            indented += &format!("{}{line}\n", "    ".repeat(depth));
+            continue;
+        }
+
+        let opening_delimiters = line.matches(['{', '(']).count();
+        let closing_delimiters = line.matches(['}', ')']).count();
+        let leading_closing_delimiters = line.chars().take_while(|c| matches!(c, '}' | ')')).count();
+
+        let line_depth = depth.saturating_sub(leading_closing_delimiters);
+        indented += &format!("{}{line}\n", "    ".repeat(line_depth));
+        depth = (depth + opening_delimiters).saturating_sub(closing_delimiters);
+    }
+    indented
+}
@@ -0,0 +1,126 @@
+// Copyright (c) ZeroC, Inc.
+
+//! A Slice code-generator for Rust, which maps Slice types to Rust types, and implements `EncodeInto` and `DecodeFrom`
+//! for them, on top of the `slice-codec` crate.
+//!
+//! It generates a Rust file for each Slice source file, named after the Slice file (ex: 'MyTypes.slice' generates
+//! 'my_types.rs'). Each of these files must be included in a Rust module that corresponds to the Slice module it
+//! declares (ex: definitions in `module Foo::Bar` must be included in a module at `crate::foo::bar`), since types
+//! which are defined in other modules are referenced by their full path. A different root than `crate` can be
+//! specified with the `root` argument (ex: '-G rs,root=crate::generated').
+//!
+//! Slice types are mapped to Rust as follows:
+//! - Structs are mapped to structs, with a public field for each of their fields. Structs whose fields all have default
+//!   values also implement `Default`, using those values.
+//! - Enums are mapped to enums with explicit discriminants. Unchecked enums are mapped to a struct which wraps their
+//!   underlying type instead, with an associated constant for each enumerator.
+//! - Enums with fields are mapped to enums, with a struct-like variant for each of their enumerators.
+//! - Type aliases are mapped to type aliases.
+//! - Constants are mapped to constants, except for string constants, which are mapped to `&str` constants.
+//! - Custom types are referenced by name, but must be implemented by hand (including `EncodeInto` and `DecodeFrom`).
+//! - `Sequence<T>` is mapped to `Vec<T>`, `Dictionary<K, V>` to `BTreeMap<K, V>`, `Result<S, F>` to `Result<S, F>`, and
+//!   optional types (`T?`) to `Option<T>`.
+//!
+//! Interfaces aren't mapped to anything, since this code-generator only handles the encoding of types.
+
+mod definitions;
+mod encoding;
+mod indent;
+mod type_mapping;
+
+use crate::definitions::{
+    generate_basic_enum, generate_constant, generate_struct, generate_type_alias, generate_variant_enum,
+};
+use crate::indent::indent_code;
+use crate::type_mapping::{module_name, TypeIndex};
+use slicec::code_generator::CodeGenerator;
+use slicec::definition_types::{CompilationContext, Diagnostic, DiagnosticLevel, GeneratedFile, SliceFile, Symbol};
+use std::path::Path;
+
+/// The Rust path that generated modules are nested under, if no root is specified.
+const DEFAULT_ROOT: &str = "crate";
+
+/// The Rust code-generator. It can be run in-process, or as a plugin through the 'slicec-rs' executable.
+#[derive(Debug, Default)]
+pub struct RustGenerator;
+
+impl CodeGenerator for RustGenerator {
+    fn name(&self) -> &str {
+        "rs"
+    }
+
+    fn generate_code(
+        &self,
+        source_files: &[SliceFile],
+        reference_files: &[SliceFile],
+        _context: &CompilationContext,
+        args: &[(String, String)],
+    ) -> (Vec<GeneratedFile>, Vec<Diagnostic>) {
+        let mut root = DEFAULT_ROOT.to_owned();
+        let mut diagnostics = Vec::new();
+        for (key, value) in args {
+            match key.as_str() {
+                "root" => root = value.clone(),
+                _ => diagnostics.push(Diagnostic {
+                    level: DiagnosticLevel::Warning,
+                    message: format!("unknown argument '{key}' was ignored"),
+                    source: None,
+                }),
+            }
+        }
+
+        let types = TypeIndex::new(source_files.iter().chain(reference_files), root);
+        let mut generated_files = Vec::new();
+        for file in source_files {
+            generated_files.push(GeneratedFile {
+                path: output_path(file),
+                contents: generate_file(file, &types, &mut diagnostics),
+            });
+        }
+        (generated_files, diagnostics)
+    }
+}
+
+/// Returns the path of the Rust file generated for the provided Slice file (ex: 'MyTypes.slice' -> 'my_types.rs').
+fn output_path(file: &SliceFile) -> String {
+    let file_stem = Path::new(&file.path).file_stem().and_then(|stem| stem.to_str());
+    format!("{}.rs", module_name(file_stem.unwrap_or(&file.path)))
+}
+
+/// Generates the Rust code for all the types defined in the provided Slice file.
+/// Definitions which can't be mapped to Rust (because the file isn't well-formed) are skipped, and an error is reported
+/// for each of them.
+fn generate_file(file: &SliceFile, types: &TypeIndex, diagnostics: &mut Vec<Diagnostic>) -> String {
+    let mut definitions = Vec::new();
+    for symbol in &file.contents {
+        let (result, entity_info) = match symbol {
+            Symbol::Struct(v) => (generate_struct(v, file, types), &v.entity_info),
+            Symbol::BasicEnum(v) => (generate_basic_enum(v), &v.entity_info),
+            Symbol::VariantEnum(v) => (generate_variant_enum(v, file, types), &v.entity_info),
+            Symbol::TypeAlias(v) => (generate_type_alias(v, file, types), &v.entity_info),
+            Symbol::Constant(v) => (generate_constant(v, file, types), &v.entity_info),
+            _ => continue,
+        };
+        match result {
+            Ok(definition) => definitions.push(definition),
+            Err(message) => diagnostics.push(Diagnostic {
+                level: DiagnosticLevel::Error,
+                message,
+                source: Some(format!(
+                    "{}::{}",
+                    file.module_declaration.identifier, entity_info.identifier
+                )),
+            }),
+        }
+    }
+
+    let mut code = format!(
+        "// Generated by slicec-rs from '{}' (module {}). Do not edit this file by hand.\n",
+        file.path, file.module_declaration.identifier,
+    );
+    for definition in definitions {
+        code += "\n";
+        code += &definition;
+    }
+    indent_code(&code)
+}
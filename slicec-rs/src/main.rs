@@ -0,0 +1,25 @@
+// Copyright (c) ZeroC, Inc.
+
+//! The entry point of the 'slicec-rs' plugin, which slicec runs when passed '-G rs'.
+//!
+//! The communication with slicec is handled by `slicec-plugin`, which calls [RustGenerator] to generate the code.
+
+use slicec::code_generator::CodeGenerator;
+use slicec_plugin::Response;
+use slicec_rs::RustGenerator;
+use std::process::ExitCode;
+
+fn main() -> ExitCode {
+    slicec_plugin::run(|request| {
+        let (generated_files, diagnostics) = RustGenerator.generate_code(
+            &request.source_files,
+            &request.reference_files,
+            &request.context,
+            &request.args,
+        );
+        Response {
+            generated_files,
+            diagnostics,
+        }
+    })
+}
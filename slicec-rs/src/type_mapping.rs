@@ -0,0 +1,296 @@
+// Copyright (c) ZeroC, Inc.
+
+//! This module maps Slice types to Rust types, and Slice identifiers to Rust identifiers.
+
+use convert_case::{Case, Casing};
+use slicec::definition_types::{Field, SliceFile, Symbol, TypeRef};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Describes how a primitive type is encoded.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PrimitiveEncoding {
+    /// The primitive is encoded with its `EncodeInto` implementation (fixed-size numbers, bools, and strings).
+    Default,
+    /// The primitive is encoded as a variable-length signed integer.
+    VarInt,
+    /// The primitive is encoded as a variable-length unsigned integer.
+    VarUInt,
+}
+
+/// The Rust mapping of a Slice primitive type.
+#[derive(Clone, Copy, Debug)]
+pub struct PrimitiveMapping {
+    pub rust_type: &'static str,
+    pub encoding: PrimitiveEncoding,
+}
+
+impl PrimitiveMapping {
+    /// Returns the mapping of the primitive type with the provided keyword, or `None` if it isn't a primitive type.
+    pub fn from_keyword(keyword: &str) -> Option<Self> {
+        let (rust_type, encoding) = match keyword {
+            "bool" => ("bool", PrimitiveEncoding::Default),
+            "int8" => ("i8", PrimitiveEncoding::Default),
+            "uint8" => ("u8", PrimitiveEncoding::Default),
+            "int16" => ("i16", PrimitiveEncoding::Default),
+            "uint16" => ("u16", PrimitiveEncoding::Default),
+            "int32" => ("i32", PrimitiveEncoding::Default),
+            "uint32" => ("u32", PrimitiveEncoding::Default),
+            "varint32" => ("i32", PrimitiveEncoding::VarInt),
+            "varuint32" => ("u32", PrimitiveEncoding::VarUInt),
+            "int64" => ("i64", PrimitiveEncoding::Default),
+            "uint64" => ("u64", PrimitiveEncoding::Default),
+            "varint62" => ("i64", PrimitiveEncoding::VarInt),
+            "varuint62" => ("u64", PrimitiveEncoding::VarUInt),
+            "float32" => ("f32", PrimitiveEncoding::Default),
+            "float64" => ("f64", PrimitiveEncoding::Default),
+            "string" => ("String", PrimitiveEncoding::Default),
+            _ => return None,
+        };
+        Some(PrimitiveMapping { rust_type, encoding })
+    }
+
+    /// Returns true if this primitive is a floating point type, which can't implement `Eq`, `Hash`, or `Ord`.
+    pub fn is_float(&self) -> bool {
+        matches!(self.rust_type, "f32" | "f64")
+    }
+}
+
+/// A fully resolved use of a Slice type, with anonymous types replaced by their definitions.
+#[derive(Clone, Debug)]
+pub struct TypeUsage {
+    pub kind: TypeKind,
+    pub is_optional: bool,
+}
+
+#[derive(Clone, Debug)]
+pub enum TypeKind {
+    Primitive(PrimitiveMapping),
+    Sequence(Box<TypeUsage>),
+    Dictionary(Box<TypeUsage>, Box<TypeUsage>),
+    Result(Box<TypeUsage>, Box<TypeUsage>),
+    /// A struct, enum, or custom type, holding its fully scoped Slice identifier, and the Rust path to it.
+    UserDefined {
+        type_id: String,
+        path: String,
+    },
+}
+
+impl TypeUsage {
+    /// Returns the Rust type that this type is mapped to.
+    pub fn rust_type(&self) -> String {
+        let rust_type = match &self.kind {
+            TypeKind::Primitive(mapping) => mapping.rust_type.to_owned(),
+            TypeKind::Sequence(element) => format!("Vec<{}>", element.rust_type()),
+            TypeKind::Dictionary(key, value) => {
+                format!("std::collections::BTreeMap<{}, {}>", key.rust_type(), value.rust_type())
+            }
+            TypeKind::Result(success, failure) => format!("Result<{}, {}>", success.rust_type(), failure.rust_type()),
+            TypeKind::UserDefined { path, .. } => path.clone(),
+        };
+        match self.is_optional {
+            true => format!("Option<{rust_type}>"),
+            false => rust_type,
+        }
+    }
+
+    /// Returns true if values of this type can be encoded and decoded with `slice-codec`'s own implementations
+    /// (through `Encoder::encode` and `Decoder::decode`), without any generated code.
+    ///
+    /// This is the case for non-optional fixed-size primitives, strings, and user-defined types (which have generated
+    /// implementations), and sequences and dictionaries of them. Var-ints need to be encoded explicitly, since they're
+    /// represented by the same Rust types as fixed-size integers.
+    pub fn is_natively_encodable(&self) -> bool {
+        if self.is_optional {
+            return false;
+        }
+        match &self.kind {
+            TypeKind::Primitive(mapping) => mapping.encoding == PrimitiveEncoding::Default,
+            TypeKind::Sequence(element) => element.is_natively_encodable(),
+            TypeKind::Dictionary(key, value) => key.is_natively_encodable() && value.is_natively_encodable(),
+            TypeKind::Result(..) => false,
+            TypeKind::UserDefined { .. } => true,
+        }
+    }
+}
+
+/// An index of all the user-defined types in a set of Slice files, which is used to resolve type IDs.
+pub struct TypeIndex<'a> {
+    /// Map of the fully scoped identifiers of structs, enums, and custom types, to their definitions (and the files
+    /// that they're defined in).
+    definitions: BTreeMap<String, (&'a Symbol, &'a SliceFile)>,
+
+    /// The Rust path that generated modules are nested under (ex: `crate`).
+    root_path: String,
+}
+
+impl<'a> TypeIndex<'a> {
+    pub fn new(files: impl IntoIterator<Item = &'a SliceFile>, root_path: String) -> Self {
+        let mut definitions = BTreeMap::new();
+        for file in files {
+            let module = &file.module_declaration.identifier;
+            for symbol in &file.contents {
+                let identifier = match symbol {
+                    Symbol::Struct(v) => &v.entity_info.identifier,
+                    Symbol::BasicEnum(v) => &v.entity_info.identifier,
+                    Symbol::VariantEnum(v) => &v.entity_info.identifier,
+                    Symbol::CustomType(v) => &v.entity_info.identifier,
+                    _ => continue,
+                };
+                definitions.insert(format!("{module}::{identifier}"), (symbol, file));
+            }
+        }
+        TypeIndex { definitions, root_path }
+    }
+
+    /// Returns the definition of the struct, enum, or custom type with the provided fully scoped identifier.
+    pub fn definition(&self, type_id: &str) -> Option<&'a Symbol> {
+        self.definitions.get(type_id).map(|(symbol, _)| *symbol)
+    }
+
+    /// Resolves the provided type reference, which appears in the provided file.
+    /// If it refers to an anonymous type which doesn't exist, this returns a message describing the problem instead.
+    pub fn resolve(&self, type_ref: &TypeRef, file: &SliceFile) -> Result<TypeUsage, String> {
+        let kind = self.resolve_type_id(&type_ref.type_id, file)?;
+        Ok(TypeUsage {
+            kind,
+            is_optional: type_ref.is_optional,
+        })
+    }
+
+    fn resolve_type_id(&self, type_id: &str, file: &SliceFile) -> Result<TypeKind, String> {
+        // Anonymous types are referenced by their index in the contents of the file they're used in.
+        if let Ok(index) = type_id.parse::<usize>() {
+            return match file.contents.get(index) {
+                Some(Symbol::SequenceType(v)) => Ok(TypeKind::Sequence(Box::new(self.resolve(&v.element_type, file)?))),
+                Some(Symbol::DictionaryType(v)) => {
+                    let key = self.resolve(&v.key_type, file)?;
+                    let value = self.resolve(&v.value_type, file)?;
+                    Ok(TypeKind::Dictionary(Box::new(key), Box::new(value)))
+                }
+                Some(Symbol::ResultType(v)) => {
+                    let success = self.resolve(&v.success_type, file)?;
+                    let failure = self.resolve(&v.failure_type, file)?;
+                    Ok(TypeKind::Result(Box::new(success), Box::new(failure)))
+                }
+                _ => Err(format!(
+                    "type ID '{type_id}' in '{}' doesn't refer to an anonymous type",
+                    file.path
+                )),
+            };
+        }
+
+        if let Some(mapping) = PrimitiveMapping::from_keyword(type_id) {
+            return Ok(TypeKind::Primitive(mapping));
+        }
+
+        let path = self.rust_path(type_id, &file.module_declaration.identifier);
+        Ok(TypeKind::UserDefined {
+            type_id: type_id.to_owned(),
+            path,
+        })
+    }
+
+    /// Returns the Rust path of the type with the provided fully scoped identifier, relative to the current module.
+    /// Types in the current module are referenced by name, and types in other modules are referenced by their full
+    /// path, under the root path (ex: `Foo::Bar::MyStruct` is mapped to `crate::foo::bar::MyStruct`).
+    fn rust_path(&self, type_id: &str, current_module: &str) -> String {
+        let (module, identifier) = type_id.rsplit_once("::").unwrap_or(("", type_id));
+        let type_name = type_name(identifier);
+        if module == current_module {
+            return type_name;
+        }
+
+        let mut path = self.root_path.clone();
+        for segment in module.split("::") {
+            path.push_str("::");
+            path.push_str(&module_name(segment));
+        }
+        format!("{path}::{type_name}")
+    }
+
+    /// Returns true if all the provided fields (which are defined in the provided file) have types which implement
+    /// `Eq`, `Hash`, and `Ord`. This is the case unless they contain a floating point number, or a custom type (which
+    /// we know nothing about).
+    pub fn are_fields_totally_ordered<'b>(
+        &self,
+        fields: impl IntoIterator<Item = &'b Field>,
+        file: &SliceFile,
+    ) -> bool {
+        self.are_fields_totally_ordered_impl(fields, file, &mut BTreeSet::new())
+    }
+
+    fn are_fields_totally_ordered_impl<'b>(
+        &self,
+        fields: impl IntoIterator<Item = &'b Field>,
+        file: &SliceFile,
+        visited: &mut BTreeSet<String>,
+    ) -> bool {
+        // Fields whose types can't be resolved are reported when they're mapped, so we only need to skip them here.
+        fields.into_iter().all(|field| {
+            let usage = self.resolve(&field.data_type, file);
+            usage.is_ok_and(|usage| self.is_totally_ordered(&usage, visited))
+        })
+    }
+
+    fn is_totally_ordered(&self, usage: &TypeUsage, visited: &mut BTreeSet<String>) -> bool {
+        match &usage.kind {
+            TypeKind::Primitive(mapping) => !mapping.is_float(),
+            TypeKind::Sequence(element) => self.is_totally_ordered(element, visited),
+            TypeKind::Dictionary(key, value) | TypeKind::Result(key, value) => {
+                self.is_totally_ordered(key, visited) && self.is_totally_ordered(value, visited)
+            }
+            TypeKind::UserDefined { type_id, .. } => {
+                // Types which are already being checked (because they're recursive) don't affect the result.
+                if !visited.insert(type_id.clone()) {
+                    return true;
+                }
+
+                match self.definitions.get(type_id) {
+                    Some((Symbol::Struct(v), file)) => self.are_fields_totally_ordered_impl(&v.fields, file, visited),
+                    Some((Symbol::VariantEnum(v), file)) => {
+                        let fields = v.variants.iter().flat_map(|variant| &variant.fields);
+                        self.are_fields_totally_ordered_impl(fields, file, visited)
+                    }
+                    Some((Symbol::BasicEnum(_), _)) => true,
+                    _ => false,
+                }
+            }
+        }
+    }
+}
+
+/// Returns the Rust name for a type (struct, enum, or type alias) with the provided Slice identifier.
+pub fn type_name(identifier: &str) -> String {
+    escape_keyword(identifier.to_case(Case::Pascal))
+}
+
+/// Returns the Rust name for a field with the provided Slice identifier.
+pub fn field_name(identifier: &str) -> String {
+    escape_keyword(identifier.to_case(Case::Snake))
+}
+
+/// Returns the Rust name for an associated constant (used for the enumerators of unchecked enums).
+pub fn constant_name(identifier: &str) -> String {
+    escape_keyword(identifier.to_case(Case::UpperSnake))
+}
+
+/// Returns the Rust name for a module with the provided Slice identifier.
+pub fn module_name(identifier: &str) -> String {
+    escape_keyword(identifier.to_case(Case::Snake))
+}
+
+/// Escapes the provided identifier if it's a Rust keyword, by making it a raw identifier (ex: `r#type`).
+/// The few keywords that can't be raw identifiers are escaped by appending an underscore instead.
+fn escape_keyword(identifier: String) -> String {
+    const KEYWORDS: &[&str] = &[
+        "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "do", "dyn", "else", "enum",
+        "extern", "false", "final", "fn", "for", "gen", "if", "impl", "in", "let", "loop", "macro", "match", "mod",
+        "move", "mut", "override", "priv", "pub", "ref", "return", "static", "struct", "trait", "true", "try", "type",
+        "typeof", "unsafe", "unsized", "use", "virtual", "where", "while", "yield",
+    ];
+    match identifier.as_str() {
+        "crate" | "self" | "Self" | "super" => identifier + "_",
+        _ if KEYWORDS.contains(&identifier.as_str()) => format!("r#{identifier}"),
+        _ => identifier,
+    }
+}
@@ -0,0 +1,255 @@
+// Copyright (c) ZeroC, Inc.
+
+use slicec::code_generator::CodeGenerator;
+use slicec::compile_from_strings;
+use slicec::definition_types::{CompilationContext, Diagnostic, DiagnosticLevel, GeneratedFile, SliceFile, Symbol};
+use slicec_rs::RustGenerator;
+use test_case::test_case;
+
+/// Compiles the provided Slice files, and runs the Rust code-generator on them with the provided arguments.
+fn generate(slice: &[&str], args: &[(&str, &str)]) -> (Vec<GeneratedFile>, Vec<Diagnostic>) {
+    generate_from_files(&compile(slice), args)
+}
+
+/// Compiles the provided Slice files, and returns them in the form that's sent to code-generators.
+fn compile(slice: &[&str]) -> Vec<SliceFile> {
+    let compilation_state = compile_from_strings(slice, None);
+    assert!(!compilation_state.diagnostics.has_errors());
+    compilation_state.files.iter().map(SliceFile::from).collect()
+}
+
+/// Runs the Rust code-generator on the provided source files, with the provided arguments.
+fn generate_from_files(source_files: &[SliceFile], args: &[(&str, &str)]) -> (Vec<GeneratedFile>, Vec<Diagnostic>) {
+    let context = CompilationContext {
+        compiler_version: String::new(),
+        defined_symbols: Vec::new(),
+        absolute_paths: Default::default(),
+        output_dir: String::new(),
+    };
+    let args = args
+        .iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect::<Vec<_>>();
+    RustGenerator.generate_code(source_files, &[], &context, &args)
+}
+
+#[test]
+fn a_file_is_generated_for_each_slice_file() {
+    // Arrange
+    let slice1 = "module Test";
+    let slice2 = "module Test";
+
+    // Act
+    let (generated_files, diagnostics) = generate(&[slice1, slice2], &[]);
+
+    // Assert
+    let paths = generated_files
+        .iter()
+        .map(|file| file.path.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(paths, ["string_0.rs", "string_1.rs"]);
+    assert!(diagnostics.is_empty());
+}
+
+#[test]
+fn optional_fields_are_encoded_in_a_bit_sequence_and_tagged_fields_are_encoded_last() {
+    // Arrange
+    let slice = "
+        module Test
+        struct S {
+            tag(2) b: string?
+            a: int32?
+            c: varuint62
+        }
+    ";
+
+    // Act
+    let (generated_files, _) = generate(&[slice], &[]);
+
+    // Assert
+    let contents = &generated_files[0].contents;
+    let expected = "
+        let S { b, a, c } = self;
+        let mut bit_sequence = [0u8; 1];
+        if a.is_some() { bit_sequence[0] |= 1 << 0; }
+        encoder.write_bytes_exact(&bit_sequence)?;
+        if let Some(value) = a {
+            encoder.encode(value)?;
+        }
+        encoder.encode_varuint(*c)?;
+        if let Some(value) = b {
+            encoder.encode_varint(2)?;";
+    assert!(trim_lines(contents).contains(&trim_lines(expected)), "{contents}");
+    assert!(contents.contains("pub a: Option<i32>,"));
+    assert!(contents.contains("2 => b = Some(decoder.decode::<String>()?),"));
+}
+
+#[test]
+fn compact_structs_have_no_tag_end_marker() {
+    // Arrange
+    let slice = "
+        module Test
+        compact struct Point { x: int32, y: int32 }
+    ";
+
+    // Act
+    let (generated_files, _) = generate(&[slice], &[]);
+
+    // Assert
+    let contents = &generated_files[0].contents;
+    assert!(!contents.contains("encoder.encode_varint(-1)?;"));
+    assert!(!contents.contains("decoder.skip_tagged_fields()?;"));
+    assert!(contents.contains("#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]"));
+}
+
+#[test]
+fn unchecked_enums_are_mapped_to_structs() {
+    // Arrange
+    let slice = "
+        module Test
+        unchecked enum E : varint32 { FirstValue = -1, SecondValue = 2 }
+    ";
+
+    // Act
+    let (generated_files, _) = generate(&[slice], &[]);
+
+    // Assert
+    let contents = &generated_files[0].contents;
+    assert!(contents.contains("pub struct E(pub i32);"));
+    assert!(contents.contains("pub const FIRST_VALUE: E = E(-1);"));
+    assert!(contents.contains("encoder.encode_varint(self.0)"));
+}
+
+#[test]
+fn constants_are_mapped_to_rust_constants() {
+    // Arrange
+    let slice = "
+        module Test
+        enum E : uint8 { A, B }
+        unchecked enum U : uint8 { C }
+        const MaxPageSize: uint32 = 500
+        const Offset: int64 = -5
+        const Enabled: bool = true
+        const Greeting: string = \"hello \\\"world\\\"\"
+        const DefaultE: E = E::B
+        const DefaultU: U = C
+    ";
+
+    // Act
+    let (generated_files, _) = generate(&[slice], &[]);
+
+    // Assert
+    let contents = &generated_files[0].contents;
+    assert!(contents.contains("pub const MAX_PAGE_SIZE: u32 = 500;"));
+    assert!(contents.contains("pub const OFFSET: i64 = -5;"));
+    assert!(contents.contains("pub const ENABLED: bool = true;"));
+    assert!(contents.contains(r#"pub const GREETING: &str = "hello \"world\"";"#));
+    assert!(contents.contains("pub const DEFAULT_E: E = E::B;"));
+    assert!(contents.contains("pub const DEFAULT_U: U = U::C;"));
+}
+
+#[test]
+fn structs_whose_fields_all_have_default_values_implement_default() {
+    // Arrange
+    let slice = "
+        module Test
+        enum E : uint8 { A, B }
+        unchecked enum U : uint8 { C }
+        struct Settings {
+            pageSize: uint32 = 500
+            offset: int64 = -5
+            enabled: bool = true
+            greeting: string = \"hello\"
+            e: E = E::B
+            u: U = C
+        }
+        struct Partial {
+            a: int32 = 1
+            b: int32
+        }
+    ";
+
+    // Act
+    let (generated_files, diagnostics) = generate(&[slice], &[]);
+
+    // Assert
+    let contents = &generated_files[0].contents;
+    let expected = "
+        impl Default for Settings {
+        fn default() -> Self {
+        Settings {
+        page_size: 500,
+        offset: -5,
+        enabled: true,
+        greeting: \"hello\".to_owned(),
+        e: E::B,
+        u: U::C,
+        }
+        }
+        }";
+    assert!(trim_lines(contents).contains(&trim_lines(expected)), "{contents}");
+    assert!(!contents.contains("impl Default for Partial"));
+    assert!(diagnostics.is_empty());
+}
+
+#[test]
+fn types_in_other_modules_are_referenced_by_their_full_path() {
+    // Arrange
+    let slice1 = "
+        module Foo::Bar
+        struct S { f: float64 }
+    ";
+    let slice2 = "
+        module Test
+        struct T { s: Foo::Bar::S }
+    ";
+
+    // Act
+    let (generated_files, _) = generate(&[slice1, slice2], &[("root", "crate::generated")]);
+
+    // Assert
+    let contents = &generated_files[1].contents;
+    assert!(contents.contains("pub s: crate::generated::foo::bar::S,"));
+
+    // Neither struct can derive `Eq`, since they both (indirectly) hold a floating point number.
+    assert!(contents.contains("#[derive(Clone, Debug, PartialEq)]"));
+}
+
+#[test]
+fn unknown_arguments_are_reported() {
+    // Arrange
+    let slice = "module Test";
+
+    // Act
+    let (_, diagnostics) = generate(&[slice], &[("foo", "bar")]);
+
+    // Assert
+    assert_eq!(diagnostics.len(), 1);
+    assert!(matches!(diagnostics[0].level, DiagnosticLevel::Warning));
+    assert_eq!(diagnostics[0].message, "unknown argument 'foo' was ignored");
+}
+
+#[test_case("99"; "past_the_end")]
+#[test_case("1"; "non_anonymous")]
+fn invalid_anonymous_type_ids_are_reported(type_id: &str) {
+    // Arrange
+    let mut source_files = compile(&["module Test\nstruct S { x: Sequence<int32> }\nstruct T {}"]);
+    let Symbol::Struct(struct_def) = &mut source_files[0].contents[1] else { unreachable!() };
+    struct_def.fields[0].data_type.type_id = type_id.to_owned();
+
+    // Act
+    let (generated_files, diagnostics) = generate_from_files(&source_files, &[]);
+
+    // Assert
+    assert_eq!(diagnostics.len(), 1);
+    assert!(matches!(diagnostics[0].level, DiagnosticLevel::Error));
+    let expected = format!("type ID '{type_id}' in 'string-0' doesn't refer to an anonymous type");
+    assert_eq!(diagnostics[0].message, expected);
+    assert_eq!(diagnostics[0].source.as_deref(), Some("Test::S"));
+    assert!(generated_files[0].contents.contains("pub struct T"));
+}
+
+/// Trims the whitespace from the start and end of each line, so generated code can be compared without indentation.
+fn trim_lines(s: &str) -> String {
+    s.trim().lines().map(str::trim).collect::<Vec<_>>().join("\n")
+}
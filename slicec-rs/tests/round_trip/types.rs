@@ -0,0 +1,368 @@
+// Generated by slicec-rs from 'tests/round_trip/types.slice' (module Test::RoundTrip). Do not edit this file by hand.
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point {
+    pub x: i32,
+    pub y: i32,
+}
+
+impl slice_codec::encode_into::EncodeInto for &Point {
+    fn encode_into(
+        self,
+        encoder: &mut slice_codec::encoder::Encoder<impl slice_codec::buffer::OutputTarget>,
+    ) -> slice_codec::Result<()> {
+        let Point { x, y } = self;
+        encoder.encode(x)?;
+        encoder.encode(y)?;
+        encoder.encode_varint(-1)?;
+        Ok(())
+    }
+}
+
+impl slice_codec::decode_from::DecodeFrom for Point {
+    fn decode_from(
+        decoder: &mut slice_codec::decoder::Decoder<impl slice_codec::buffer::InputSource>,
+    ) -> slice_codec::Result<Self> {
+        let x = decoder.decode::<i32>()?;
+        let y = decoder.decode::<i32>()?;
+        decoder.skip_tagged_fields()?;
+        Ok(Point { x, y })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Size {
+    pub width: u16,
+    pub height: u16,
+}
+
+impl slice_codec::encode_into::EncodeInto for &Size {
+    fn encode_into(
+        self,
+        encoder: &mut slice_codec::encoder::Encoder<impl slice_codec::buffer::OutputTarget>,
+    ) -> slice_codec::Result<()> {
+        let Size { width, height } = self;
+        encoder.encode(width)?;
+        encoder.encode(height)?;
+        Ok(())
+    }
+}
+
+impl slice_codec::decode_from::DecodeFrom for Size {
+    fn decode_from(
+        decoder: &mut slice_codec::decoder::Decoder<impl slice_codec::buffer::InputSource>,
+    ) -> slice_codec::Result<Self> {
+        let width = decoder.decode::<u16>()?;
+        let height = decoder.decode::<u16>()?;
+        Ok(Size { width, height })
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[repr(u8)]
+pub enum Color {
+    Red = 0,
+    Green = 5,
+    Blue = 6,
+}
+
+impl slice_codec::encode_into::EncodeInto for &Color {
+    fn encode_into(
+        self,
+        encoder: &mut slice_codec::encoder::Encoder<impl slice_codec::buffer::OutputTarget>,
+    ) -> slice_codec::Result<()> {
+        encoder.encode(*self as u8)
+    }
+}
+
+impl slice_codec::decode_from::DecodeFrom for Color {
+    fn decode_from(
+        decoder: &mut slice_codec::decoder::Decoder<impl slice_codec::buffer::InputSource>,
+    ) -> slice_codec::Result<Self> {
+        match decoder.decode::<u8>()? {
+            0 => Ok(Color::Red),
+            5 => Ok(Color::Green),
+            6 => Ok(Color::Blue),
+            value => Err(slice_codec::InvalidDataErrorKind::IllegalValue { desc: "Color", value: Some(value.into()) }.into()),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Flags(pub i32);
+
+impl Flags {
+    pub const NONE: Flags = Flags(0);
+    pub const FIRST: Flags = Flags(1);
+    pub const SECOND: Flags = Flags(2);
+}
+
+impl slice_codec::encode_into::EncodeInto for &Flags {
+    fn encode_into(
+        self,
+        encoder: &mut slice_codec::encoder::Encoder<impl slice_codec::buffer::OutputTarget>,
+    ) -> slice_codec::Result<()> {
+        encoder.encode(self.0)
+    }
+}
+
+impl slice_codec::decode_from::DecodeFrom for Flags {
+    fn decode_from(
+        decoder: &mut slice_codec::decoder::Decoder<impl slice_codec::buffer::InputSource>,
+    ) -> slice_codec::Result<Self> {
+        Ok(Flags(decoder.decode::<i32>()?))
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Shape {
+    Circle {
+        radius: f64,
+    },
+    Rectangle {
+        top_left: Point,
+        size: Size,
+    },
+    Empty,
+}
+
+impl slice_codec::encode_into::EncodeInto for &Shape {
+    fn encode_into(
+        self,
+        encoder: &mut slice_codec::encoder::Encoder<impl slice_codec::buffer::OutputTarget>,
+    ) -> slice_codec::Result<()> {
+        match self {
+            Shape::Circle { radius } => {
+                encoder.encode_varint(0)?;
+                encoder.encode(radius)?;
+                encoder.encode_varint(-1)?;
+            }
+            Shape::Rectangle { top_left, size } => {
+                encoder.encode_varint(1)?;
+                encoder.encode(top_left)?;
+                encoder.encode(size)?;
+                encoder.encode_varint(-1)?;
+            }
+            Shape::Empty => {
+                encoder.encode_varint(2)?;
+                encoder.encode_varint(-1)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl slice_codec::decode_from::DecodeFrom for Shape {
+    fn decode_from(
+        decoder: &mut slice_codec::decoder::Decoder<impl slice_codec::buffer::InputSource>,
+    ) -> slice_codec::Result<Self> {
+        match decoder.decode_varint::<i32>()? {
+            0 => {
+                let radius = decoder.decode::<f64>()?;
+                decoder.skip_tagged_fields()?;
+                Ok(Shape::Circle { radius })
+            }
+            1 => {
+                let top_left = decoder.decode::<Point>()?;
+                let size = decoder.decode::<Size>()?;
+                decoder.skip_tagged_fields()?;
+                Ok(Shape::Rectangle { top_left, size })
+            }
+            2 => {
+                decoder.skip_tagged_fields()?;
+                Ok(Shape::Empty)
+            }
+            value => Err(slice_codec::InvalidDataErrorKind::IllegalValue { desc: "Shape", value: Some(value.into()) }.into()),
+        }
+    }
+}
+
+pub type Points = Vec<Point>;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Everything {
+    pub name: String,
+    pub nickname: Option<String>,
+    pub age: Option<u8>,
+    pub favorite_color: Option<Color>,
+    pub score: Option<i64>,
+    pub color: Color,
+    pub flags: Flags,
+    pub shape: Shape,
+    pub points: Vec<Point>,
+    pub lookup: std::collections::BTreeMap<String, Option<Point>>,
+    pub outcome: Result<Size, String>,
+    pub bytes: Vec<u8>,
+    pub nested: Vec<Vec<bool>>,
+}
+
+impl slice_codec::encode_into::EncodeInto for &Everything {
+    fn encode_into(
+        self,
+        encoder: &mut slice_codec::encoder::Encoder<impl slice_codec::buffer::OutputTarget>,
+    ) -> slice_codec::Result<()> {
+        let Everything { name, nickname, age, favorite_color, score, color, flags, shape, points, lookup, outcome, bytes, nested } = self;
+        let mut bit_sequence = [0u8; 1];
+        if nickname.is_some() { bit_sequence[0] |= 1 << 0; }
+        if age.is_some() { bit_sequence[0] |= 1 << 1; }
+        encoder.write_bytes_exact(&bit_sequence)?;
+        encoder.encode(name)?;
+        if let Some(value) = nickname {
+            encoder.encode(value)?;
+        }
+        if let Some(value) = age {
+            encoder.encode(value)?;
+        }
+        encoder.encode(color)?;
+        encoder.encode(flags)?;
+        encoder.encode(shape)?;
+        encoder.encode(points)?;
+        encoder.encode_size(lookup.len())?;
+        let mut bit_sequence = vec![0u8; lookup.len().div_ceil(8)];
+        for (index, value) in lookup.values().enumerate() {
+            if value.is_some() { bit_sequence[index / 8] |= 1 << (index % 8); }
+        }
+        encoder.write_bytes_exact(&bit_sequence)?;
+        for (key, value) in lookup {
+            encoder.encode(key)?;
+            if let Some(value) = value {
+                encoder.encode(value)?;
+            }
+        }
+        match outcome {
+            Ok(value) => {
+                encoder.encode_varuint(0u32)?;
+                encoder.encode(value)?;
+            }
+            Err(value) => {
+                encoder.encode_varuint(1u32)?;
+                encoder.encode(value)?;
+            }
+        }
+        encoder.encode(bytes)?;
+        encoder.encode(nested)?;
+        if let Some(value) = favorite_color {
+            encoder.encode_varint(1)?;
+            let mut buffer: Vec<u8> = Vec::new();
+            {
+                let mut encoder = slice_codec::encoder::Encoder::from(&mut buffer);
+                encoder.encode(value)?;
+            }
+            encoder.encode_size(buffer.len())?;
+            encoder.write_bytes_exact(&buffer)?;
+        }
+        if let Some(value) = score {
+            encoder.encode_varint(10)?;
+            let mut buffer: Vec<u8> = Vec::new();
+            {
+                let mut encoder = slice_codec::encoder::Encoder::from(&mut buffer);
+                encoder.encode_varint(*value)?;
+            }
+            encoder.encode_size(buffer.len())?;
+            encoder.write_bytes_exact(&buffer)?;
+        }
+        encoder.encode_varint(-1)?;
+        Ok(())
+    }
+}
+
+impl slice_codec::decode_from::DecodeFrom for Everything {
+    fn decode_from(
+        decoder: &mut slice_codec::decoder::Decoder<impl slice_codec::buffer::InputSource>,
+    ) -> slice_codec::Result<Self> {
+        let bit_sequence = *decoder.read_bytes_exact::<1>()?;
+        let name = decoder.decode::<String>()?;
+        let nickname = if bit_sequence[0] & (1 << 0) != 0 { Some(decoder.decode::<String>()?) } else { None };
+        let age = if bit_sequence[0] & (1 << 1) != 0 { Some(decoder.decode::<u8>()?) } else { None };
+        let color = decoder.decode::<Color>()?;
+        let flags = decoder.decode::<Flags>()?;
+        let shape = decoder.decode::<Shape>()?;
+        let points = decoder.decode::<Vec<Point>>()?;
+        let lookup = {
+            let length = decoder.decode_size()?;
+            let bit_sequence = decoder.read_byte_slice_exact(length.div_ceil(8))?.to_vec();
+            let mut entries = std::collections::BTreeMap::new();
+            for index in 0..length {
+                let key = decoder.decode::<String>()?;
+                let value = if bit_sequence[index / 8] & (1 << (index % 8)) != 0 { Some(decoder.decode::<Point>()?) } else { None };
+                entries.insert(key, value);
+            }
+            entries
+        };
+        let outcome = match decoder.decode_varuint::<u32>()? {
+            0 => Ok(decoder.decode::<Size>()?),
+            1 => Err(decoder.decode::<String>()?),
+            value => return Err(slice_codec::InvalidDataErrorKind::IllegalValue { desc: "Result", value: Some(value.into()) }.into()),
+        };
+        let bytes = decoder.decode::<Vec<u8>>()?;
+        let nested = decoder.decode::<Vec<Vec<bool>>>()?;
+        let mut favorite_color = None;
+        let mut score = None;
+        loop {
+            let tag = decoder.decode_varint::<i32>()?;
+            if tag == -1 { break; }
+            let size = decoder.decode_size()?;
+            match tag {
+                1 => favorite_color = Some(decoder.decode::<Color>()?),
+                10 => score = Some(decoder.decode_varint::<i64>()?),
+                _ => { decoder.read_byte_slice_exact(size)?; }
+            }
+        }
+        Ok(Everything { name, nickname, age, favorite_color, score, color, flags, shape, points, lookup, outcome, bytes, nested })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Defaults {
+    pub count: u32,
+    pub offset: i16,
+    pub enabled: bool,
+    pub greeting: String,
+    pub color: Color,
+    pub flags: Flags,
+}
+
+impl slice_codec::encode_into::EncodeInto for &Defaults {
+    fn encode_into(
+        self,
+        encoder: &mut slice_codec::encoder::Encoder<impl slice_codec::buffer::OutputTarget>,
+    ) -> slice_codec::Result<()> {
+        let Defaults { count, offset, enabled, greeting, color, flags } = self;
+        encoder.encode(count)?;
+        encoder.encode(offset)?;
+        encoder.encode(enabled)?;
+        encoder.encode(greeting)?;
+        encoder.encode(color)?;
+        encoder.encode(flags)?;
+        encoder.encode_varint(-1)?;
+        Ok(())
+    }
+}
+
+impl slice_codec::decode_from::DecodeFrom for Defaults {
+    fn decode_from(
+        decoder: &mut slice_codec::decoder::Decoder<impl slice_codec::buffer::InputSource>,
+    ) -> slice_codec::Result<Self> {
+        let count = decoder.decode::<u32>()?;
+        let offset = decoder.decode::<i16>()?;
+        let enabled = decoder.decode::<bool>()?;
+        let greeting = decoder.decode::<String>()?;
+        let color = decoder.decode::<Color>()?;
+        let flags = decoder.decode::<Flags>()?;
+        decoder.skip_tagged_fields()?;
+        Ok(Defaults { count, offset, enabled, greeting, color, flags })
+    }
+}
+
+impl Default for Defaults {
+    fn default() -> Self {
+        Defaults {
+            count: 30,
+            offset: -1,
+            enabled: true,
+            greeting: "hello".to_owned(),
+            color: Color::Blue,
+            flags: Flags::SECOND,
+        }
+    }
+}
@@ -0,0 +1,191 @@
+// Copyright (c) ZeroC, Inc.
+
+//! These tests compile the code that slicec-rs generates for 'round_trip/types.slice' (which is checked in as
+//! 'round_trip/types.rs'), and check that values of the generated types survive being encoded and then decoded.
+
+use slice_codec::buffer::InputSource;
+use slice_codec::decoder::Decoder;
+use slice_codec::encoder::Encoder;
+use slicec::code_generator::CodeGenerator;
+use slicec::compile_from_options;
+use slicec::definition_types::{CompilationContext, SliceFile};
+use slicec::slice_options::SliceOptions;
+use slicec_rs::RustGenerator;
+use std::collections::BTreeMap;
+use test::round_trip::*;
+use test_case::test_case;
+
+// Definitions in 'module Test::RoundTrip' must be included at 'crate::test::round_trip'.
+mod test {
+    pub mod round_trip {
+        include!("round_trip/types.rs");
+    }
+}
+
+const SLICE_PATH: &str = "tests/round_trip/types.slice";
+const GENERATED_PATH: &str = "tests/round_trip/types.rs";
+
+#[test]
+fn generated_code_is_up_to_date() {
+    // Arrange
+    let options = SliceOptions {
+        sources: vec![SLICE_PATH.to_owned()],
+        ..SliceOptions::default()
+    };
+    let compilation_state = compile_from_options(&options);
+    assert!(!compilation_state.diagnostics.has_errors());
+    let source_files = compilation_state.files.iter().map(SliceFile::from).collect::<Vec<_>>();
+    let context = CompilationContext {
+        compiler_version: String::new(),
+        defined_symbols: Vec::new(),
+        absolute_paths: Default::default(),
+        output_dir: String::new(),
+    };
+
+    // Act
+    let (generated_files, diagnostics) = RustGenerator.generate_code(&source_files, &[], &context, &[]);
+
+    // Assert
+    assert!(diagnostics.is_empty());
+    let generated_code = &generated_files[0].contents;
+    if std::env::var_os("UPDATE_GENERATED_CODE").is_some() {
+        std::fs::write(GENERATED_PATH, generated_code).unwrap();
+    }
+    let checked_in_code = std::fs::read_to_string(GENERATED_PATH).unwrap_or_default();
+    assert!(
+        *generated_code == checked_in_code,
+        "'{GENERATED_PATH}' is out of date, run the tests with 'UPDATE_GENERATED_CODE=1' to regenerate it",
+    );
+}
+
+/// Encodes the provided value, decodes it again, and checks that the decoded value is equal to the original,
+/// and that the whole encoded buffer was consumed in the process.
+macro_rules! assert_round_trips {
+    ($value:expr) => {{
+        let value = $value;
+        let mut buffer: Vec<u8> = Vec::new();
+        Encoder::from(&mut buffer).encode(&value).unwrap();
+
+        let mut decoder = Decoder::from(&buffer);
+        let decoded = decoder.decode().unwrap();
+        assert_eq!(value, decoded);
+        assert_eq!(decoder.remaining(), 0);
+    }};
+}
+
+/// Returns an `Everything` with all of its optional and tagged fields unset, and all other fields empty or zeroed.
+fn create_everything() -> Everything {
+    Everything {
+        name: "everything".to_owned(),
+        nickname: None,
+        age: None,
+        favorite_color: None,
+        score: None,
+        color: Color::Red,
+        flags: Flags::NONE,
+        shape: Shape::Empty,
+        points: Points::new(),
+        lookup: BTreeMap::new(),
+        outcome: Ok(Size { width: 0, height: 0 }),
+        bytes: Vec::new(),
+        nested: Vec::new(),
+    }
+}
+
+#[test]
+fn structs_round_trip() {
+    assert_round_trips!(Point { x: -7, y: i32::MAX });
+}
+
+#[test]
+fn compact_structs_round_trip() {
+    assert_round_trips!(Size { width: 640, height: 480 });
+}
+
+#[test_case(Color::Red; "first enumerator")]
+#[test_case(Color::Green; "explicit value")]
+#[test_case(Color::Blue; "implicit value after explicit value")]
+fn enums_round_trip(color: Color) {
+    assert_round_trips!(color);
+}
+
+#[test]
+fn unchecked_enums_round_trip_unknown_values() {
+    assert_round_trips!(Flags::SECOND);
+    assert_round_trips!(Flags(79));
+}
+
+#[test]
+fn enums_with_unknown_values_fail_to_decode() {
+    // Arrange
+    let mut buffer: Vec<u8> = Vec::new();
+    Encoder::from(&mut buffer).encode(3u8).unwrap();
+
+    // Act
+    let result = Decoder::from(buffer.as_slice()).decode::<Color>();
+
+    // Assert
+    assert!(result.is_err());
+}
+
+#[test_case(Shape::Circle { radius: 1.5 }; "enumerator with field")]
+#[test_case(Shape::Rectangle { top_left: Point { x: 1, y: 2 }, size: Size { width: 3, height: 4 } }; "enumerator with struct fields")]
+#[test_case(Shape::Empty; "enumerator without fields")]
+fn variant_enums_round_trip(shape: Shape) {
+    assert_round_trips!(shape);
+}
+
+#[test]
+fn unset_optional_and_tagged_fields_round_trip() {
+    assert_round_trips!(create_everything());
+}
+
+#[test]
+fn set_optional_and_tagged_fields_round_trip() {
+    assert_round_trips!(Everything {
+        nickname: Some("all".to_owned()),
+        age: Some(42),
+        favorite_color: Some(Color::Blue),
+        score: Some(-1_000_000_000_000),
+        ..create_everything()
+    });
+}
+
+#[test]
+fn collections_and_results_round_trip() {
+    let lookup = [
+        ("origin".to_owned(), Some(Point { x: 0, y: 0 })),
+        ("nowhere".to_owned(), None),
+    ];
+    assert_round_trips!(Everything {
+        color: Color::Green,
+        flags: Flags::FIRST,
+        shape: Shape::Circle { radius: -0.25 },
+        points: vec![Point { x: 1, y: 1 }, Point { x: 2, y: 4 }],
+        lookup: BTreeMap::from(lookup),
+        outcome: Err("failure".to_owned()),
+        bytes: vec![0, 1, 255],
+        nested: vec![vec![true, false], Vec::new()],
+        ..create_everything()
+    });
+}
+
+#[test]
+fn structs_with_default_values_round_trip() {
+    // Arrange
+    let expected = Defaults {
+        count: 30,
+        offset: -1,
+        enabled: true,
+        greeting: "hello".to_owned(),
+        color: Color::Blue,
+        flags: Flags::SECOND,
+    };
+
+    // Act
+    let defaults = Defaults::default();
+
+    // Assert
+    assert_eq!(defaults, expected);
+    assert_round_trips!(defaults);
+}
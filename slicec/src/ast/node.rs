@@ -82,7 +82,7 @@ macro_rules! generate_node_enum {
 // generate the `Node` enum with variants for every type allowed to be in the AST.
 generate_node_enum! {
     Module, Struct, Field, Interface, Operation, Parameter, Enum,
-    Enumerator, CustomType, TypeAlias, ResultType, Sequence, Dictionary, Primitive, Attribute
+    Enumerator, CustomType, TypeAlias, Constant, ResultType, Sequence, Dictionary, Primitive, Attribute
 }
 
 impl<'a> TryFrom<&'a Node> for WeakPtr<dyn Type> {
@@ -129,6 +129,7 @@ impl<'a> TryFrom<&'a Node> for &'a dyn Entity {
             Node::Enumerator(enumerator_ptr) => Ok(enumerator_ptr.borrow()),
             Node::CustomType(custom_type_ptr) => Ok(custom_type_ptr.borrow()),
             Node::TypeAlias(type_alias_ptr) => Ok(type_alias_ptr.borrow()),
+            Node::Constant(constant_ptr) => Ok(constant_ptr.borrow()),
             _ => Err(LookupError::TypeMismatch {
                 expected: "entity".to_owned(),
                 actual: ccase!(lower, node.to_string()),
@@ -163,6 +164,7 @@ impl_into_node_for!(Enum);
 impl_into_node_for!(Enumerator);
 impl_into_node_for!(CustomType);
 impl_into_node_for!(TypeAlias);
+impl_into_node_for!(Constant);
 impl_into_node_for!(ResultType);
 impl_into_node_for!(Sequence);
 impl_into_node_for!(Dictionary);
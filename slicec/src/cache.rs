@@ -0,0 +1,374 @@
+// Copyright (c) ZeroC, Inc.
+
+//! This module implements the generation cache, which lets slicec skip running a code-generator when its inputs haven't
+//! changed since the last time it ran, and the files it generated are still intact.
+//!
+//! A code-generator's inputs are hashed into a key, which covers the exact payload that would be sent to it (which
+//! holds the Slice files, the compilation context, and its arguments), and the contents of its executable. After it
+//! runs successfully, its key is stored in the cache, along with the hash of each file it generated, and any
+//! diagnostics it reported. If its key is unchanged the next time slicec runs, and all of its files still have the same
+//! hashes, its response is reconstructed from the files on disk, instead of running it again.
+//!
+//! The cache also stores each plugin that passed its handshake (see [crate::handshake]), along with the hash of its
+//! executable, so plugins aren't described again on every run of slicec, only after their executable changes.
+
+use crate::definition_types::{Diagnostic, GeneratedFile};
+use crate::handshake::PROTOCOL_VERSION;
+use crate::slice_options::SliceOptions;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::io::{ErrorKind, Result};
+use std::path::{Path, PathBuf};
+
+/// The name of the cache file that slicec stores in the cache directory.
+const CACHE_FILE_NAME: &str = ".slicec-cache";
+
+/// A cache of the responses that code-generators returned the last time they ran successfully.
+#[derive(Debug, Default, Deserialize, Serialize)]
+pub struct Cache {
+    /// Map of code-generator names to the entry describing their last successful run.
+    #[serde(default)]
+    entries: BTreeMap<String, CacheEntry>,
+
+    /// Map of the paths of plugins to the entry describing their last successful handshake.
+    #[serde(default)]
+    handshakes: BTreeMap<String, HandshakeEntry>,
+
+    /// The path of the file this cache is stored in.
+    #[serde(skip)]
+    path: PathBuf,
+}
+
+/// Describes the last successful run of a code-generator.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct CacheEntry {
+    /// The hash of the code-generator's inputs.
+    key: String,
+
+    /// Map of the paths of the files that the code-generator generated (as it returned them) to their hashes.
+    files: BTreeMap<String, String>,
+
+    /// The diagnostics that the code-generator reported.
+    diagnostics: Vec<Diagnostic>,
+}
+
+/// Describes the last successful handshake with a plugin.
+#[derive(Debug, Deserialize, Serialize)]
+struct HandshakeEntry {
+    /// The hash of the plugin's executable.
+    executable_hash: String,
+
+    /// The version of the code-generator protocol that the plugin used.
+    protocol_version: u32,
+}
+
+impl Cache {
+    /// Loads the cache that's stored in the provided directory.
+    /// If no cache exists, or it can't be read, this returns an empty cache instead, since a cache is only an
+    /// optimization; the worst that can happen without it is that code-generators are run when they didn't need to be.
+    pub fn load(cache_dir: &Path) -> Self {
+        let path = cache_dir.join(CACHE_FILE_NAME);
+        let contents = std::fs::read(&path).ok();
+        let mut cache = contents
+            .and_then(|contents| serde_json::from_slice::<Cache>(&contents).ok())
+            .unwrap_or_default();
+        cache.path = path;
+        cache
+    }
+
+    /// Returns the path of this cache's file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Writes this cache to its file, creating the cache directory if necessary.
+    /// If the cache is empty, any existing cache file is deleted instead.
+    pub fn save(&self) -> Result<()> {
+        if self.entries.is_empty() && self.handshakes.is_empty() {
+            return match std::fs::remove_file(&self.path) {
+                Err(error) if error.kind() != ErrorKind::NotFound => Err(error),
+                _ => Ok(()),
+            };
+        }
+
+        if let Some(parent_dir) = self.path.parent() {
+            std::fs::create_dir_all(parent_dir)?;
+        }
+        let mut contents = serde_json::to_string_pretty(self)?;
+        contents.push('\n');
+        std::fs::write(&self.path, contents)
+    }
+
+    /// Returns the response that the specified code-generator returned the last time it ran, if its key is unchanged,
+    /// and all the files it generated still exist (in the provided output directory) with unchanged contents.
+    pub fn get(
+        &self,
+        generator_name: &str,
+        key: &str,
+        output_dir: &Path,
+    ) -> Option<(Vec<GeneratedFile>, Vec<Diagnostic>)> {
+        let entry = self.entries.get(generator_name).filter(|entry| entry.key == key)?;
+
+        let mut generated_files = Vec::new();
+        for (path, hash) in &entry.files {
+            let contents = std::fs::read_to_string(output_dir.join(path)).ok()?;
+            if hash_bytes(contents.as_bytes()) != *hash {
+                return None;
+            }
+            let path = path.clone();
+            generated_files.push(GeneratedFile { path, contents });
+        }
+        Some((generated_files, entry.diagnostics.clone()))
+    }
+
+    /// Stores an entry for the specified code-generator, replacing any entry that was previously stored for it.
+    pub fn insert(&mut self, generator_name: &str, entry: CacheEntry) {
+        self.entries.insert(generator_name.to_owned(), entry);
+    }
+
+    /// Removes the entry for the specified code-generator from this cache.
+    pub fn remove(&mut self, generator_name: &str) {
+        self.entries.remove(generator_name);
+    }
+
+    /// Removes all the entries and handshakes from this cache.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.handshakes.clear();
+    }
+
+    /// Returns true if the plugin at the provided path passed its last handshake, and its executable still has the
+    /// provided hash.
+    pub fn has_handshake(&self, plugin_path: &str, executable_hash: &str) -> bool {
+        self.handshakes
+            .get(plugin_path)
+            .is_some_and(|entry| entry.executable_hash == executable_hash && entry.protocol_version == PROTOCOL_VERSION)
+    }
+
+    /// Stores that the plugin at the provided path passed its handshake, while its executable had the provided hash.
+    pub fn insert_handshake(&mut self, plugin_path: &str, executable_hash: String) {
+        let entry = HandshakeEntry {
+            executable_hash,
+            protocol_version: PROTOCOL_VERSION,
+        };
+        self.handshakes.insert(plugin_path.to_owned(), entry);
+    }
+}
+
+impl CacheEntry {
+    /// Creates an entry describing a code-generator's response, which was returned for the provided key.
+    pub fn new(key: String, generated_files: &[GeneratedFile], diagnostics: &[Diagnostic]) -> Self {
+        let files = generated_files
+            .iter()
+            .map(|file| (file.path.clone(), hash_bytes(file.contents.as_bytes())))
+            .collect();
+        let diagnostics = diagnostics.to_vec();
+        CacheEntry { key, files, diagnostics }
+    }
+}
+
+/// Computes the key for a code-generator, by hashing its executable and the payload that would be sent to it.
+/// If the executable can't be read, this returns `None`, since there's no way to tell if the code-generator changed.
+pub fn compute_key(plugin_path: &str, plugin_payload: &[u8]) -> Option<String> {
+    let executable = std::fs::read(plugin_path).ok()?;
+
+    // Hash the length of the executable first, so there's no ambiguity about where it ends and the payload begins.
+    let mut hasher = Sha256::new();
+    hasher.update((executable.len() as u64).to_le_bytes());
+    hasher.update(&executable);
+    hasher.update(plugin_payload);
+    Some(to_hex_string(&hasher.finalize()))
+}
+
+/// Returns the hash of the plugin's executable, or `None` if it can't be read.
+pub fn hash_executable(plugin_path: &str) -> Option<String> {
+    std::fs::read(plugin_path)
+        .ok()
+        .map(|executable| hash_bytes(&executable))
+}
+
+/// Returns the hash of the provided bytes, as a hexadecimal string.
+fn hash_bytes(bytes: &[u8]) -> String {
+    to_hex_string(&Sha256::digest(bytes))
+}
+
+fn to_hex_string(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+/// Loads the generation cache from the cache directory (or the output directory, if no cache directory was specified).
+pub(crate) fn load_cache(options: &SliceOptions) -> Cache {
+    let cache_dir = options.cache_dir.as_ref().or(options.output_dir.as_ref());
+    Cache::load(Path::new(cache_dir.map(String::as_str).unwrap_or_default()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::definition_types::DiagnosticLevel;
+    use crate::utils::test_util::TempDir;
+
+    /// Creates a cache (stored in the provided directory) holding an entry for a code-generator named 'gen', which
+    /// generated a file named 'a.rs' in the 'output' directory and reported a warning. This returns the entry's key.
+    fn create_cache(dir: &TempDir, plugin_path: &Path) -> (Cache, String) {
+        let generated_file = GeneratedFile {
+            path: "a.rs".to_owned(),
+            contents: "struct A;".to_owned(),
+        };
+        let diagnostic = Diagnostic {
+            level: DiagnosticLevel::Warning,
+            message: "something looks off".to_owned(),
+            source: None,
+        };
+        dir.write_file(Path::new("output").join(&generated_file.path), &generated_file.contents);
+
+        let key = compute_key(&plugin_path.display().to_string(), b"payload").unwrap();
+        let mut cache = Cache::load(dir);
+        cache.insert("gen", CacheEntry::new(key.clone(), &[generated_file], &[diagnostic]));
+        (cache, key)
+    }
+
+    #[test]
+    fn unchanged_responses_are_reused() {
+        // Arrange
+        let dir = TempDir::new("cache-unchanged");
+        let plugin_path = dir.write_file("gen", "executable");
+        let (cache, key) = create_cache(&dir, &plugin_path);
+        cache.save().unwrap();
+
+        // Act
+        let response = Cache::load(&dir).get("gen", &key, &dir.join("output"));
+
+        // Assert
+        let (generated_files, diagnostics) = response.unwrap();
+        assert_eq!(generated_files.len(), 1);
+        assert_eq!(generated_files[0].path, "a.rs");
+        assert_eq!(generated_files[0].contents, "struct A;");
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].message, "something looks off");
+    }
+
+    #[test]
+    fn changed_payloads_cause_a_rerun() {
+        // Arrange
+        let dir = TempDir::new("cache-changed-payload");
+        let plugin_path = dir.write_file("gen", "executable");
+        let (cache, key) = create_cache(&dir, &plugin_path);
+
+        // Act
+        let new_key = compute_key(&plugin_path.display().to_string(), b"changed payload").unwrap();
+
+        // Assert
+        assert_ne!(new_key, key);
+        assert!(cache.get("gen", &new_key, &dir.join("output")).is_none());
+    }
+
+    #[test]
+    fn changed_executables_cause_a_rerun() {
+        // Arrange
+        let dir = TempDir::new("cache-changed-executable");
+        let plugin_path = dir.write_file("gen", "executable");
+        let (cache, key) = create_cache(&dir, &plugin_path);
+
+        // Act
+        dir.write_file("gen", "updated executable");
+        let new_key = compute_key(&plugin_path.display().to_string(), b"payload").unwrap();
+
+        // Assert
+        assert_ne!(new_key, key);
+        assert!(cache.get("gen", &new_key, &dir.join("output")).is_none());
+    }
+
+    #[test]
+    fn edited_output_files_cause_a_rerun() {
+        // Arrange
+        let dir = TempDir::new("cache-edited-output");
+        let plugin_path = dir.write_file("gen", "executable");
+        let (cache, key) = create_cache(&dir, &plugin_path);
+
+        // Act
+        dir.write_file("output/a.rs", "struct B;");
+
+        // Assert
+        assert!(cache.get("gen", &key, &dir.join("output")).is_none());
+    }
+
+    #[test]
+    fn deleted_output_files_cause_a_rerun() {
+        // Arrange
+        let dir = TempDir::new("cache-deleted-output");
+        let plugin_path = dir.write_file("gen", "executable");
+        let (cache, key) = create_cache(&dir, &plugin_path);
+
+        // Act
+        std::fs::remove_file(dir.join("output/a.rs")).unwrap();
+
+        // Assert
+        assert!(cache.get("gen", &key, &dir.join("output")).is_none());
+    }
+
+    #[test]
+    fn plugins_without_a_readable_executable_have_no_key() {
+        // Arrange
+        let dir = TempDir::new("cache-missing-executable");
+
+        // Act
+        let key = compute_key(&dir.join("missing").display().to_string(), b"payload");
+
+        // Assert
+        assert_eq!(key, None);
+    }
+
+    #[test]
+    fn empty_caches_delete_their_file() {
+        // Arrange
+        let dir = TempDir::new("cache-empty");
+        let plugin_path = dir.write_file("gen", "executable");
+        let (mut cache, _) = create_cache(&dir, &plugin_path);
+        cache.save().unwrap();
+
+        // Act
+        cache.remove("gen");
+        cache.save().unwrap();
+
+        // Assert
+        assert!(!cache.path().exists());
+    }
+
+    #[test]
+    fn handshakes_are_reused_until_the_executable_changes() {
+        // Arrange
+        let dir = TempDir::new("cache-handshakes");
+        let plugin_path = dir.write_file("gen", "executable").display().to_string();
+        let mut cache = Cache::load(&dir);
+        cache.insert_handshake(&plugin_path, hash_executable(&plugin_path).unwrap());
+        cache.save().unwrap();
+        let cache = Cache::load(&dir);
+        assert!(cache.has_handshake(&plugin_path, &hash_executable(&plugin_path).unwrap()));
+
+        // Act
+        dir.write_file("gen", "updated executable");
+
+        // Assert
+        assert!(!cache.has_handshake(&plugin_path, &hash_executable(&plugin_path).unwrap()));
+    }
+
+    #[test]
+    fn cleared_caches_delete_their_file() {
+        // Arrange
+        let dir = TempDir::new("cache-cleared");
+        let plugin_path = dir.write_file("gen", "executable");
+        let (mut cache, _) = create_cache(&dir, &plugin_path);
+        let plugin_path = plugin_path.display().to_string();
+        cache.insert_handshake(&plugin_path, hash_executable(&plugin_path).unwrap());
+        cache.save().unwrap();
+
+        // Act
+        cache.clear();
+        cache.save().unwrap();
+
+        // Assert
+        assert!(!cache.path().exists());
+    }
+}
@@ -0,0 +1,33 @@
+// Copyright (c) ZeroC, Inc.
+
+//! This module defines the [CodeGenerator] trait, which lets code-generators written in Rust be run in-process by
+//! slicec, instead of as separate plugin executables. See [Driver](crate::driver::Driver) for how to register them.
+
+use crate::definition_types::{CompilationContext, Diagnostic, GeneratedFile, SliceFile};
+
+pub use crate::handshake::PROTOCOL_VERSION;
+
+/// A code-generator that runs in-process, and receives the same model of the Slice definitions as plugins do, but
+/// without it needing to be encoded and decoded.
+///
+/// It's run exactly like a plugin would be: the files it returns are checked and written to the output directory,
+/// and the diagnostics it returns are emitted alongside the compiler's own diagnostics.
+pub trait CodeGenerator {
+    /// Returns the name of this code-generator.
+    /// Users select this code-generator by passing its name to '--generator', and it's used to refer to this
+    /// code-generator in diagnostics and the manifest of generated files.
+    fn name(&self) -> &str;
+
+    /// Generates code for the provided Slice files, returning the files it generated and any diagnostics it reported.
+    ///
+    /// `source_files` holds the files that code should be generated for, and `reference_files` holds the files
+    /// which they reference, but that code shouldn't be generated for. `args` holds the arguments which were passed to
+    /// this code-generator on the command line (ex: '-G name,arg1=value1').
+    fn generate_code(
+        &self,
+        source_files: &[SliceFile],
+        reference_files: &[SliceFile],
+        context: &CompilationContext,
+        args: &[(String, String)],
+    ) -> (Vec<GeneratedFile>, Vec<Diagnostic>);
+}
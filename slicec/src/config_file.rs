@@ -0,0 +1,248 @@
+// Copyright (c) ZeroC, Inc.
+
+//! This module implements loading options from a configuration file (named 'slice.toml' by default).
+//! This lets projects store their options in a file, instead of passing them all on the command line.
+//!
+//! A configuration file can be specified with '--config <FILE>'. Otherwise, slicec loads the file named 'slice.toml' in
+//! the current directory (if there is one). Parent directories aren't searched, so that running slicec from a directory
+//! without a configuration file never picks up an unrelated one from further up the filesystem.
+//!
+//! # Format
+//!
+//! ```toml
+//! # Slice files to compile. Glob patterns are supported.
+//! sources = ["slice/**/*.slice"]
+//! # Directories or Slice files to reference. Glob patterns are supported.
+//! references = ["../common/slice"]
+//! # Preprocessor symbols to define.
+//! defined-symbols = ["DEBUG"]
+//! # The output directory for generated code.
+//! output-dir = "generated"
+//!
+//! [lints]
+//! allow = ["Deprecated"]
+//! deny = ["BrokenDocLink"]
+//!
+//! # Code-generators to run, and their arguments. 'name' accepts anything '--generator' does.
+//! [[generators]]
+//! name = "cs"
+//! args = { rpc = "icerpc" }
+//! ```
+//! All relative paths are relative to the directory that contains the configuration file.
+//!
+//! # Merging with the command line
+//!
+//! Options passed on the command line take precedence over options in the configuration file:
+//! - Sources, generators, and the output directory from the configuration file are only used if none were specified on
+//!   the command line.
+//! - References and defined symbols from the configuration file are used in addition to those on the command line.
+//! - Allowed and denied lints from the configuration file are used in addition to those on the command line, except for
+//!   lints which the command line allows or denies the opposite way.
+
+use crate::diagnostics::{Diagnostic, Diagnostics, Error, Lint};
+use crate::slice_file::{Location, SliceFile, Span};
+use crate::slice_options::{Plugin, SliceOptions};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use toml::Spanned;
+
+/// The name of the configuration file that slicec looks for, if no configuration file was specified.
+pub const CONFIG_FILE_NAME: &str = "slice.toml";
+
+/// The options that can be set in a configuration file. See the module documentation for a description of each.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields, rename_all = "kebab-case")]
+struct ConfigFile {
+    sources: Vec<Spanned<String>>,
+    references: Vec<Spanned<String>>,
+    defined_symbols: Vec<String>,
+    output_dir: Option<String>,
+    lints: LintsConfig,
+    generators: Vec<GeneratorConfig>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct LintsConfig {
+    allow: Vec<Spanned<String>>,
+    deny: Vec<Spanned<String>>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct GeneratorConfig {
+    name: String,
+    #[serde(default)]
+    args: BTreeMap<String, String>,
+}
+
+/// Loads the configuration file (if there is one), and merges its options into the provided options.
+/// Options which were passed on the command line take precedence over the options in the configuration file.
+/// The path of the configuration file that was loaded is stored in the options (in case it was found automatically).
+///
+/// If a configuration file was loaded, this returns it as a [SliceFile], so that any errors which were reported
+/// about it can show snippets of it. The returned file only holds the configuration file's text, and isn't parsed.
+pub fn apply_config_file(options: &mut SliceOptions, diagnostics: &mut Diagnostics) -> Option<SliceFile> {
+    let path = match &options.config {
+        Some(path) => PathBuf::from(path),
+        None => find_config_file()?,
+    };
+
+    // Record which configuration file was used, in case it was found automatically.
+    let path_string = path.display().to_string();
+    options.config = Some(path_string.clone());
+
+    let raw_text = match std::fs::read_to_string(&path) {
+        Ok(raw_text) => raw_text,
+        Err(error) => {
+            let action = "read configuration file";
+            Diagnostic::new(Error::IO {
+                action,
+                path: path_string,
+                error,
+            })
+            .push_into(diagnostics);
+            return None;
+        }
+    };
+    let config_file = SliceFile::new(path_string, raw_text, false);
+
+    let config = match toml::from_str::<ConfigFile>(&config_file.raw_text) {
+        Ok(config) => config,
+        Err(error) => {
+            report_error(error.message().to_owned(), error.span(), &config_file, diagnostics);
+            return Some(config_file);
+        }
+    };
+
+    // All relative paths in the configuration file are relative to the directory which contains it.
+    let config_dir = path.parent().unwrap_or(Path::new(""));
+
+    let sources = expand_patterns(&config.sources, config_dir, &config_file, diagnostics);
+    let references = expand_patterns(&config.references, config_dir, &config_file, diagnostics);
+    let allowed_lints = resolve_lint_names(&config.lints.allow, &config_file, diagnostics);
+    let denied_lints = resolve_lint_names(&config.lints.deny, &config_file, diagnostics);
+
+    // Merge the configuration file's options into the command line options.
+    if options.sources.is_empty() {
+        options.sources = sources;
+    }
+    options.references.splice(0..0, references);
+    options.defined_symbols.splice(0..0, config.defined_symbols);
+
+    // Lints that the command line allows can't be denied by the configuration file, and vice versa.
+    let contains_lint = |lints: &[String], name: &str| lints.iter().any(|lint| lint.eq_ignore_ascii_case(name));
+    let allowed_lints = allowed_lints
+        .into_iter()
+        .filter(|name| !contains_lint(&options.denied_lints, name));
+    let denied_lints = denied_lints
+        .into_iter()
+        .filter(|name| !contains_lint(&options.allowed_lints, name));
+    let (allowed_lints, denied_lints) = (allowed_lints.collect::<Vec<_>>(), denied_lints.collect::<Vec<_>>());
+    options.allowed_lints.splice(0..0, allowed_lints);
+    options.denied_lints.splice(0..0, denied_lints);
+
+    if options.output_dir.is_none() {
+        options.output_dir = config.output_dir.map(|dir| config_dir.join(dir).display().to_string());
+    }
+    if options.generators.is_empty() {
+        options.generators = config
+            .generators
+            .into_iter()
+            .map(|generator| Plugin {
+                path: get_generator_path(generator.name, config_dir),
+                args: generator.args.into_iter().collect(),
+            })
+            .collect();
+    }
+
+    Some(config_file)
+}
+
+/// Looks for a file named 'slice.toml' in the current directory, and returns its (relative) path if there is one.
+fn find_config_file() -> Option<PathBuf> {
+    let path = PathBuf::from(CONFIG_FILE_NAME);
+    path.is_file().then_some(path)
+}
+
+/// Expands the provided glob patterns into the paths of the files and directories that they match.
+/// An error is reported for each pattern that is invalid, or that doesn't match anything.
+fn expand_patterns(
+    patterns: &[Spanned<String>],
+    config_dir: &Path,
+    config_file: &SliceFile,
+    diagnostics: &mut Diagnostics,
+) -> Vec<String> {
+    let mut paths = Vec::new();
+    for pattern in patterns {
+        let full_pattern = config_dir.join(pattern.get_ref()).display().to_string();
+        match glob::glob(&full_pattern) {
+            Ok(matches) => {
+                let mut matched_paths = matches.filter_map(Result::ok).peekable();
+                if matched_paths.peek().is_none() {
+                    let message = format!("'{}' doesn't match any files or directories", pattern.get_ref());
+                    report_error(message, Some(pattern.span()), config_file, diagnostics);
+                }
+                paths.extend(matched_paths.map(|path| path.display().to_string()));
+            }
+            Err(error) => {
+                let message = format!("'{}' isn't a valid glob pattern: {}", pattern.get_ref(), error.msg);
+                report_error(message, Some(pattern.span()), config_file, diagnostics);
+            }
+        }
+    }
+    paths
+}
+
+/// Returns the properly capitalized names of the provided lints. Like on the command line, these are case-insensitive.
+/// An error is reported for each lint that doesn't exist.
+fn resolve_lint_names(
+    lints: &[Spanned<String>],
+    config_file: &SliceFile,
+    diagnostics: &mut Diagnostics,
+) -> Vec<String> {
+    let mut names = Vec::new();
+    for lint in lints {
+        let name = Lint::ALLOWABLE_LINT_IDENTIFIERS
+            .iter()
+            .find(|identifier| identifier.eq_ignore_ascii_case(lint.get_ref()));
+        match name {
+            Some(name) => names.push(name.to_string()),
+            None => {
+                let message = format!("unknown lint '{}'", lint.get_ref());
+                report_error(message, Some(lint.span()), config_file, diagnostics);
+            }
+        }
+    }
+    names
+}
+
+/// Returns the path of a code-generator from the configuration file. Code-generators that are specified by name are
+/// returned as-is, but paths are made relative to the configuration file's directory.
+fn get_generator_path(name: String, config_dir: &Path) -> String {
+    match Path::new(&name).components().count() {
+        1 => name,
+        _ => config_dir.join(name).display().to_string(),
+    }
+}
+
+/// Reports an error about the configuration file, which points to the provided range of bytes within it (if any).
+fn report_error(message: String, range: Option<Range<usize>>, config_file: &SliceFile, diagnostics: &mut Diagnostics) {
+    let mut diagnostic = Diagnostic::new(Error::InvalidConfigFile { message });
+    if let Some(range) = range {
+        let start = get_location(&config_file.raw_text, range.start);
+        let end = get_location(&config_file.raw_text, range.end);
+        diagnostic = diagnostic.set_span(&Span::new(start, end, &config_file.relative_path));
+    }
+    diagnostic.push_into(diagnostics);
+}
+
+/// Returns the location (row and column) of the character that starts at the provided byte offset in the text.
+fn get_location(text: &str, offset: usize) -> Location {
+    let preceding_text = &text[..offset.min(text.len())];
+    let row = preceding_text.matches('\n').count() + 1;
+    let col = preceding_text.rsplit('\n').next().unwrap_or_default().chars().count() + 1;
+    Location { row, col }
+}
@@ -1,7 +1,15 @@
 // Copyright (c) ZeroC, Inc.
 
-//! This module contains the handwritten encoding code for the Slice-compiler definitions.
-//! After rust code-gen has been implemented, this file will be deleted, and we will use generated definitions instead.
+//! This module contains the Rust definitions of the types in the Slice-compiler definitions (the Slice files under
+//! 'slice/Compiler'), along with their (handwritten) encoding and decoding code. These are the types that are sent to
+//! code-generators: both plugins (which receive them encoded), and code-generators that implement [CodeGenerator].
+//!
+//! These types are part of slicec's public API, since [CodeGenerator] and the 'slicec-plugin' crate expose them.
+//! They mirror the code-generator protocol, and so they change whenever it does: any release which changes
+//! [PROTOCOL_VERSION] may also add, remove, or change these types, without any other deprecation period.
+//!
+//! [CodeGenerator]: crate::code_generator::CodeGenerator
+//! [PROTOCOL_VERSION]: crate::code_generator::PROTOCOL_VERSION
 
 #![allow(dead_code)]
 
@@ -12,6 +20,9 @@ use slice_codec::encode_into::EncodeInto;
 use slice_codec::encoder::Encoder;
 use slice_codec::{InvalidDataErrorKind, Result};
 
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
 /// TAG_END_MARKER must be encoded at the end of every non-compact type.
 const TAG_END_MARKER: i32 = -1;
 
@@ -32,6 +43,23 @@ macro_rules! implement_encode_into_for_struct {
     }
 }
 
+/// This macro implements `DecodeFrom` for a Rust struct (which is mapped from a non-compact Slice struct).
+/// It decodes all the struct's fields (in definition order), and then skips any tagged fields.
+///
+/// It uses macro-function-syntax, and should be called like:
+/// `implement_decode_from_for_struct!(struct_type_name, field1, field2, ...);`
+macro_rules! implement_decode_from_for_struct {
+    ($type_name:ident$(, $field_name:ident)*$(,)?) => {
+        impl DecodeFrom for $type_name {
+            fn decode_from(decoder: &mut Decoder<impl InputSource>) -> Result<Self> {
+                $(let $field_name = decoder.decode()?;)*
+                decoder.skip_tagged_fields()?;
+                Ok($type_name { $($field_name),* })
+            }
+        }
+    }
+}
+
 // ================= //
 // Hand-mapped types //
 // ================= //
@@ -40,26 +68,46 @@ pub type EntityId = String;
 pub type TypeId = String;
 pub type Message = Vec<MessageComponent>;
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Span {
+    pub file: String,
+    pub start_row: u32,
+    pub start_column: u32,
+    pub end_row: u32,
+    pub end_column: u32,
+}
+implement_encode_into_for_struct!(Span, file, start_row, start_column, end_row, end_column);
+implement_decode_from_for_struct!(Span, file, start_row, start_column, end_row, end_column);
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct Attribute {
     pub directive: String,
     pub args: Vec<String>,
+    pub span: Span,
 }
-implement_encode_into_for_struct!(Attribute, directive, args);
+implement_encode_into_for_struct!(Attribute, directive, args, span);
+implement_decode_from_for_struct!(Attribute, directive, args, span);
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct TypeRef {
     pub type_id: TypeId,
     pub is_optional: bool,
     pub type_attributes: Vec<Attribute>,
+    pub span: Span,
 }
-implement_encode_into_for_struct!(TypeRef, type_id, is_optional, type_attributes);
+implement_encode_into_for_struct!(TypeRef, type_id, is_optional, type_attributes, span);
+implement_decode_from_for_struct!(TypeRef, type_id, is_optional, type_attributes, span);
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct EntityInfo {
     pub identifier: String,
     pub attributes: Vec<Attribute>,
     pub comment: Option<DocComment>,
+    pub span: Span,
 }
 impl EncodeInto for &EntityInfo {
     fn encode_into(self, encoder: &mut Encoder<impl OutputTarget>) -> Result<()> {
@@ -72,36 +120,66 @@ impl EncodeInto for &EntityInfo {
         if let Some(comment_value) = &self.comment {
             encoder.encode(comment_value)?;
         }
+        encoder.encode(&self.span)?;
         encoder.encode_varint(TAG_END_MARKER)?;
         Ok(())
     }
 }
+impl DecodeFrom for EntityInfo {
+    fn decode_from(decoder: &mut Decoder<impl InputSource>) -> Result<Self> {
+        // Decode the bit-sequence. With only one optional, this is just a bool.
+        let has_comment = decoder.decode::<bool>()?;
 
-#[derive(Clone, Debug)]
+        // Decode the actual fields.
+        let identifier = decoder.decode()?;
+        let attributes = decoder.decode()?;
+        let comment = has_comment.then(|| decoder.decode()).transpose()?;
+        let span = decoder.decode()?;
+
+        decoder.skip_tagged_fields()?;
+
+        Ok(EntityInfo {
+            identifier,
+            attributes,
+            comment,
+            span,
+        })
+    }
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct Module {
     pub identifier: String,
     pub attributes: Vec<Attribute>,
 }
 implement_encode_into_for_struct!(Module, identifier, attributes);
+implement_decode_from_for_struct!(Module, identifier, attributes);
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct Struct {
     pub entity_info: EntityInfo,
     pub is_compact: bool,
     pub fields: Vec<Field>,
 }
 implement_encode_into_for_struct!(Struct, entity_info, is_compact, fields);
+implement_decode_from_for_struct!(Struct, entity_info, is_compact, fields);
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct Field {
     pub entity_info: EntityInfo,
     pub tag: Option<i32>, // TODO: varint32 isn't a real type?
     pub data_type: TypeRef,
+    pub default_value: Option<ConstantValue>, // Only non-optional fields can have default values.
 }
 impl EncodeInto for &Field {
     fn encode_into(self, encoder: &mut Encoder<impl OutputTarget>) -> Result<()> {
-        // Encode the bit-sequence. With only one optional, this is just a bool.
-        encoder.encode(self.tag.is_some())?;
+        // Encode the bit-sequence. With two optionals, this is a single byte, where bit 0 is set if there's a tag, and
+        // bit 1 is set if there's a default value.
+        let bit_sequence = u8::from(self.tag.is_some()) | (u8::from(self.default_value.is_some()) << 1);
+        encoder.encode(bit_sequence)?;
 
         // Encode the actual fields.
         encoder.encode(&self.entity_info)?;
@@ -109,20 +187,49 @@ impl EncodeInto for &Field {
             encoder.encode_varint(tag_value)?;
         }
         encoder.encode(&self.data_type)?;
+        if let Some(default_value) = &self.default_value {
+            encoder.encode(default_value)?;
+        }
         encoder.encode_varint(TAG_END_MARKER)?;
         Ok(())
     }
 }
+impl DecodeFrom for Field {
+    fn decode_from(decoder: &mut Decoder<impl InputSource>) -> Result<Self> {
+        // Decode the bit-sequence. With two optionals, this is a single byte.
+        let bit_sequence = decoder.decode::<u8>()?;
+        let has_tag = bit_sequence & 1 != 0;
+        let has_default_value = bit_sequence & (1 << 1) != 0;
 
-#[derive(Clone, Debug)]
+        // Decode the actual fields.
+        let entity_info = decoder.decode()?;
+        let tag = has_tag.then(|| decoder.decode_varint()).transpose()?;
+        let data_type = decoder.decode()?;
+        let default_value = has_default_value.then(|| decoder.decode()).transpose()?;
+
+        decoder.skip_tagged_fields()?;
+
+        Ok(Field {
+            entity_info,
+            tag,
+            data_type,
+            default_value,
+        })
+    }
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct Interface {
     pub entity_info: EntityInfo,
     pub bases: Vec<EntityId>,
     pub operations: Vec<Operation>,
 }
 implement_encode_into_for_struct!(Interface, entity_info, bases, operations);
+implement_decode_from_for_struct!(Interface, entity_info, bases, operations);
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct Operation {
     pub entity_info: EntityInfo,
     pub is_idempotent: bool,
@@ -130,6 +237,7 @@ pub struct Operation {
     pub has_streamed_parameter: bool,
     pub return_type: Vec<Field>,
     pub has_streamed_return: bool,
+    pub returns_message: Message,
 }
 implement_encode_into_for_struct!(
     Operation,
@@ -139,9 +247,21 @@ implement_encode_into_for_struct!(
     has_streamed_parameter,
     return_type,
     has_streamed_return,
+    returns_message,
+);
+implement_decode_from_for_struct!(
+    Operation,
+    entity_info,
+    is_idempotent,
+    parameters,
+    has_streamed_parameter,
+    return_type,
+    has_streamed_return,
+    returns_message,
 );
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct BasicEnum {
     pub entity_info: EntityInfo,
     pub is_unchecked: bool,
@@ -149,16 +269,20 @@ pub struct BasicEnum {
     pub enumerators: Vec<Enumerator>,
 }
 implement_encode_into_for_struct!(BasicEnum, entity_info, is_unchecked, underlying, enumerators);
+implement_decode_from_for_struct!(BasicEnum, entity_info, is_unchecked, underlying, enumerators);
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct Enumerator {
     pub entity_info: EntityInfo,
     pub absolute_value: u64,
     pub has_negative_value: bool,
 }
 implement_encode_into_for_struct!(Enumerator, entity_info, absolute_value, has_negative_value);
+implement_decode_from_for_struct!(Enumerator, entity_info, absolute_value, has_negative_value);
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct VariantEnum {
     pub entity_info: EntityInfo,
     pub is_compact: bool,
@@ -166,57 +290,149 @@ pub struct VariantEnum {
     pub variants: Vec<Variant>,
 }
 implement_encode_into_for_struct!(VariantEnum, entity_info, is_compact, is_unchecked, variants);
+implement_decode_from_for_struct!(VariantEnum, entity_info, is_compact, is_unchecked, variants);
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct Variant {
     pub entity_info: EntityInfo,
     pub discriminant: i32,
     pub fields: Vec<Field>,
 }
 implement_encode_into_for_struct!(Variant, entity_info, discriminant, fields);
+implement_decode_from_for_struct!(Variant, entity_info, discriminant, fields);
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct CustomType {
     pub entity_info: EntityInfo,
 }
 implement_encode_into_for_struct!(CustomType, entity_info);
+implement_decode_from_for_struct!(CustomType, entity_info);
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct TypeAlias {
     pub entity_info: EntityInfo,
     pub underlying_type: TypeRef, // Can never be optional.
 }
 implement_encode_into_for_struct!(TypeAlias, entity_info, underlying_type);
+implement_decode_from_for_struct!(TypeAlias, entity_info, underlying_type);
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Constant {
+    pub entity_info: EntityInfo,
+    pub data_type: TypeRef, // Can never be optional.
+    pub value: ConstantValue,
+}
+implement_encode_into_for_struct!(Constant, entity_info, data_type, value);
+implement_decode_from_for_struct!(Constant, entity_info, data_type, value);
+
+#[repr(u8)]
+#[derive(Clone, Debug, Serialize)]
+#[serde(tag = "kind", content = "value", rename_all_fields = "camelCase")]
+pub enum ConstantValue {
+    Bool(bool) = 0,
+    Integer {
+        absolute_value: u64,
+        has_negative_value: bool,
+    } = 1,
+    String(String) = 2,
+    Enumerator(EntityId) = 3,
+}
+impl EncodeInto for &ConstantValue {
+    fn encode_into(self, encoder: &mut Encoder<impl OutputTarget>) -> Result<()> {
+        // Write the discriminant value.
+        // SAFETY: this cast is guaranteed to be safe because the enum is marked with `repr(u8)`, so it's safe to cast
+        // it directly to a `u8`.
+        unsafe {
+            let discriminant = *<*const _>::from(self).cast::<u8>();
+            encoder.encode_varint(discriminant)?;
+        }
+
+        // Encode the actual value.
+        match self {
+            ConstantValue::Bool(v) => encoder.encode(v)?,
+            ConstantValue::Integer {
+                absolute_value,
+                has_negative_value,
+            } => {
+                encoder.encode(absolute_value)?;
+                encoder.encode(has_negative_value)?;
+            }
+            ConstantValue::String(v) => encoder.encode(v)?,
+            ConstantValue::Enumerator(v) => encoder.encode(v)?,
+        }
+
+        encoder.encode_varint(TAG_END_MARKER)?;
+        Ok(())
+    }
+}
+impl DecodeFrom for ConstantValue {
+    fn decode_from(decoder: &mut Decoder<impl InputSource>) -> Result<Self> {
+        // Decode the discriminant value, and then the actual value.
+        let discriminant = decoder.decode_varint::<u8>()?;
+        let value = match discriminant {
+            0 => ConstantValue::Bool(decoder.decode()?),
+            1 => ConstantValue::Integer {
+                absolute_value: decoder.decode()?,
+                has_negative_value: decoder.decode()?,
+            },
+            2 => ConstantValue::String(decoder.decode()?),
+            3 => ConstantValue::Enumerator(decoder.decode()?),
+            _ => {
+                let error = InvalidDataErrorKind::IllegalValue {
+                    desc: "ConstantValue",
+                    value: Some(discriminant.into()),
+                };
+                return Err(error.into());
+            }
+        };
+
+        decoder.skip_tagged_fields()?;
+        Ok(value)
+    }
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct SequenceType {
     pub element_type: TypeRef,
 }
 implement_encode_into_for_struct!(SequenceType, element_type);
+implement_decode_from_for_struct!(SequenceType, element_type);
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct DictionaryType {
     pub key_type: TypeRef, // Can never be optional.
     pub value_type: TypeRef,
 }
 implement_encode_into_for_struct!(DictionaryType, key_type, value_type);
+implement_decode_from_for_struct!(DictionaryType, key_type, value_type);
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct ResultType {
     pub success_type: TypeRef,
     pub failure_type: TypeRef,
 }
 implement_encode_into_for_struct!(ResultType, success_type, failure_type);
+implement_decode_from_for_struct!(ResultType, success_type, failure_type);
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct DocComment {
     pub overview: Message,
     pub see_tags: Vec<EntityId>,
 }
 implement_encode_into_for_struct!(DocComment, overview, see_tags);
+implement_decode_from_for_struct!(DocComment, overview, see_tags);
 
 #[repr(u8)]
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize)]
+#[serde(tag = "kind", content = "value")]
 pub enum MessageComponent {
     Text(String) = 0,
     Link(EntityId) = 1,
@@ -241,6 +457,26 @@ impl EncodeInto for &MessageComponent {
         Ok(())
     }
 }
+impl DecodeFrom for MessageComponent {
+    fn decode_from(decoder: &mut Decoder<impl InputSource>) -> Result<Self> {
+        // Decode the discriminant value, and then the actual value.
+        let discriminant = decoder.decode_varint::<u8>()?;
+        let component = match discriminant {
+            0 => MessageComponent::Text(decoder.decode()?),
+            1 => MessageComponent::Link(decoder.decode()?),
+            _ => {
+                let error = InvalidDataErrorKind::IllegalValue {
+                    desc: "MessageComponent",
+                    value: Some(discriminant.into()),
+                };
+                return Err(error.into());
+            }
+        };
+
+        decoder.skip_tagged_fields()?;
+        Ok(component)
+    }
+}
 
 pub struct Arguments(pub Vec<(String, String)>);
 impl EncodeInto for Arguments {
@@ -253,8 +489,50 @@ impl EncodeInto for Arguments {
         Ok(())
     }
 }
+impl DecodeFrom for Arguments {
+    fn decode_from(decoder: &mut Decoder<impl InputSource>) -> Result<Self> {
+        let size = decoder.decode_size()?;
+        let mut args = Vec::new();
+        for _ in 0..size {
+            args.push((decoder.decode()?, decoder.decode()?));
+        }
+        Ok(Arguments(args))
+    }
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CompilationContext {
+    pub compiler_version: String,
+    pub defined_symbols: Vec<String>,
+    pub absolute_paths: BTreeMap<String, String>,
+    pub output_dir: String,
+}
+implement_encode_into_for_struct!(
+    CompilationContext,
+    compiler_version,
+    defined_symbols,
+    absolute_paths,
+    output_dir,
+);
+implement_decode_from_for_struct!(
+    CompilationContext,
+    compiler_version,
+    defined_symbols,
+    absolute_paths,
+    output_dir,
+);
 
 #[derive(Clone, Debug)]
+pub struct GeneratorInfo {
+    pub name: String,
+    pub protocol_version: u32,
+}
+implement_encode_into_for_struct!(GeneratorInfo, name, protocol_version);
+implement_decode_from_for_struct!(GeneratorInfo, name, protocol_version);
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct SliceFile {
     pub path: String,
     pub module_declaration: Module,
@@ -262,9 +540,11 @@ pub struct SliceFile {
     pub contents: Vec<Symbol>,
 }
 implement_encode_into_for_struct!(SliceFile, path, module_declaration, attributes, contents);
+implement_decode_from_for_struct!(SliceFile, path, module_declaration, attributes, contents);
 
 #[repr(u8)]
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize)]
+#[serde(tag = "kind", content = "value")]
 pub enum Symbol {
     Interface(Interface) = 0,
     BasicEnum(BasicEnum) = 1,
@@ -275,6 +555,7 @@ pub enum Symbol {
     DictionaryType(DictionaryType) = 6,
     ResultType(ResultType) = 7, // TODO make result come before dictionary!
     TypeAlias(TypeAlias) = 8,
+    Constant(Constant) = 9,
 }
 impl EncodeInto for &Symbol {
     fn encode_into(self, encoder: &mut Encoder<impl OutputTarget>) -> Result<()> {
@@ -297,18 +578,56 @@ impl EncodeInto for &Symbol {
             Symbol::DictionaryType(v) => encoder.encode(v)?,
             Symbol::ResultType(v) => encoder.encode(v)?,
             Symbol::TypeAlias(v) => encoder.encode(v)?,
+            Symbol::Constant(v) => encoder.encode(v)?,
         }
 
         encoder.encode_varint(TAG_END_MARKER)?;
         Ok(())
     }
 }
+impl DecodeFrom for Symbol {
+    fn decode_from(decoder: &mut Decoder<impl InputSource>) -> Result<Self> {
+        // Decode the discriminant value, and then the actual value.
+        let discriminant = decoder.decode_varint::<u8>()?;
+        let symbol = match discriminant {
+            0 => Symbol::Interface(decoder.decode()?),
+            1 => Symbol::BasicEnum(decoder.decode()?),
+            2 => Symbol::VariantEnum(decoder.decode()?),
+            3 => Symbol::Struct(decoder.decode()?),
+            4 => Symbol::CustomType(decoder.decode()?),
+            5 => Symbol::SequenceType(decoder.decode()?),
+            6 => Symbol::DictionaryType(decoder.decode()?),
+            7 => Symbol::ResultType(decoder.decode()?),
+            8 => Symbol::TypeAlias(decoder.decode()?),
+            9 => Symbol::Constant(decoder.decode()?),
+            _ => {
+                let error = InvalidDataErrorKind::IllegalValue {
+                    desc: "Symbol",
+                    value: Some(discriminant.into()),
+                };
+                return Err(error.into());
+            }
+        };
 
-#[derive(Clone, Debug)]
+        decoder.skip_tagged_fields()?;
+        Ok(symbol)
+    }
+}
+
+#[derive(Clone, Debug, Serialize)]
 pub struct GeneratedFile {
     pub path: String,
     pub contents: String,
 }
+impl EncodeInto for &GeneratedFile {
+    fn encode_into(self, encoder: &mut Encoder<impl OutputTarget>) -> Result<()> {
+        encoder.encode(&self.path)?;
+        encoder.encode(&self.contents)?;
+
+        encoder.encode_varint(TAG_END_MARKER)?;
+        Ok(())
+    }
+}
 impl DecodeFrom for GeneratedFile {
     fn decode_from(decoder: &mut Decoder<impl InputSource>) -> Result<Self> {
         let path = decoder.decode()?;
@@ -320,12 +639,28 @@ impl DecodeFrom for GeneratedFile {
     }
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct Diagnostic {
     pub level: DiagnosticLevel,
     pub message: String,
     pub source: Option<String>,
 }
+impl EncodeInto for &Diagnostic {
+    fn encode_into(self, encoder: &mut Encoder<impl OutputTarget>) -> Result<()> {
+        // Encode the bit-sequence. With only one optional, this is just a bool.
+        encoder.encode(self.source.is_some())?;
+
+        // Encode the actual fields.
+        encoder.encode(self.level)?;
+        encoder.encode(&self.message)?;
+        if let Some(source) = &self.source {
+            encoder.encode(source)?;
+        }
+
+        encoder.encode_varint(TAG_END_MARKER)?;
+        Ok(())
+    }
+}
 impl DecodeFrom for Diagnostic {
     fn decode_from(decoder: &mut Decoder<impl InputSource>) -> Result<Self> {
         // Decode the bit-sequence. With only one optional, this is just a bool.
@@ -343,12 +678,18 @@ impl DecodeFrom for Diagnostic {
 }
 
 #[repr(u8)]
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
 pub enum DiagnosticLevel {
     Info = 0,
     Warning = 1,
     Error = 2,
 }
+impl EncodeInto for DiagnosticLevel {
+    fn encode_into(self, encoder: &mut Encoder<impl OutputTarget>) -> Result<()> {
+        encoder.encode(self as u8)
+    }
+}
 impl DecodeFrom for DiagnosticLevel {
     fn decode_from(decoder: &mut Decoder<impl InputSource>) -> Result<Self> {
         let value = decoder.decode::<u8>()?;
@@ -52,6 +52,7 @@ impl<'a, T: Write> DiagnosticEmitter<'a, T> {
             let prefix = match diagnostic.level() {
                 DiagnosticLevel::Error => console::style(format!("error [{code}]")).red().bold(),
                 DiagnosticLevel::Warning => console::style(format!("warning [{code}]")).yellow().bold(),
+                DiagnosticLevel::Info => console::style(format!("info [{code}]")).cyan().bold(),
                 DiagnosticLevel::Allowed => continue,
             };
 
@@ -86,6 +87,7 @@ impl<'a, T: Write> DiagnosticEmitter<'a, T> {
             let severity = match diagnostic.level() {
                 DiagnosticLevel::Error => "error",
                 DiagnosticLevel::Warning => "warning",
+                DiagnosticLevel::Info => "info",
                 DiagnosticLevel::Allowed => continue,
             };
 
@@ -1,13 +1,13 @@
 // Copyright (c) ZeroC, Inc.
 
-use super::{Error, Lint, Note};
+use super::{Error, GeneratorDiagnostic, Lint, Note};
 use crate::ast::Ast;
 use crate::grammar::{attributes, Attributable, Entity};
 use crate::slice_file::{SliceFile, Span};
 use crate::slice_options::SliceOptions;
 
 /// A diagnostic is a message that is reported to the user during compilation.
-/// It can either hold an [Error] or a [Lint].
+/// It can either hold an [Error], a [Lint], or a [GeneratorDiagnostic].
 #[derive(Debug)]
 pub struct Diagnostic {
     kind: DiagnosticKind,
@@ -23,6 +23,7 @@ impl Diagnostic {
         let level = match &kind {
             DiagnosticKind::Error(_) => DiagnosticLevel::Error,
             DiagnosticKind::Lint(lint) => lint.get_default_level(),
+            DiagnosticKind::Generator(generator_diagnostic) => generator_diagnostic.level,
         };
 
         Diagnostic {
@@ -39,14 +40,17 @@ impl Diagnostic {
         match &self.kind {
             DiagnosticKind::Error(error) => error.message(),
             DiagnosticKind::Lint(lint) => lint.message(),
+            DiagnosticKind::Generator(generator_diagnostic) => generator_diagnostic.message.clone(),
         }
     }
 
-    /// Returns this diagnostic's code. This is either the name of a lint or of the form `E###`.
+    /// Returns this diagnostic's code. This is either the name of a lint, of the form `E###`,
+    /// or for diagnostics reported by code-generators, the name of the code-generator which reported it.
     pub fn code(&self) -> &str {
         match &self.kind {
             DiagnosticKind::Error(error) => error.code(),
             DiagnosticKind::Lint(lint) => lint.code(),
+            DiagnosticKind::Generator(generator_diagnostic) => &generator_diagnostic.generator,
         }
     }
 
@@ -103,6 +107,7 @@ impl Diagnostic {
 pub enum DiagnosticKind {
     Error(Error),
     Lint(Lint),
+    Generator(GeneratorDiagnostic),
 }
 
 impl From<Error> for DiagnosticKind {
@@ -117,6 +122,12 @@ impl From<Lint> for DiagnosticKind {
     }
 }
 
+impl From<GeneratorDiagnostic> for DiagnosticKind {
+    fn from(generator_diagnostic: GeneratorDiagnostic) -> Self {
+        DiagnosticKind::Generator(generator_diagnostic)
+    }
+}
+
 /// Diagnostic levels describe the severity of a diagnostic, and how the compiler should react to their emission.
 #[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
 pub enum DiagnosticLevel {
@@ -127,6 +138,10 @@ pub enum DiagnosticLevel {
     /// Diagnostics with the `Warning` level will be emitted, but will not influence the exit code of the compiler.
     Warning,
 
+    /// Diagnostics with the `Info` level will be emitted, but are purely informational.
+    /// Only code-generators can report diagnostics with this level.
+    Info,
+
     /// Diagnostics with the `Allowed` level will be suppressed and will not emit any message.
     Allowed,
 }
@@ -147,7 +162,11 @@ impl Diagnostics {
     /// Returns true if this contains any diagnostics that are errors.
     pub fn has_errors(&self) -> bool {
         let mut diagnostics = self.0.iter();
-        diagnostics.any(|diagnostic| matches!(diagnostic.kind, DiagnosticKind::Error(_)))
+        diagnostics.any(|diagnostic| match &diagnostic.kind {
+            DiagnosticKind::Error(_) => true,
+            DiagnosticKind::Lint(_) => false,
+            DiagnosticKind::Generator(generator_diagnostic) => generator_diagnostic.level == DiagnosticLevel::Error,
+        })
     }
 
     /// Returns true if this contains no diagnostics.
@@ -155,45 +174,38 @@ impl Diagnostics {
         self.0.is_empty()
     }
 
+    /// Returns true if this contains any lints that are denied by a `--deny` flag, and aren't allowed.
+    /// Since lint levels aren't applied until [Diagnostics::into_updated] runs, [Diagnostics::has_errors] doesn't
+    /// count these lints as errors, even though they'll be reported as errors.
+    pub fn has_denied_lints(&self, ast: &Ast, files: &[SliceFile], options: &SliceOptions) -> bool {
+        let mut lints = self
+            .0
+            .iter()
+            .filter(|diagnostic| matches!(diagnostic.kind, DiagnosticKind::Lint(_)));
+        lints.any(|lint| get_updated_level(lint, ast, files, options) == Some(DiagnosticLevel::Error))
+    }
+
     /// Returns the diagnostics this struct contains after it has patched and updated them.
     /// Lint levels can be configured via attributes or command line options, but these aren't applied until this runs.
     pub fn into_updated(mut self, ast: &Ast, files: &[SliceFile], options: &SliceOptions) -> Vec<Diagnostic> {
-        // Helper function that checks whether a lint should be allowed according to the provided identifiers.
-        fn is_lint_allowed_by<'b>(mut identifiers: impl Iterator<Item = &'b String>, lint: &Lint) -> bool {
-            identifiers.any(|identifier| identifier == "All" || identifier == lint.code())
-        }
-
-        // Helper function that checks whether a lint is allowed by attributes on the provided entity.
-        fn is_lint_allowed_by_attributes(attributable: &(impl Attributable + ?Sized), lint: &Lint) -> bool {
-            let attributes = attributable.all_attributes().into_iter();
-            let mut allowed = attributes.filter_map(|a| a.downcast::<attributes::Allow>());
-            allowed.any(|allow| is_lint_allowed_by(allow.allowed_lints.iter(), lint))
-        }
-
         for diagnostic in &mut self.0 {
-            // If this diagnostic is a lint, update its diagnostic level. Errors always have a level of `Error`.
-            if let DiagnosticKind::Lint(lint) = &diagnostic.kind {
-                // Check if the lint is allowed by an `--allow` flag passed on the command line.
-                if is_lint_allowed_by(options.allowed_lints.iter(), lint) {
-                    diagnostic.level = DiagnosticLevel::Allowed;
-                }
-
-                // If the diagnostic has a span, check if it's affected by an `allow` attribute on its file.
-                if let Some(span) = diagnostic.span() {
-                    let file = files.iter().find(|f| f.relative_path == span.file).expect("no file");
-                    if is_lint_allowed_by_attributes(file, lint) {
-                        diagnostic.level = DiagnosticLevel::Allowed;
+            // If this diagnostic was reported by a code-generator, and refers to a Slice element,
+            // resolve that element so we can report a span for the diagnostic, and check for `allow` attributes on it.
+            if let DiagnosticKind::Generator(GeneratorDiagnostic { source: Some(source), .. }) = &diagnostic.kind {
+                match ast.find_element::<dyn Entity>(source) {
+                    Ok(entity) => {
+                        diagnostic.span = Some(entity.span().clone());
+                        diagnostic.scope = Some(entity.parser_scoped_identifier());
                     }
+                    Err(_) => diagnostic.notes.push(Note {
+                        message: format!("reported on '{source}', which could not be found"),
+                        span: None,
+                    }),
                 }
+            }
 
-                // If the diagnostic has a scope, check if it's affected by an `allow` attribute in that scope.
-                if let Some(scope) = diagnostic.scope() {
-                    if let Ok(entity) = ast.find_element::<dyn Entity>(scope) {
-                        if is_lint_allowed_by_attributes(entity, lint) {
-                            diagnostic.level = DiagnosticLevel::Allowed;
-                        }
-                    }
-                }
+            if let Some(level) = get_updated_level(diagnostic, ast, files, options) {
+                diagnostic.level = level;
             }
         }
         self.0
@@ -206,6 +218,76 @@ impl Diagnostics {
     }
 }
 
+/// Returns the level that the provided diagnostic should be updated to, or `None` if its level shouldn't change.
+/// Only lints and non-error generator diagnostics can be updated. Errors (including errors reported by code-generators)
+/// always have a level of `Error`.
+fn get_updated_level(
+    diagnostic: &Diagnostic,
+    ast: &Ast,
+    files: &[SliceFile],
+    options: &SliceOptions,
+) -> Option<DiagnosticLevel> {
+    // Helper function that checks whether a diagnostic should be allowed according to the provided identifiers.
+    fn is_allowed_by<'b>(mut identifiers: impl Iterator<Item = &'b String>, code: &str) -> bool {
+        identifiers.any(|identifier| identifier == "All" || identifier == code)
+    }
+
+    // Helper function that checks whether a diagnostic is allowed by attributes on the provided entity.
+    fn is_allowed_by_attributes(attributable: &(impl Attributable + ?Sized), code: &str) -> bool {
+        let attributes = attributable.all_attributes().into_iter();
+        let mut allowed = attributes.filter_map(|a| a.downcast::<attributes::Allow>());
+        allowed.any(|allow| is_allowed_by(allow.allowed_lints.iter(), code))
+    }
+
+    let is_allowable = match &diagnostic.kind {
+        DiagnosticKind::Error(_) => false,
+        DiagnosticKind::Lint(_) => true,
+        DiagnosticKind::Generator(generator_diagnostic) => generator_diagnostic.level != DiagnosticLevel::Error,
+    };
+    if !is_allowable {
+        return None;
+    }
+
+    let code = diagnostic.code();
+    let mut is_allowed = false;
+
+    // Check if the diagnostic is a lint that's denied by a `--deny` flag passed on the command line.
+    // Denied lints are reported as errors, unless they're also allowed (which is checked below).
+    // Diagnostics reported by code-generators can't be denied, since they're reported after files are written.
+    let is_lint = matches!(diagnostic.kind, DiagnosticKind::Lint(_));
+    let is_denied = is_lint && is_allowed_by(options.denied_lints.iter(), code);
+
+    // Check if the diagnostic is allowed by an `--allow` flag passed on the command line.
+    if is_allowed_by(options.allowed_lints.iter(), code) {
+        is_allowed = true;
+    }
+
+    // If the diagnostic has a span, check if it's affected by an `allow` attribute on its file.
+    if let Some(span) = diagnostic.span() {
+        let file = files.iter().find(|f| f.relative_path == span.file).expect("no file");
+        if is_allowed_by_attributes(file, code) {
+            is_allowed = true;
+        }
+    }
+
+    // If the diagnostic has a scope, check if it's affected by an `allow` attribute in that scope.
+    if let Some(scope) = diagnostic.scope() {
+        if let Ok(entity) = ast.find_element::<dyn Entity>(scope) {
+            if is_allowed_by_attributes(entity, code) {
+                is_allowed = true;
+            }
+        }
+    }
+
+    if is_allowed {
+        Some(DiagnosticLevel::Allowed)
+    } else if is_denied {
+        Some(DiagnosticLevel::Error)
+    } else {
+        None
+    }
+}
+
 pub fn get_totals(diagnostics: &[Diagnostic]) -> (usize, usize) {
     let (mut total_warnings, mut total_errors) = (0, 0);
 
@@ -213,7 +295,7 @@ pub fn get_totals(diagnostics: &[Diagnostic]) -> (usize, usize) {
         match diagnostic.level() {
             DiagnosticLevel::Error => total_errors += 1,
             DiagnosticLevel::Warning => total_warnings += 1,
-            DiagnosticLevel::Allowed => {}
+            DiagnosticLevel::Info | DiagnosticLevel::Allowed => {}
         }
     }
 
@@ -232,6 +232,159 @@ pub enum Error {
     // ----------------  Type Alias Errors ---------------- //
     /// A type alias had an optional underlying type.
     TypeAliasOfOptional,
+
+    // ----------------  Constant Errors ---------------- //
+    /// A constant's type was not an integral type, bool, string, or an enum without fields.
+    ConstantTypeNotSupported {
+        /// The identifier of the constant.
+        constant_identifier: String,
+        /// The name of the type that the constant was declared with.
+        kind: String,
+    },
+
+    /// A constant's value was not a valid value for its type (ex: a string literal for an integral type).
+    ConstantValueTypeMismatch {
+        /// The identifier of the constant.
+        constant_identifier: String,
+        /// The type that the constant was declared with.
+        type_string: String,
+        /// The value that the constant was given.
+        value: String,
+    },
+
+    /// A constant's value was outside the bounds of its integral type.
+    ConstantValueOutOfBounds {
+        /// The identifier of the constant.
+        constant_identifier: String,
+        /// The out of bounds value.
+        value: i128,
+        /// The minimum value of the constant's type.
+        min: i128,
+        /// The maximum value of the constant's type.
+        max: i128,
+    },
+
+    // ----------------  Default Value Errors ---------------- //
+    /// A field with a default value was not of an integral type, bool, string, or an enum without fields.
+    DefaultValueTypeNotSupported {
+        /// The identifier of the field.
+        field_identifier: String,
+        /// The name of the field's type.
+        kind: String,
+    },
+
+    /// A field's default value was not a valid value for its type (ex: a string literal for an integral type).
+    DefaultValueTypeMismatch {
+        /// The identifier of the field.
+        field_identifier: String,
+        /// The type of the field.
+        type_string: String,
+        /// The default value that the field was given.
+        value: String,
+    },
+
+    /// A field's default value was outside the bounds of its integral type.
+    DefaultValueOutOfBounds {
+        /// The identifier of the field.
+        field_identifier: String,
+        /// The out of bounds value.
+        value: i128,
+        /// The minimum value of the field's type.
+        min: i128,
+        /// The maximum value of the field's type.
+        max: i128,
+    },
+
+    // ----------------  Code Generation Errors ---------------- //
+    /// A generated file was missing from disk, or its contents on disk differ from what was generated.
+    /// This is only reported when running with `--check`.
+    GeneratedFileOutOfDate {
+        /// The path of the generated file.
+        path: String,
+        /// Whether the generated file was missing from disk (true) or had different contents on disk (false).
+        is_missing: bool,
+    },
+
+    /// A file that was previously generated is no longer generated, but still exists on disk.
+    StaleGeneratedFile {
+        /// The path of the stale file.
+        path: String,
+    },
+
+    /// A code-generator that was specified by name couldn't be found in any of the plugin search locations.
+    GeneratorNotFound {
+        /// The name that the code-generator was specified with.
+        name: String,
+        /// The names of the executables that were searched for (in order).
+        executables: Vec<String>,
+    },
+
+    /// Multiple code-generators with the same name were specified.
+    /// Generated files are tracked by the name of their code-generator, so each name can only be used once.
+    DuplicateGeneratorName {
+        /// The name that the code-generators share.
+        name: String,
+        /// The path of the first code-generator with this name.
+        first_path: String,
+        /// The path of the other code-generator with this name.
+        second_path: String,
+    },
+
+    /// Both the generated files and the AST JSON were requested to be written to stdout.
+    StdoutUsedTwice,
+
+    /// A code-generator didn't respond to the compiler's 'describe' request with a valid response.
+    GeneratorHandshakeFailed {
+        /// The name of the code-generator.
+        generator: String,
+        /// A description of why the handshake failed.
+        reason: String,
+    },
+
+    /// A code-generator uses a different version of the code-generator protocol than the compiler.
+    GeneratorProtocolMismatch {
+        /// The name of the code-generator.
+        generator: String,
+        /// The protocol version that the code-generator uses.
+        generator_version: u32,
+        /// The protocol version that the compiler uses.
+        compiler_version: u32,
+    },
+
+    /// A transformer returned Slice definitions which aren't well-formed (ex: a type ID that doesn't refer to a type).
+    InvalidTransformedDefinitions {
+        /// The name of the transformer.
+        transformer: String,
+        /// A message describing the problem.
+        message: String,
+    },
+
+    /// The output of a code-generator didn't match a snapshot of its expected output.
+    /// This is only reported when running the 'test-generator' command.
+    SnapshotMismatch {
+        /// The name of the test case.
+        test_case: String,
+        /// The path of the snapshot, relative to the test case's directory.
+        path: String,
+        /// Whether the snapshot was missing from disk (true) or had different contents on disk (false).
+        is_missing: bool,
+    },
+
+    /// A test case has a snapshot of a file that the code-generator didn't generate.
+    /// This is only reported when running the 'test-generator' command.
+    SnapshotNotGenerated {
+        /// The name of the test case.
+        test_case: String,
+        /// The path of the snapshot, relative to the test case's directory.
+        path: String,
+    },
+
+    // ----------------  Configuration File Errors ---------------- //
+    /// A configuration file contained invalid syntax, or an invalid value.
+    InvalidConfigFile {
+        /// A message describing the problem.
+        message: String,
+    },
 }
 
 implement_diagnostic_functions!(
@@ -495,6 +648,122 @@ implement_diagnostic_functions!(
         CannotBeCompact,
         format!("'{kind}' '{identifier}' cannot be marked compact"),
         kind, identifier
+    ),
+    (
+        "E037",
+        GeneratedFileOutOfDate,
+        if *is_missing {
+            format!("generated file '{path}' is missing")
+        } else {
+            format!("generated file '{path}' is out of date")
+        },
+        path, is_missing
+    ),
+    (
+        "E038",
+        StaleGeneratedFile,
+        format!("generated file '{path}' is no longer generated, and should be removed"),
+        path
+    ),
+    (
+        "E039",
+        GeneratorNotFound,
+        format!(
+            "unable to find code-generator '{name}': no executable named {} was found",
+            executables.iter().map(|executable| format!("'{executable}'")).collect::<Vec<_>>().join(" or "),
+        ),
+        name, executables
+    ),
+    (
+        "E040",
+        GeneratorHandshakeFailed,
+        format!("code-generator '{generator}' did not respond to the 'describe' request: {reason}"),
+        generator, reason
+    ),
+    (
+        "E041",
+        GeneratorProtocolMismatch,
+        format!("code-generator '{generator}' uses version {generator_version} of the code-generator protocol, but slicec uses version {compiler_version}"),
+        generator, generator_version, compiler_version
+    ),
+    (
+        "E042",
+        InvalidConfigFile,
+        format!("invalid configuration file: {message}"),
+        message
+    ),
+    (
+        "E043",
+        InvalidTransformedDefinitions,
+        format!("transformer '{transformer}' returned invalid Slice definitions: {message}"),
+        transformer, message
+    ),
+    (
+        "E044",
+        SnapshotMismatch,
+        if *is_missing {
+            format!("test case '{test_case}' has no snapshot for '{path}'")
+        } else {
+            format!("'{path}' does not match its snapshot in test case '{test_case}'")
+        },
+        test_case, path, is_missing
+    ),
+    (
+        "E045",
+        SnapshotNotGenerated,
+        format!("test case '{test_case}' has a snapshot for '{path}', but it was not generated"),
+        test_case, path
+    ),
+    (
+        "E046",
+        ConstantTypeNotSupported,
+        format!("invalid constant '{constant_identifier}': type '{kind}' is not supported; constants can only have integral, bool, string, or enum (without fields) types"),
+        constant_identifier, kind
+    ),
+    (
+        "E047",
+        ConstantValueTypeMismatch,
+        format!("invalid constant '{constant_identifier}': '{value}' is not a valid value for type '{type_string}'"),
+        constant_identifier, type_string, value
+    ),
+    (
+        "E048",
+        ConstantValueOutOfBounds,
+        format!(
+            "invalid constant '{constant_identifier}': value '{value}' is out of bounds. The value must be between '{min}..{max}', inclusive",
+        ),
+        constant_identifier, value, min, max
+    ),
+    (
+        "E049",
+        DefaultValueTypeNotSupported,
+        format!("invalid default value for field '{field_identifier}': fields of type '{kind}' cannot have default values; only fields of integral, bool, string, or enum types can"),
+        field_identifier, kind
+    ),
+    (
+        "E050",
+        DefaultValueTypeMismatch,
+        format!("invalid default value for field '{field_identifier}': '{value}' is not a valid value for type '{type_string}'"),
+        field_identifier, type_string, value
+    ),
+    (
+        "E051",
+        DefaultValueOutOfBounds,
+        format!(
+            "invalid default value for field '{field_identifier}': value '{value}' is out of bounds. The value must be between '{min}..{max}', inclusive",
+        ),
+        field_identifier, value, min, max
+    ),
+    (
+        "E052",
+        DuplicateGeneratorName,
+        format!("code-generators '{first_path}' and '{second_path}' are both named '{name}'"),
+        name, first_path, second_path
+    ),
+    (
+        "E053",
+        StdoutUsedTwice,
+        "'-O -' and '--emit-ast-json -' cannot be used together, since they would both write to stdout"
     )
 );
 
@@ -0,0 +1,20 @@
+// Copyright (c) ZeroC, Inc.
+
+use super::DiagnosticLevel;
+
+/// A diagnostic that was reported by a code-generator, instead of by the compiler itself.
+#[derive(Debug)]
+pub struct GeneratorDiagnostic {
+    /// The name of the code-generator that reported this diagnostic.
+    pub generator: String,
+
+    /// The level that the code-generator reported this diagnostic with.
+    pub level: DiagnosticLevel,
+
+    /// The message describing this diagnostic.
+    pub message: String,
+
+    /// The fully-scoped identifier of the Slice element this diagnostic refers to (if it refers to one).
+    /// This is resolved to a [Span](crate::slice_file::Span) when the diagnostic is updated.
+    pub source: Option<String>,
+}
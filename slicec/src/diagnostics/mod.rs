@@ -5,10 +5,12 @@ use serde::Serialize;
 
 mod diagnostic;
 mod errors;
+mod generator;
 mod lints;
 
 pub use diagnostic::*;
 pub use errors::Error;
+pub use generator::GeneratorDiagnostic;
 pub use lints::Lint;
 
 /// Stores additional information about a diagnostic.
@@ -0,0 +1,473 @@
+// Copyright (c) ZeroC, Inc.
+
+//! This module contains the [Driver], which runs slicec from start to finish: it compiles the Slice files specified by
+//! a set of options, runs the code-generators on them, and then writes the generated files and emits any diagnostics.
+//! The 'slicec' executable is just a driver with the built-in code-generators registered.
+
+use crate::cache::{self, load_cache};
+use crate::code_generator::CodeGenerator;
+use crate::compilation_state::CompilationState;
+use crate::diagnostic_emitter::DiagnosticEmitter;
+use crate::diagnostics::{Diagnostic, Diagnostics};
+use crate::generation::{
+    convert_generator_error_to_diagnostic, convert_slice_files, create_compilation_context,
+    encode_generate_code_request, encode_plugin_payload, handle_generated_files, handle_generator_response,
+    save_request_file, write_bundled_files, write_depfile, GenerationState, GeneratorJob, OutputMode,
+};
+use crate::handshake::HandshakeCache;
+use crate::json_generator::{self, JsonGenerator};
+use crate::manifest::{clean_generated_files, handle_stale_files, load_manifest};
+use crate::slice_options::{DiagnosticFormat, SliceOptions, SlicecCommand};
+use crate::utils::plugin_util::resolve_generators;
+use crate::watch::{self, Snapshot};
+use crate::{config_file, handshake, plugin_process, replay, test_generator, transform};
+use std::io::{Error, Write};
+use std::num::NonZeroUsize;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::time::Duration;
+
+/// Runs slicec with a set of options, and a set of code-generators which are run in-process (instead of as plugins).
+///
+/// Code-generators that are written in Rust can implement [CodeGenerator] and be registered with a driver, so that a
+/// custom executable can run them without starting a separate process, or encoding and decoding their requests.
+/// Any code-generators which aren't registered with the driver are still run as plugins.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use clap::Parser;
+/// # use slicec::code_generator::CodeGenerator;
+/// # use slicec::definition_types::{CompilationContext, Diagnostic, GeneratedFile, SliceFile};
+/// # use slicec::driver::Driver;
+/// # use slicec::slice_options::{Plugin, SliceOptions};
+/// # use std::process::ExitCode;
+/// struct MyGenerator;
+///
+/// impl CodeGenerator for MyGenerator {
+///     fn name(&self) -> &str {
+///         "my-generator"
+///     }
+///
+///     fn generate_code(
+///         &self,
+///         source_files: &[SliceFile],
+///         reference_files: &[SliceFile],
+///         context: &CompilationContext,
+///         args: &[(String, String)],
+///     ) -> (Vec<GeneratedFile>, Vec<Diagnostic>) {
+///         // ...
+///         # (Vec::new(), Vec::new())
+///     }
+/// }
+///
+/// fn main() -> ExitCode {
+///     let mut driver = Driver::new();
+///     driver.register_generator(MyGenerator);
+///
+///     // Always run our code-generator, without users having to pass '-G my-generator'.
+///     let mut options = SliceOptions::parse();
+///     options.generators.push(Plugin {
+///         path: "my-generator".to_owned(),
+///         args: Vec::new(),
+///     });
+///     driver.run(options)
+/// }
+/// ```
+pub struct Driver {
+    /// The code-generators which are run in-process when they're selected by name.
+    generators: Vec<Box<dyn CodeGenerator>>,
+}
+
+impl Driver {
+    /// Creates a new driver, with slicec's built-in code-generators already registered.
+    pub fn new() -> Self {
+        let mut driver = Driver { generators: Vec::new() };
+        driver.register_generator(JsonGenerator);
+        driver
+    }
+
+    /// Registers a code-generator to be run in-process. Whenever it's selected by name (with '--generator'), it's run
+    /// instead of searching for a plugin with that name. Any previously registered code-generator with the same name
+    /// is replaced by this one.
+    pub fn register_generator(&mut self, generator: impl CodeGenerator + 'static) {
+        self.generators.retain(|other| other.name() != generator.name());
+        self.generators.push(Box::new(generator));
+    }
+
+    /// Returns the registered code-generator with the provided name, if there is one.
+    fn find_generator(&self, name: &str) -> Option<&dyn CodeGenerator> {
+        let generator = self.generators.iter().find(|generator| generator.name() == name);
+        generator.map(Box::as_ref)
+    }
+
+    /// Runs slicec with the provided options, and returns the exit code that it should exit with.
+    /// If '--watch' was specified, this never returns, and instead reruns slicec whenever a Slice file changes.
+    pub fn run(&self, options: SliceOptions) -> ExitCode {
+        // If a subcommand was specified, we run it instead of compiling anything.
+        match &options.command {
+            Some(SlicecCommand::ReplayRequest { request_file, generator }) => {
+                let code_generator = self.find_generator(generator);
+                return replay::replay_request(request_file, generator, code_generator, &options);
+            }
+            Some(SlicecCommand::TestGenerator {
+                test_dir,
+                generator,
+                bless,
+            }) => {
+                let code_generator = self.find_generator(&generator.path);
+                return test_generator::test_generator(test_dir, generator, code_generator, *bless, &options);
+            }
+            None => {}
+        }
+
+        // Handshakes are kept between reruns, so plugins are only described again if their executable changes.
+        let mut handshake_cache = HandshakeCache::default();
+
+        loop {
+            // Each run starts from the original options, since they're updated with the configuration file's options.
+            let mut slice_options = options.clone();
+
+            // Load the configuration file (if there is one), and merge its options with the command line options.
+            // If the configuration file is invalid, we report its errors instead of compiling anything.
+            let mut config_state = CompilationState::create();
+            let config_file = config_file::apply_config_file(&mut slice_options, &mut config_state.diagnostics);
+            config_state.files.extend(config_file);
+            let is_config_valid = !config_state.diagnostics.has_errors();
+            if !is_config_valid {
+                config_state.emit_diagnostics(&slice_options);
+            }
+
+            // If we're watching for changes, snapshot the files before compiling them, so no changes can be missed.
+            let snapshot = slice_options.watch.then(|| Snapshot::take(&slice_options));
+
+            let exit_code = match is_config_valid {
+                true => self.compile_and_generate(&mut slice_options, &mut handshake_cache),
+                false => ExitCode::FAILURE,
+            };
+
+            // If we're not watching for changes, we're finished. Otherwise, we wait for a file to change and rerun.
+            let Some(snapshot) = snapshot else {
+                return exit_code;
+            };
+            let is_human_format = slice_options.diagnostic_format == DiagnosticFormat::Human;
+            if is_human_format {
+                let watching = console::style("Watching").cyan().bold();
+                let file_count = snapshot.file_count();
+                println!("{watching}: waiting for changes to {file_count} file(s)...");
+            }
+            let changed_path = watch::wait_for_change(&options, &snapshot);
+            if is_human_format {
+                let changed = console::style("Changed").cyan().bold();
+                println!("{changed}: '{}', recompiling...", changed_path.display());
+            }
+        }
+    }
+
+    /// Compiles the Slice files specified by the provided options, runs the code-generators on them, and handles the
+    /// files and diagnostics that they return. This returns the exit code that slicec should exit with.
+    /// Plugins whose handshake is stored in the provided cache aren't described again.
+    fn compile_and_generate(&self, slice_options: &mut SliceOptions, handshake_cache: &mut HandshakeCache) -> ExitCode {
+        // Resolve the paths of any code-generators that were specified by name (instead of by path).
+        // Code-generators that are registered with this driver are run in-process, so there's nothing to resolve.
+        let mut resolution_diagnostics = Diagnostics::new();
+        let registered_names = self
+            .generators
+            .iter()
+            .map(|generator| generator.name())
+            .collect::<Vec<_>>();
+        resolve_generators(slice_options, &registered_names, &mut resolution_diagnostics);
+
+        // The generated files and the AST JSON can't both be written to 'stdout', since they'd be mixed together.
+        if slice_options.output_dir.as_deref() == Some("-") && slice_options.emit_ast_json.as_deref() == Some("-") {
+            Diagnostic::new(crate::diagnostics::Error::StdoutUsedTwice).push_into(&mut resolution_diagnostics);
+        }
+
+        // If we're cleaning up previously generated files, we do that instead of compiling anything.
+        // We don't clean anything if a code-generator couldn't be found, since we'd be unable to tell which files are
+        // its.
+        if slice_options.clean {
+            let mut compilation_state = CompilationState::create();
+            compilation_state.diagnostics = resolution_diagnostics;
+            if !compilation_state.diagnostics.has_errors() {
+                clean_generated_files(slice_options, &mut compilation_state.diagnostics);
+            }
+            return match compilation_state.emit_diagnostics(slice_options) {
+                false => ExitCode::SUCCESS,
+                true => ExitCode::FAILURE,
+            };
+        }
+
+        // Check whether the generated files should be bundled together, instead of being written to the output
+        // directory. If so, there's no output directory, and nothing is written to disk other than the archive
+        // (if there is one).
+        let output_mode = match (&slice_options.output_archive, slice_options.output_dir.as_deref()) {
+            (Some(archive_path), _) => OutputMode::Archive(archive_path.clone()),
+            (None, Some("-")) => OutputMode::Stdout,
+            _ => OutputMode::Directory,
+        };
+        if output_mode != OutputMode::Directory {
+            slice_options.output_dir = None;
+        }
+
+        // Perform the compilation.
+        let compilation_state = crate::compile_from_options(slice_options);
+        let CompilationState {
+            ast,
+            mut diagnostics,
+            files,
+        } = compilation_state;
+        diagnostics.extend(resolution_diagnostics);
+
+        // If a plugin's inputs are unchanged since it last ran, and its generated files are intact, we reuse its
+        // cached response instead of running it again. The cache also stores which plugins passed their handshake.
+        // Since bundled files aren't written to the output directory, we don't cache them.
+        let use_cache = !slice_options.no_cache && output_mode == OutputMode::Directory;
+        let mut cache = use_cache.then(|| load_cache(slice_options));
+
+        // If there were no errors in the Slice files, and all the plugins could be found, convert the parsed files into
+        // the representation that's sent to code-generators, and then run any transformers over them.
+        // Lints which are denied count as errors here, even though their level isn't updated until they're emitted.
+        let has_errors = diagnostics.has_errors() || diagnostics.has_denied_lints(&ast, &files, slice_options);
+        let converted_files = (!has_errors).then(|| {
+            let (mut source_files, mut reference_files) = convert_slice_files(&files);
+            let context = create_compilation_context(&files, None, slice_options);
+            let (sources, references) = (&mut source_files, &mut reference_files);
+            transform::run_transformers(
+                sources,
+                references,
+                &context,
+                &ast,
+                handshake_cache,
+                cache.as_mut(),
+                slice_options,
+                &mut diagnostics,
+            );
+            (source_files, reference_files, context)
+        });
+
+        // Only invoke the code-generators if all of the above succeeded.
+        if let Some((source_files, reference_files, context)) = converted_files.filter(|_| !diagnostics.has_errors()) {
+            // Encode the request which will be sent to each of the code-generation plugins.
+            let encoded_request = match encode_generate_code_request(&source_files, &reference_files, &context) {
+                Ok(result) => result,
+                Err(error) => {
+                    eprintln!("Critical error: failed to encode request payload!\n{error:?}");
+                    return ExitCode::from(79);
+                }
+            };
+
+            // Determine how each of the code-generators should be run. For plugins, this means encoding their payload.
+            // If requested, we also save the payload sent to each plugin, so that it can be replayed later.
+            let mut jobs = Vec::new();
+            for generator in &slice_options.generators {
+                let job = match self.find_generator(&generator.path) {
+                    Some(code_generator) => {
+                        // In-process generators aren't sent a payload, but we still save it, so it can be replayed.
+                        if slice_options.emit_request.is_some() {
+                            if let Ok(payload) = encode_plugin_payload(generator, &encoded_request) {
+                                save_request_file(generator, &payload, slice_options, &mut diagnostics);
+                            }
+                        }
+                        GeneratorJob::InProcess(code_generator)
+                    }
+                    None => {
+                        let payload = encode_plugin_payload(generator, &encoded_request)
+                            .map_err(Error::from)
+                            .inspect(|payload| save_request_file(generator, payload, slice_options, &mut diagnostics));
+                        let cache_key = match (&cache, &payload) {
+                            (Some(_), Ok(payload)) => cache::compute_key(&generator.path, payload),
+                            _ => None,
+                        };
+                        let output_dir = Path::new(slice_options.output_dir.as_deref().unwrap_or_default());
+                        let cached_response = cache
+                            .as_ref()
+                            .zip(cache_key.as_deref())
+                            .and_then(|(cache, key)| cache.get(generator.name(), key, output_dir));
+                        match cached_response {
+                            Some((generated_files, generator_diagnostics)) => {
+                                GeneratorJob::Cached(generated_files, generator_diagnostics)
+                            }
+                            None => GeneratorJob::Plugin(payload, cache_key),
+                        }
+                    }
+                };
+                jobs.push((generator, job));
+            }
+
+            // Perform a handshake with each of the plugins we're going to run, to check that they use the same protocol
+            // version as us. Any plugins that fail the handshake are skipped, so they don't fail with obscure decoding
+            // errors. In-process generators always use the same protocol as us, so there's no need for a handshake.
+            let timeout = slice_options.generator_timeout.map(Duration::from_secs);
+            let max_jobs = slice_options.jobs.map(NonZeroUsize::get);
+            let plugins = jobs.iter().filter(|(_, job)| matches!(job, GeneratorJob::Plugin(..)));
+            let plugins = plugins.map(|(plugin, _)| *plugin).collect::<Vec<_>>();
+            let handshake_results = handshake::describe_plugins(
+                &plugins,
+                handshake_cache,
+                cache.as_mut(),
+                max_jobs,
+                timeout,
+                &mut diagnostics,
+            );
+            let mut handshake_results = handshake_results.into_iter();
+            jobs.retain(|(_, job)| match job {
+                GeneratorJob::Plugin(..) => handshake_results.next().unwrap(),
+                _ => true,
+            });
+
+            // Run the plugins in parallel (up to the job limit), and block until they're all finished.
+            let plugin_payloads = jobs
+                .iter_mut()
+                .filter_map(|(plugin, job)| match job {
+                    GeneratorJob::Plugin(payload, _) => {
+                        Some((plugin.path.as_str(), std::mem::replace(payload, Ok(Vec::new()))))
+                    }
+                    _ => None,
+                })
+                .collect();
+            let mut generator_responses = plugin_process::run_plugins(plugin_payloads, max_jobs, timeout).into_iter();
+
+            // If a generator completed successfully, we write any generated files in its response, and store any
+            // diagnostics the generator reported so we can emit them at the end along with all the others.
+            // Responses are handled in the order the generators were specified in, regardless of when they finished.
+            // Bundled files aren't written to the output directory, so there's no manifest to track them with.
+            let manifest = match output_mode {
+                OutputMode::Directory => load_manifest(slice_options, &mut diagnostics),
+                _ => None,
+            };
+            let mut state = GenerationState {
+                manifest,
+                output_mode: output_mode.clone(),
+                ..GenerationState::default()
+            };
+            for (generator, job) in jobs {
+                let generator_result = match job {
+                    GeneratorJob::InProcess(code_generator) => {
+                        let args = &generator.args;
+                        let (generated_files, generator_diagnostics) =
+                            code_generator.generate_code(&source_files, &reference_files, &context, args);
+                        handle_generated_files(
+                            generator,
+                            generated_files,
+                            generator_diagnostics,
+                            slice_options,
+                            &mut state,
+                        )
+                    }
+                    GeneratorJob::Cached(generated_files, generator_diagnostics) => handle_generated_files(
+                        generator,
+                        generated_files,
+                        generator_diagnostics,
+                        slice_options,
+                        &mut state,
+                    ),
+                    GeneratorJob::Plugin(_, cache_key) => generator_responses
+                        .next()
+                        .unwrap() // Returns the response payload if the generator ran successfully.
+                        .and_then(|payload| {
+                            handle_generator_response(generator, payload, cache_key, &mut cache, slice_options, &mut state)
+                        }), // Returns any diagnostics if the payload successfully decoded.
+                };
+                let generator_diagnostics =
+                    generator_result.unwrap_or_else(|err| convert_generator_error_to_diagnostic(generator, err));
+
+                diagnostics.extend(generator_diagnostics); // Store the generator's diagnostics for later emission.
+            }
+
+            // Clean up any files which were previously generated, but no longer are, and then update the manifest and
+            // the generation cache. We don't update either of them for dry-runs and checks, since neither of them
+            // should write anything to disk.
+            handle_stale_files(&state, slice_options, &mut diagnostics);
+            if !slice_options.dry_run && !slice_options.check {
+                if let Some(manifest) = &state.manifest {
+                    if let Err(error) = manifest.save() {
+                        let action = "write manifest";
+                        let path = manifest.path().display().to_string();
+                        Diagnostic::new(crate::diagnostics::Error::IO { action, path, error })
+                            .push_into(&mut diagnostics);
+                    }
+                }
+                if let Some(cache) = &cache {
+                    if let Err(error) = cache.save() {
+                        let action = "write generation cache";
+                        let path = cache.path().display().to_string();
+                        Diagnostic::new(crate::diagnostics::Error::IO { action, path, error })
+                            .push_into(&mut diagnostics);
+                    }
+                }
+            }
+
+            // If the generated files are being bundled together, and code-generation succeeded, write the bundle.
+            if !diagnostics.has_errors() {
+                write_bundled_files(&state, &mut diagnostics);
+            }
+
+            // If requested, write the parsed Slice definitions as JSON, either to a file, or to 'stdout'.
+            // We skip this for dry-runs and checks, since neither of them should write anything to disk.
+            if let Some(json_path) = &slice_options.emit_ast_json {
+                if !slice_options.dry_run && !slice_options.check {
+                    let json = json_generator::generate_ast_json(&source_files, &reference_files, &context);
+                    let result = match json_path.as_str() {
+                        "-" => std::io::stdout().write_all(json.as_bytes()),
+                        _ => std::fs::write(json_path, json),
+                    };
+                    if let Err(error) = result {
+                        let action = "write AST JSON";
+                        let path = json_path.clone();
+                        Diagnostic::new(crate::diagnostics::Error::IO { action, path, error })
+                            .push_into(&mut diagnostics);
+                    }
+                }
+            }
+
+            // If requested, and code-generation succeeded, write a dependency file for the generated files.
+            // We skip this for dry-runs and checks, since neither of them should write anything to disk.
+            if let Some(depfile_path) = &slice_options.depfile {
+                if !diagnostics.has_errors() && !slice_options.dry_run && !slice_options.check {
+                    // If the generated files were bundled into an archive, it's the only target, and if they were
+                    // written to 'stdout', there's no targets at all.
+                    let targets = match &state.output_mode {
+                        OutputMode::Directory => state.generated_paths.iter().map(|(path, _)| path.clone()).collect(),
+                        OutputMode::Archive(archive_path) => vec![PathBuf::from(archive_path)],
+                        OutputMode::Stdout => Vec::new(),
+                    };
+                    if let Err(error) = write_depfile(depfile_path, &targets, &files, slice_options.config.as_deref()) {
+                        let action = "write depfile";
+                        let path = depfile_path.clone();
+                        Diagnostic::new(crate::diagnostics::Error::IO { action, path, error })
+                            .push_into(&mut diagnostics);
+                    }
+                }
+            }
+        }
+
+        // Process the diagnostics (filter out allowed lints, and update diagnostic levels as necessary).
+        let updated_diagnostics = diagnostics.into_updated(&ast, &files, slice_options);
+        let (warning_count, error_count) = crate::diagnostics::get_totals(&updated_diagnostics);
+
+        // Print any diagnostics to the console, along with the total number of warnings and errors emitted.
+        let mut stderr = console::Term::stderr();
+        let mut emitter = DiagnosticEmitter::new(&mut stderr, slice_options, &files);
+        DiagnosticEmitter::emit_diagnostics(&mut emitter, updated_diagnostics).expect("failed to emit diagnostics");
+
+        // Only emit the summary message if we're writing human-readable output,
+        // and 'stdout' isn't being used for the AST JSON or generated files (since the summary would corrupt them).
+        let is_stdout_in_use = slice_options.emit_ast_json.as_deref() == Some("-") || output_mode == OutputMode::Stdout;
+        if slice_options.diagnostic_format == DiagnosticFormat::Human && !is_stdout_in_use {
+            crate::diagnostic_emitter::emit_totals(warning_count, error_count).expect("failed to emit totals");
+        }
+
+        // Finished.
+        match error_count == 0 {
+            true => ExitCode::SUCCESS,
+            false => ExitCode::FAILURE,
+        }
+    }
+}
+
+impl Default for Driver {
+    fn default() -> Self {
+        Self::new()
+    }
+}
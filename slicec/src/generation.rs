@@ -0,0 +1,671 @@
+// Copyright (c) ZeroC, Inc.
+
+//! This module contains the logic for running code-generators: encoding the requests that are sent to them, decoding
+//! their responses, and validating and writing the files that they generate (or bundling them together).
+
+use crate::cache::{Cache, CacheEntry};
+use crate::code_generator::CodeGenerator;
+use crate::definition_types;
+use crate::diagnostics::{Diagnostic, DiagnosticLevel, Diagnostics, GeneratorDiagnostic};
+use crate::manifest::Manifest;
+use crate::reporting::{check_generated_file, get_generated_file_status, report_generated_file, GeneratedFileStatus};
+use crate::slice_file::SliceFile;
+use crate::slice_options::{Plugin, SliceOptions};
+use slice_codec::decoder::Decoder;
+use slice_codec::encoder::Encoder;
+use std::fs::File;
+use std::io::{Error, ErrorKind, Write};
+use std::path::{Component, Path, PathBuf};
+
+/// Attempts to encode a 'generateCode' request for the provided Slice files into a byte-buffer.
+/// If the encoding succeeds, this returns `Ok` with the encoded bytes,
+/// otherwise this returns `Err` with an error describing the failure.
+pub(crate) fn encode_generate_code_request(
+    source_files: &[definition_types::SliceFile],
+    reference_files: &[definition_types::SliceFile],
+    context: &definition_types::CompilationContext,
+) -> Result<Vec<u8>, slice_codec::Error> {
+    // Create a buffer to encode into, and an encoder over-top of it.
+    let mut encoding_buffer: Vec<u8> = Vec::new();
+    let mut slice_encoder = Encoder::from(&mut encoding_buffer);
+
+    // Encode the 'operation name'.
+    slice_encoder.encode("generateCode")?;
+
+    // Encode the Slice-files as 2 sequences; one of source files, and one of reference files.
+    slice_encoder.encode(source_files)?;
+    slice_encoder.encode(reference_files)?;
+
+    // Encode the context that these files were compiled in.
+    slice_encoder.encode(context)?;
+
+    // We're done!
+    Ok(encoding_buffer)
+}
+
+/// Sorts the parsed files into two groups: source files and reference files.
+/// We also convert from the AST representation to the Slice representation at this time.
+pub(crate) fn convert_slice_files(
+    parsed_files: &[SliceFile],
+) -> (Vec<definition_types::SliceFile>, Vec<definition_types::SliceFile>) {
+    let mut source_files = Vec::new();
+    let mut reference_files = Vec::new();
+    for parsed_file in parsed_files {
+        // Convert the Slice file from AST representation to Slice representation.
+        let converted_file = definition_types::SliceFile::from(parsed_file);
+        // Determine whether this is a source or reference file and place it accordingly.
+        match parsed_file.is_source {
+            true => source_files.push(converted_file),
+            false => reference_files.push(converted_file),
+        }
+    }
+    (source_files, reference_files)
+}
+
+/// Creates a description of the context that the provided Slice files were compiled in, for the code-generators.
+/// The files' relative paths are resolved against the provided base directory, or the working directory if it's `None`.
+pub(crate) fn create_compilation_context(
+    parsed_files: &[SliceFile],
+    base_dir: Option<&Path>,
+    options: &SliceOptions,
+) -> definition_types::CompilationContext {
+    // Helper function which returns the absolute version of a path. If this fails, the path is returned as-is.
+    fn get_absolute_path(path: &Path) -> String {
+        let absolute_path = std::path::absolute(path).unwrap_or_else(|_| path.to_owned());
+        absolute_path.display().to_string()
+    }
+
+    let absolute_paths = parsed_files.iter().map(|file| {
+        let path = base_dir.unwrap_or(Path::new("")).join(&file.relative_path);
+        (file.relative_path.clone(), get_absolute_path(&path))
+    });
+    let output_dir = Path::new(options.output_dir.as_deref().unwrap_or("."));
+
+    definition_types::CompilationContext {
+        compiler_version: env!("CARGO_PKG_VERSION").to_owned(),
+        defined_symbols: options.defined_symbols.clone(),
+        absolute_paths: absolute_paths.collect(),
+        output_dir: get_absolute_path(output_dir),
+    }
+}
+
+/// Encodes the complete payload that is written to a code-generator's 'stdin'.
+/// This is the encoded 'generateCode' request, followed by the generator's encoded arguments.
+pub(crate) fn encode_plugin_payload(plugin: &Plugin, encoded_request: &[u8]) -> Result<Vec<u8>, slice_codec::Error> {
+    let mut plugin_payload = encoded_request.to_vec();
+    let mut slice_encoder = Encoder::from(&mut plugin_payload);
+    slice_encoder.encode(definition_types::Arguments(plugin.args.clone()))?;
+    Ok(plugin_payload)
+}
+
+/// The contents of a plugin's payload: the source files, reference files, and compilation context from the
+/// 'generateCode' request, followed by the generator's arguments.
+pub(crate) type PluginPayload = (
+    Vec<definition_types::SliceFile>,
+    Vec<definition_types::SliceFile>,
+    definition_types::CompilationContext,
+    Vec<(String, String)>,
+);
+
+/// Decodes a payload that was encoded by [encode_plugin_payload].
+pub(crate) fn decode_plugin_payload(plugin_payload: &[u8]) -> std::io::Result<PluginPayload> {
+    let mut slice_decoder = Decoder::from(plugin_payload);
+    let operation = slice_decoder.decode::<String>()?;
+    if operation != "generateCode" {
+        let message = format!("expected a 'generateCode' request, but got '{operation}'");
+        return Err(Error::new(ErrorKind::InvalidData, message));
+    }
+    let source_files = slice_decoder.decode()?;
+    let reference_files = slice_decoder.decode()?;
+    let context = slice_decoder.decode()?;
+    let definition_types::Arguments(args) = slice_decoder.decode()?;
+    Ok((source_files, reference_files, context, args))
+}
+
+/// Decodes a generator's response. It consists of 2 sequences, one of generated files and one of diagnostics.
+pub(crate) fn decode_generator_response(
+    response_payload: &[u8],
+) -> std::io::Result<(Vec<definition_types::GeneratedFile>, Vec<definition_types::Diagnostic>)> {
+    let mut slice_decoder = Decoder::from(response_payload);
+    let generated_files = slice_decoder.decode()?;
+    let generator_diagnostics = slice_decoder.decode()?;
+    Ok((generated_files, generator_diagnostics))
+}
+
+/// Returns the path that the request payload for the provided generator should be saved to.
+/// If there's only a single generator, this is just the path that was passed to '--emit-request'.
+/// Otherwise, the generator's name is inserted before the file's extension, so that each generator gets its own file.
+fn get_request_file_path(request_file: &str, generator: &Plugin, generator_count: usize) -> PathBuf {
+    let path = PathBuf::from(request_file);
+    if generator_count == 1 {
+        return path;
+    }
+
+    let mut file_name = path.file_stem().unwrap_or_default().to_os_string();
+    file_name.push(format!(".{}", generator.name()));
+    if let Some(extension) = path.extension() {
+        file_name.push(".");
+        file_name.push(extension);
+    }
+    path.with_file_name(file_name)
+}
+
+/// If '--emit-request' was specified, saves the payload that is sent to the provided generator to disk.
+pub(crate) fn save_request_file(
+    generator: &Plugin,
+    plugin_payload: &[u8],
+    options: &SliceOptions,
+    diagnostics: &mut Diagnostics,
+) {
+    if let Some(request_file) = &options.emit_request {
+        let path = get_request_file_path(request_file, generator, options.generators.len());
+        if let Err(error) = std::fs::write(&path, plugin_payload) {
+            let action = "write request file";
+            let path = path.display().to_string();
+            Diagnostic::new(crate::diagnostics::Error::IO { action, path, error }).push_into(diagnostics);
+        }
+    }
+}
+
+/// Stores the state that's shared between all the code-generators while their responses are being handled.
+#[derive(Debug, Default)]
+pub(crate) struct GenerationState {
+    /// The paths of all the files that have been generated so far, along with the names of their generators.
+    pub(crate) generated_paths: Vec<(PathBuf, String)>,
+
+    /// The manifest of previously generated files, or `None` if it couldn't be loaded.
+    pub(crate) manifest: Option<Manifest>,
+
+    /// The paths of files that were previously generated, but no longer are, along with the names of their generators.
+    pub(crate) stale_paths: Vec<(PathBuf, String)>,
+
+    /// Where the generated files are written to.
+    pub(crate) output_mode: OutputMode,
+
+    /// The generated files which are bundled together, instead of being written to the output directory.
+    pub(crate) bundled_files: Vec<definition_types::GeneratedFile>,
+}
+
+/// Describes where generated files are written to.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub(crate) enum OutputMode {
+    /// Each generated file is written to its own file in the output directory.
+    #[default]
+    Directory,
+
+    /// All the generated files are bundled into a single tar archive, which is written to the provided path.
+    Archive(String),
+
+    /// All the generated files are bundled into a single stream, which is written to 'stdout'.
+    Stdout,
+}
+
+/// Describes how a code-generator will be run.
+pub(crate) enum GeneratorJob<'a> {
+    /// The code-generator is registered with the driver, and so is run in-process.
+    InProcess(&'a dyn CodeGenerator),
+
+    /// The code-generator is run as a plugin, with the provided payload.
+    /// If the generation cache is enabled, this also holds the plugin's cache key (unless it couldn't be computed).
+    Plugin(std::io::Result<Vec<u8>>, Option<String>),
+
+    /// The code-generator's inputs are unchanged since it last ran, so it isn't run again.
+    /// Instead, the files and diagnostics it previously returned are reused.
+    Cached(Vec<definition_types::GeneratedFile>, Vec<definition_types::Diagnostic>),
+}
+
+/// Handles a plugin's response. If the response is handled without any errors, and the plugin has a cache key,
+/// the response is also stored in the generation cache, so the plugin can be skipped if its inputs don't change.
+pub(crate) fn handle_generator_response(
+    generator: &Plugin,
+    response_payload: Vec<u8>,
+    cache_key: Option<String>,
+    cache: &mut Option<Cache>,
+    options: &SliceOptions,
+    state: &mut GenerationState,
+) -> std::io::Result<Diagnostics> {
+    let (generated_files, generator_diagnostics) = decode_generator_response(&response_payload)?;
+    let cache_entry = cache_key.map(|key| CacheEntry::new(key, &generated_files, &generator_diagnostics));
+
+    let diagnostics = handle_generated_files(generator, generated_files, generator_diagnostics, options, state)?;
+    match (cache, cache_entry) {
+        (Some(cache), Some(cache_entry)) if !diagnostics.has_errors() => cache.insert(generator.name(), cache_entry),
+        (Some(cache), _) => cache.remove(generator.name()),
+        _ => {}
+    }
+    Ok(diagnostics)
+}
+
+/// Handles the files and diagnostics that a generator returned (whether it's a plugin, or run in-process).
+pub(crate) fn handle_generated_files(
+    generator: &Plugin,
+    generated_files: Vec<definition_types::GeneratedFile>,
+    generator_diagnostics: Vec<definition_types::Diagnostic>,
+    options: &SliceOptions,
+    state: &mut GenerationState,
+) -> std::io::Result<Diagnostics> {
+    // Convert the diagnostics we decoded from the generator into diagnostics that slicec can handle.
+    let mut diagnostics = Diagnostics::new();
+    for generator_diagnostic in generator_diagnostics {
+        convert_generator_diagnostic(generator, generator_diagnostic).push_into(&mut diagnostics);
+    }
+
+    // If no errors were reported, make sure the paths of all the generated files are valid, and that no other file
+    // has already been generated at any of those paths.
+    let mut validated_files = Vec::new();
+    if !diagnostics.has_errors() {
+        for generated_file in &generated_files {
+            match validate_generated_file_path(generated_file, &options.output_dir, &state.generated_paths) {
+                Ok(path) => {
+                    state.generated_paths.push((path.clone(), generator.name().to_owned()));
+                    validated_files.push((path, generated_file));
+                }
+                Err(io_error) => {
+                    let diagnostic = crate::diagnostics::Error::IO {
+                        action: "write generated file",
+                        path: generated_file.path.to_owned(),
+                        error: io_error,
+                    };
+                    Diagnostic::new(diagnostic).push_into(&mut diagnostics);
+                }
+            }
+        }
+    }
+
+    // If the response was valid, record its files in the manifest, replacing the files previously recorded for this
+    // generator. Any files which it previously generated, but didn't generate this time, are now stale.
+    if !diagnostics.has_errors() {
+        if let Some(manifest) = &mut state.manifest {
+            let paths = validated_files.iter().map(|(path, _)| path.as_path()).collect();
+            let stale_paths = manifest.replace(generator.name(), paths);
+            let name = generator.name();
+            state
+                .stale_paths
+                .extend(stale_paths.into_iter().map(|path| (path, name.to_owned())));
+        }
+    }
+
+    // If there's still no errors, attempt to generate the files in the response.
+    // If this is a dry-run, we instead report what would happen to each file, without writing anything to disk.
+    // And if we're checking the generated files, we instead report an error for each file that's out of date.
+    if !diagnostics.has_errors() {
+        for (path, generated_file) in validated_files {
+            let generated_file_bytes = generated_file.contents.as_bytes();
+
+            if options.dry_run {
+                let status = get_generated_file_status(&path, generated_file_bytes);
+                let format = options.diagnostic_format;
+                report_generated_file(generator.name(), &path, status, format, &mut diagnostics);
+                continue;
+            }
+
+            if options.check {
+                if let Some(diagnostic) = check_generated_file(&path, generated_file_bytes, options.diff) {
+                    diagnostic.push_into(&mut diagnostics);
+                }
+                continue;
+            }
+
+            // If the generated files are being bundled together, we store the file so it can be bundled at the end.
+            if state.output_mode != OutputMode::Directory {
+                state.bundled_files.push(definition_types::GeneratedFile {
+                    path: path.display().to_string(),
+                    contents: generated_file.contents.clone(),
+                });
+                continue;
+            }
+
+            // Try to write the generated file to disk.
+            if let Err(io_error) = write_generated_file(&path, generated_file_bytes) {
+                // If an error occurred during writing the file, create a diagnostic that slicec can report.
+                let diagnostic = crate::diagnostics::Error::IO {
+                    action: "write generated file",
+                    path: generated_file.path.to_owned(),
+                    error: io_error,
+                };
+                Diagnostic::new(diagnostic).push_into(&mut diagnostics);
+            }
+        }
+    }
+
+    // Return any decoded diagnostics, so slicec can emit them at the end.
+    Ok(diagnostics)
+}
+
+pub(crate) fn convert_generator_diagnostic(
+    generator: &Plugin,
+    generator_diagnostic: definition_types::Diagnostic,
+) -> Diagnostic {
+    let level = match generator_diagnostic.level {
+        definition_types::DiagnosticLevel::Info => DiagnosticLevel::Info,
+        definition_types::DiagnosticLevel::Warning => DiagnosticLevel::Warning,
+        definition_types::DiagnosticLevel::Error => DiagnosticLevel::Error,
+    };
+
+    Diagnostic::new(GeneratorDiagnostic {
+        generator: generator.name().to_owned(),
+        level,
+        message: generator_diagnostic.message,
+        source: generator_diagnostic.source,
+    })
+}
+
+/// Returns the path that the provided generated file should be written to, after checking that it's valid.
+/// If an output directory was specified, it's prepended to the generated file's relative path.
+///
+/// Generated file paths must be relative, and cannot escape the output directory (by using '..').
+/// Additionally, it's an error for multiple files to be generated at the same path, even by different generators.
+pub(crate) fn validate_generated_file_path(
+    generated_file: &definition_types::GeneratedFile,
+    output_dir: &Option<String>,
+    generated_paths: &[(PathBuf, String)],
+) -> std::io::Result<PathBuf> {
+    let normalized_path = normalize_relative_path(&generated_file.path)?;
+
+    let path = match output_dir {
+        Some(dir) => PathBuf::from(dir).join(normalized_path),
+        None => normalized_path,
+    };
+
+    // Check whether a file has already been generated at this path.
+    if let Some((_, generator_name)) = generated_paths.iter().find(|(other_path, _)| *other_path == path) {
+        let message = format!("a file was already generated at this path by '{generator_name}'");
+        return Err(Error::new(ErrorKind::AlreadyExists, message));
+    }
+
+    Ok(path)
+}
+
+/// Normalizes the provided path (which is relative to the output directory), by removing any '.' and '..' components.
+/// This returns an error if the path isn't relative, if it escapes the output directory, or if it has no file name.
+pub(crate) fn normalize_relative_path(path: &str) -> std::io::Result<PathBuf> {
+    // Windows prefixes (ex: 'C:' or '\\server') are only parsed as such on Windows, so we check for them explicitly.
+    // Otherwise, a path like 'C:\file' would be accepted on other platforms, as a file name containing '\'.
+    if has_windows_prefix(path) {
+        let message = "generated files must use relative paths";
+        return Err(Error::new(ErrorKind::InvalidInput, message));
+    }
+
+    // Normalize the path, checking that it doesn't escape the output directory as we do so.
+    let mut normalized_path = PathBuf::new();
+    for component in Path::new(path).components() {
+        match component {
+            Component::Normal(segment) => normalized_path.push(segment),
+            Component::CurDir => {}
+            Component::ParentDir if normalized_path.pop() => {}
+            Component::ParentDir => {
+                let message = "generated files cannot be written outside of the output directory";
+                return Err(Error::new(ErrorKind::InvalidInput, message));
+            }
+            Component::RootDir | Component::Prefix(_) => {
+                let message = "generated files must use relative paths";
+                return Err(Error::new(ErrorKind::InvalidInput, message));
+            }
+        }
+    }
+    if normalized_path.as_os_str().is_empty() {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            "generated files must have a file name",
+        ));
+    }
+
+    Ok(normalized_path)
+}
+
+/// Returns true if the provided path starts with a drive letter (ex: 'C:') or a UNC prefix (ex: '\\server').
+fn has_windows_prefix(path: &str) -> bool {
+    let bytes = path.as_bytes();
+    let has_drive_letter = bytes.len() >= 2 && bytes[0].is_ascii_alphabetic() && bytes[1] == b':';
+    has_drive_letter || path.starts_with("\\\\")
+}
+
+fn write_generated_file(generated_file_path: &Path, generated_file_bytes: &[u8]) -> std::io::Result<()> {
+    // If the generated file already exists on disk, and is identical to what we want to write,
+    // we don't overwrite the file, and instead return immediately.
+    if get_generated_file_status(generated_file_path, generated_file_bytes) == GeneratedFileStatus::Unchanged {
+        return Ok(());
+    }
+
+    // Create any missing parent directories, then write the generated file to disk.
+    if let Some(parent_dir) = generated_file_path.parent() {
+        std::fs::create_dir_all(parent_dir)?;
+    }
+    let mut file = File::create(generated_file_path)?;
+    file.write_all(generated_file_bytes)?;
+    Ok(())
+}
+
+/// Writes a dependency file to the provided path, using the Makefile syntax (which is also understood by Ninja).
+/// It contains a rule for each generated file, listing every Slice file (source and reference) as a prerequisite,
+/// since a change to any of the Slice files passed to the code-generators could affect any of the generated files.
+/// The configuration file (if one was loaded) is also listed as a prerequisite, since it can change the options too.
+///
+/// Drive letters in Windows paths (ex: 'C:\file') are left as-is, since both Make and Ninja accept them.
+pub(crate) fn write_depfile(
+    depfile_path: &str,
+    targets: &[PathBuf],
+    files: &[SliceFile],
+    config_file: Option<&str>,
+) -> std::io::Result<()> {
+    // Helper function which escapes any characters in a path that have a special meaning in Makefiles.
+    fn escape_path(path: &str) -> String {
+        let mut escaped = String::with_capacity(path.len());
+        for c in path.chars() {
+            match c {
+                ' ' | '#' => escaped.push('\\'),
+                '$' => escaped.push('$'),
+                _ => {}
+            }
+            escaped.push(c);
+        }
+        escaped
+    }
+
+    let file_paths = files.iter().map(|file| file.relative_path.as_str());
+    let prerequisites = file_paths.chain(config_file).map(escape_path);
+    let prerequisites = prerequisites.collect::<Vec<_>>().join(" \\\n  ");
+
+    let mut contents = String::new();
+    for target in targets {
+        let target = escape_path(&target.display().to_string());
+        contents += &format!("{target}: \\\n  {prerequisites}\n");
+    }
+    std::fs::write(depfile_path, contents)
+}
+
+/// Writes the generated files which were bundled together, either into a tar archive, or to 'stdout'.
+/// If the generated files aren't being bundled together, this does nothing.
+pub(crate) fn write_bundled_files(state: &GenerationState, diagnostics: &mut Diagnostics) {
+    let (result, action, path) = match &state.output_mode {
+        OutputMode::Directory => return,
+        OutputMode::Archive(archive_path) => {
+            let result = write_archive(Path::new(archive_path), &state.bundled_files);
+            (result, "write output archive", archive_path.clone())
+        }
+        OutputMode::Stdout => {
+            let result = write_stream(&mut std::io::stdout(), &state.bundled_files);
+            (result, "write generated files", "stdout".to_owned())
+        }
+    };
+    if let Err(error) = result {
+        Diagnostic::new(crate::diagnostics::Error::IO { action, path, error }).push_into(diagnostics);
+    }
+}
+
+/// Writes the provided generated files into a tar archive at the provided path, replacing any existing file.
+/// Every entry has the same modification time and permissions, so the archive only changes if the files do.
+fn write_archive(archive_path: &Path, generated_files: &[definition_types::GeneratedFile]) -> std::io::Result<()> {
+    // Create any missing parent directories, then write each of the generated files into the archive.
+    if let Some(parent_dir) = archive_path.parent() {
+        std::fs::create_dir_all(parent_dir)?;
+    }
+    let mut builder = tar::Builder::new(File::create(archive_path)?);
+    for generated_file in generated_files {
+        let contents = generated_file.contents.as_bytes();
+        let mut header = tar::Header::new_gnu();
+        header.set_size(contents.len() as u64);
+        header.set_mode(0o644);
+        header.set_mtime(0);
+        builder.append_data(&mut header, &generated_file.path, contents)?;
+    }
+    builder.into_inner()?.flush()
+}
+
+/// Writes the provided generated files to the provided writer, as a Slice-encoded sequence of `GeneratedFile`s.
+/// This is the same encoding that code-generators use for the generated files in their responses.
+fn write_stream(writer: &mut impl Write, generated_files: &[definition_types::GeneratedFile]) -> std::io::Result<()> {
+    let mut encoding_buffer: Vec<u8> = Vec::new();
+    let mut slice_encoder = Encoder::from(&mut encoding_buffer);
+    slice_encoder.encode(generated_files)?;
+
+    writer.write_all(&encoding_buffer)?;
+    writer.flush()
+}
+
+pub(crate) fn convert_generator_error_to_diagnostic(
+    generator: &Plugin,
+    generator_error: std::io::Error,
+) -> Diagnostics {
+    let mapped_io_error = crate::diagnostics::Error::IO {
+        action: "run code-generator",
+        path: generator.path.clone(),
+        error: generator_error,
+    };
+
+    let mut diagnostics = Diagnostics::new();
+    Diagnostic::new(mapped_io_error).push_into(&mut diagnostics);
+    diagnostics
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::definition_types;
+    use crate::utils::test_util::TempDir;
+    use clap::Parser;
+    use test_case::test_case;
+
+    fn generated_file(path: &str) -> definition_types::GeneratedFile {
+        definition_types::GeneratedFile {
+            path: path.to_owned(),
+            contents: String::new(),
+        }
+    }
+
+    #[test_case("foo.rs", "out/foo.rs"; "file")]
+    #[test_case("./nested/foo.rs", "out/nested/foo.rs"; "current dir component")]
+    #[test_case("nested/../foo.rs", "out/foo.rs"; "parent dir component")]
+    fn relative_paths_are_joined_to_the_output_dir(path: &str, expected: &str) {
+        // Arrange
+        let output_dir = Some("out".to_owned());
+
+        // Act
+        let result = validate_generated_file_path(&generated_file(path), &output_dir, &[]);
+
+        // Assert
+        assert_eq!(result.unwrap(), PathBuf::from(expected));
+    }
+
+    #[test_case("../foo.rs"; "parent dir")]
+    #[test_case("nested/../../foo.rs"; "nested parent dir")]
+    #[test_case("/foo.rs"; "absolute path")]
+    #[test_case("C:\\foo.rs"; "drive letter")]
+    #[test_case("c:foo.rs"; "drive relative path")]
+    #[test_case("\\\\server\\share\\foo.rs"; "unc path")]
+    #[test_case(""; "empty path")]
+    #[test_case("nested/.."; "path without file name")]
+    fn paths_outside_of_the_output_dir_are_rejected(path: &str) {
+        // Arrange
+        let output_dir = Some("out".to_owned());
+
+        // Act
+        let result = validate_generated_file_path(&generated_file(path), &output_dir, &[]);
+
+        // Assert
+        assert_eq!(result.unwrap_err().kind(), ErrorKind::InvalidInput);
+    }
+
+    #[test_case("foo.rs"; "same path")]
+    #[test_case("nested/../foo.rs"; "same normalized path")]
+    fn paths_cannot_be_generated_twice(path: &str) {
+        // Arrange
+        let output_dir = Some("out".to_owned());
+        let generated_paths = [(PathBuf::from("out/foo.rs"), "other-generator".to_owned())];
+
+        // Act
+        let result = validate_generated_file_path(&generated_file(path), &output_dir, &generated_paths);
+
+        // Assert
+        let error = result.unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::AlreadyExists);
+        assert!(error.to_string().contains("'other-generator'"));
+    }
+
+    #[test]
+    fn compilation_context_describes_the_compilation() {
+        // Arrange
+        let options = SliceOptions::parse_from(["", "-D", "FOO", "-D", "BAR", "-O", "generated"]);
+        let compilation_state = crate::compile_from_strings(&["module A", "module B"], Some(&options));
+
+        // Act
+        let context = create_compilation_context(&compilation_state.files, None, &options);
+
+        // Assert
+        assert_eq!(context.compiler_version, env!("CARGO_PKG_VERSION"));
+        assert_eq!(context.defined_symbols, ["FOO", "BAR"]);
+        assert_eq!(context.absolute_paths.keys().collect::<Vec<_>>(), [
+            "string-0", "string-1"
+        ]);
+        for (relative_path, absolute_path) in &context.absolute_paths {
+            assert!(Path::new(absolute_path).is_absolute());
+            assert!(absolute_path.ends_with(relative_path.as_str()));
+        }
+        assert!(Path::new(&context.output_dir).is_absolute());
+        assert!(context.output_dir.ends_with("generated"));
+    }
+
+    #[test]
+    fn depfiles_list_every_slice_file_and_the_config_file_for_each_target() {
+        // Arrange
+        let dir = TempDir::new("depfile-layout");
+        let depfile_path = dir.join("out.d").display().to_string();
+        let targets = [PathBuf::from("out/a.rs"), PathBuf::from("out/b.rs")];
+        let files = [
+            SliceFile::new("a.slice".to_owned(), String::new(), true),
+            SliceFile::new("refs/b.slice".to_owned(), String::new(), false),
+        ];
+
+        // Act
+        write_depfile(&depfile_path, &targets, &files, Some("slice.toml")).unwrap();
+
+        // Assert
+        let contents = std::fs::read_to_string(&depfile_path).unwrap();
+        assert_eq!(
+            contents,
+            concat!(
+                "out/a.rs: \\\n  a.slice \\\n  refs/b.slice \\\n  slice.toml\n",
+                "out/b.rs: \\\n  a.slice \\\n  refs/b.slice \\\n  slice.toml\n",
+            )
+        );
+    }
+
+    #[test_case("space", "my file.slice", "my\\ file.slice")]
+    #[test_case("hash", "#file.slice", "\\#file.slice")]
+    #[test_case("dollar", "$file.slice", "$$file.slice")]
+    #[test_case("drive", "C:\\dir\\file.slice", "C:\\dir\\file.slice")]
+    fn depfile_paths_are_escaped(name: &str, path: &str, expected: &str) {
+        // Arrange
+        let dir = TempDir::new(&format!("depfile-escaping-{name}"));
+        let depfile_path = dir.join("out.d").display().to_string();
+        let targets = [PathBuf::from(path)];
+        let files = [SliceFile::new(path.to_owned(), String::new(), true)];
+
+        // Act
+        write_depfile(&depfile_path, &targets, &files, None).unwrap();
+
+        // Assert
+        let contents = std::fs::read_to_string(&depfile_path).unwrap();
+        assert_eq!(contents, format!("{expected}: \\\n  {expected}\n"));
+    }
+}
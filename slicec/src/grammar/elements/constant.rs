@@ -0,0 +1,100 @@
+// Copyright (c) ZeroC, Inc.
+
+use super::super::*;
+use crate::slice_file::Span;
+use crate::utils::ptr_util::WeakPtr;
+
+#[derive(Debug)]
+pub struct Constant {
+    pub identifier: Identifier,
+    pub data_type: TypeRef,
+    pub value: ConstantValue,
+    pub scope: Scope,
+    pub attributes: Vec<WeakPtr<Attribute>>,
+    pub comment: Option<DocComment>,
+    pub span: Span,
+}
+
+impl Constant {
+    /// Returns the enumerator that this constant's value refers to, if the constant's type is an enum, and its value
+    /// is the identifier of one of that enum's enumerators. Otherwise this returns `None`.
+    pub fn enumerator(&self) -> Option<&Enumerator> {
+        match self.data_type.concrete_type() {
+            Types::Enum(enum_def) => self.value.as_enumerator(enum_def),
+            _ => None,
+        }
+    }
+}
+
+/// The value of a constant (or the default value of a field), as it was written in the Slice file.
+///
+/// Boolean values (`true` and `false`) and enumerators are both written as identifiers; which one an identifier
+/// refers to depends on the type that the value is used with. See [ConstantValue::as_bool] and
+/// [ConstantValue::as_enumerator].
+#[derive(Debug)]
+pub enum ConstantValue {
+    Integer(Integer<i128>),
+    String(String, Span),
+    Identifier(Identifier),
+}
+
+impl ConstantValue {
+    /// Returns this value as a boolean, if it's `true` or `false`. Otherwise this returns `None`.
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            Self::Identifier(identifier) if identifier.value == "true" => Some(true),
+            Self::Identifier(identifier) if identifier.value == "false" => Some(false),
+            _ => None,
+        }
+    }
+
+    /// Returns the enumerator of the provided enum that this value refers to, if it's the identifier of one of that
+    /// enum's enumerators. Otherwise this returns `None`.
+    ///
+    /// Enumerators can be referred to by their identifier alone (ex: `Red`), or qualified by the identifier of their
+    /// enum (ex: `Color::Red`, `MyModule::Color::Red`, or `::MyModule::Color::Red`).
+    pub fn as_enumerator<'a>(&self, enum_def: &'a Enum) -> Option<&'a Enumerator> {
+        let Self::Identifier(identifier) = self else { return None };
+
+        let (enum_identifier, enumerator_identifier) = match identifier.value.rsplit_once("::") {
+            Some((prefix, suffix)) => (Some(prefix.trim_start_matches("::")), suffix),
+            None => (None, identifier.value.as_str()),
+        };
+
+        // If the enumerator was qualified, make sure that it was qualified by the correct enum.
+        if let Some(enum_identifier) = enum_identifier {
+            let scoped_identifier = enum_def.module_scoped_identifier();
+            if scoped_identifier != enum_identifier && !scoped_identifier.ends_with(&format!("::{enum_identifier}")) {
+                return None;
+            }
+        }
+
+        enum_def
+            .enumerators()
+            .into_iter()
+            .find(|enumerator| enumerator.identifier() == enumerator_identifier)
+    }
+
+    pub fn span(&self) -> &Span {
+        match self {
+            Self::Integer(integer) => &integer.span,
+            Self::String(_, span) => span,
+            Self::Identifier(identifier) => &identifier.span,
+        }
+    }
+}
+
+impl std::fmt::Display for ConstantValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Integer(integer) => write!(f, "{}", integer.value),
+            Self::String(value, _) => write!(f, "{value:?}"),
+            Self::Identifier(identifier) => write!(f, "{}", identifier.value),
+        }
+    }
+}
+
+implement_Element_for!(Constant, "constant");
+implement_Attributable_for!(Constant);
+implement_Entity_for!(Constant);
+implement_Commentable_for!(Constant);
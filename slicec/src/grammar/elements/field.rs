@@ -9,6 +9,7 @@ pub struct Field {
     pub identifier: Identifier,
     pub data_type: TypeRef,
     pub tag: Option<Integer<u32>>,
+    pub default_value: Option<ConstantValue>,
     pub parent: WeakPtr<dyn Container<Field>>,
     pub scope: Scope,
     pub attributes: Vec<WeakPtr<Attribute>>,
@@ -1,6 +1,7 @@
 // Copyright (c) ZeroC, Inc.
 
 mod attribute;
+mod constant;
 mod custom_type;
 mod dictionary;
 mod r#enum;
@@ -21,6 +22,7 @@ mod type_ref;
 
 // Re-export the grammar elements directly into this module so consumers don't need to think about submodule structure).
 pub use self::attribute::*;
+pub use self::constant::*;
 pub use self::custom_type::*;
 pub use self::dictionary::*;
 pub use self::enumerator::*;
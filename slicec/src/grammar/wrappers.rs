@@ -23,7 +23,7 @@ macro_rules! generate_definition_wrapper {
     };
 }
 
-generate_definition_wrapper!(Struct, Interface, Enum, CustomType, TypeAlias);
+generate_definition_wrapper!(Struct, Interface, Enum, CustomType, TypeAlias, Constant);
 
 macro_rules! generate_entities_wrapper {
     ($($variant:ident),*) => {
@@ -46,7 +46,9 @@ pub trait AsEntities {
     fn concrete_entity(&self) -> Entities<'_>;
 }
 
-generate_entities_wrapper!(Struct, Field, Interface, Operation, Parameter, Enum, Enumerator, CustomType, TypeAlias);
+generate_entities_wrapper!(
+    Struct, Field, Interface, Operation, Parameter, Enum, Enumerator, CustomType, TypeAlias, Constant
+);
 
 macro_rules! generate_attributables_wrapper {
     ($($variant:ident),*) => {
@@ -70,7 +72,8 @@ pub trait AsAttributables {
 }
 
 generate_attributables_wrapper!(
-    Module, Struct, Field, Interface, Operation, Parameter, Enum, Enumerator, CustomType, TypeAlias, TypeRef, SliceFile
+    Module, Struct, Field, Interface, Operation, Parameter, Enum, Enumerator, CustomType, TypeAlias, Constant, TypeRef,
+    SliceFile
 );
 
 macro_rules! generate_types_wrapper {
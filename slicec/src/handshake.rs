@@ -0,0 +1,282 @@
+// Copyright (c) ZeroC, Inc.
+
+//! This module implements the handshake that slicec performs with each plugin before sending it anything else.
+//! slicec sends a 'describe' request to the plugin, which responds with its name, and the version of the
+//! code-generator protocol that it uses.
+//!
+//! Each request is handled by a separate run of the plugin, so the handshake costs an extra process startup per plugin.
+//! Since a plugin's response only depends on its executable, successful handshakes are remembered, and aren't performed
+//! again until the plugin's executable changes. They're stored in a [HandshakeCache], which lasts across the reruns of
+//! '--watch', and in the generation cache (see [crate::cache]), which lasts across separate runs of slicec.
+
+use crate::cache::{self, Cache};
+use crate::definition_types::GeneratorInfo;
+use crate::diagnostics::{Diagnostic, Diagnostics, Error};
+use crate::plugin_process;
+use crate::slice_options::Plugin;
+use slice_codec::decoder::Decoder;
+use slice_codec::encoder::Encoder;
+use std::collections::HashMap;
+use std::time::{Duration, SystemTime};
+
+/// The version of the code-generator protocol that this version of slicec uses.
+/// This must be incremented whenever a change is made to the definitions in 'slice/Compiler'.
+pub const PROTOCOL_VERSION: u32 = 1;
+
+/// Caches which plugins passed their handshake.
+/// Entries are keyed by the path of the plugin's executable, and are only valid while the executable's size and
+/// modification time are unchanged.
+#[derive(Debug, Default)]
+pub struct HandshakeCache {
+    entries: HashMap<String, ExecutableStamp>,
+}
+
+/// The size and modification time of an executable, which are used to detect when it's been changed.
+type ExecutableStamp = (u64, SystemTime);
+
+impl HandshakeCache {
+    /// Returns true if the plugin at the provided path passed its handshake, and its executable hasn't changed since.
+    pub fn contains(&self, plugin_path: &str) -> bool {
+        let stamp = self.entries.get(plugin_path);
+        stamp.is_some_and(|stamp| executable_stamp(plugin_path).as_ref() == Some(stamp))
+    }
+
+    /// Stores that the plugin at the provided path passed its handshake.
+    /// If the plugin's executable can't be inspected, nothing is stored, since changes to it can't be detected.
+    pub fn insert(&mut self, plugin_path: &str) {
+        if let Some(stamp) = executable_stamp(plugin_path) {
+            self.entries.insert(plugin_path.to_owned(), stamp);
+        }
+    }
+}
+
+/// Returns the size and modification time of the executable at the provided path, or `None` if it can't be inspected.
+fn executable_stamp(path: &str) -> Option<ExecutableStamp> {
+    let metadata = std::fs::metadata(path).ok()?;
+    Some((metadata.len(), metadata.modified().ok()?))
+}
+
+/// Performs a handshake with each of the provided plugins (unless it already passed one, according to either of the
+/// provided caches), to check that they use the same protocol version as us. This returns whether each of the plugins
+/// passed its handshake (in the same order as the plugins were provided), and reports an error for each that didn't.
+/// Plugins that pass their handshake are stored in both caches.
+pub fn describe_plugins(
+    plugins: &[&Plugin],
+    handshake_cache: &mut HandshakeCache,
+    mut cache: Option<&mut Cache>,
+    max_jobs: Option<usize>,
+    timeout: Option<Duration>,
+    diagnostics: &mut Diagnostics,
+) -> Vec<bool> {
+    // We only hash the executables of plugins which aren't in the in-memory cache, since hashing is much slower.
+    let executable_hashes = plugins
+        .iter()
+        .map(|plugin| match (&cache, handshake_cache.contains(&plugin.path)) {
+            (Some(_), false) => cache::hash_executable(&plugin.path),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    let is_described = plugins
+        .iter()
+        .zip(&executable_hashes)
+        .map(|(plugin, executable_hash)| {
+            handshake_cache.contains(&plugin.path)
+                || matches!((&cache, executable_hash), (Some(cache), Some(hash)) if cache.has_handshake(&plugin.path, hash))
+        })
+        .collect::<Vec<_>>();
+
+    let undescribed_plugins = plugins
+        .iter()
+        .zip(&is_described)
+        .filter(|(_, is_described)| !**is_described);
+    let describe_payloads = undescribed_plugins.map(|(plugin, _)| {
+        (
+            plugin.path.as_str(),
+            encode_describe_request().map_err(std::io::Error::from),
+        )
+    });
+    let mut describe_responses =
+        plugin_process::run_plugins(describe_payloads.collect(), max_jobs, timeout).into_iter();
+
+    let results = plugins.iter().zip(executable_hashes).zip(is_described);
+    let results = results.map(|((plugin, executable_hash), is_described)| {
+        if is_described {
+            handshake_cache.insert(&plugin.path);
+            return true;
+        }
+        let response = describe_responses.next().unwrap(); // There's a response for each undescribed plugin.
+        if check_describe_response(plugin, response, diagnostics).is_none() {
+            return false;
+        }
+        handshake_cache.insert(&plugin.path);
+        if let Some((cache, executable_hash)) = cache.as_deref_mut().zip(executable_hash) {
+            cache.insert_handshake(&plugin.path, executable_hash);
+        }
+        true
+    });
+    results.collect()
+}
+
+/// Encodes the 'describe' request, which is sent to each code-generator before any other requests.
+pub fn encode_describe_request() -> Result<Vec<u8>, slice_codec::Error> {
+    let mut encoding_buffer: Vec<u8> = Vec::new();
+    let mut slice_encoder = Encoder::from(&mut encoding_buffer);
+
+    slice_encoder.encode("describe")?;
+    slice_encoder.encode(PROTOCOL_VERSION)?;
+
+    Ok(encoding_buffer)
+}
+
+/// Checks a code-generator's response to the 'describe' request.
+/// If the code-generator uses the same protocol version as slicec, this returns the information it reported.
+/// Otherwise, or if the code-generator failed to respond, this reports an error and returns `None`.
+pub fn check_describe_response(
+    generator: &Plugin,
+    response: std::io::Result<Vec<u8>>,
+    diagnostics: &mut Diagnostics,
+) -> Option<GeneratorInfo> {
+    let generator_info = response.and_then(|payload| {
+        let mut slice_decoder = Decoder::from(payload.as_slice());
+        Ok(slice_decoder.decode::<GeneratorInfo>()?)
+    });
+
+    match generator_info {
+        Ok(info) if info.protocol_version == PROTOCOL_VERSION => Some(info),
+
+        Ok(info) => {
+            let error = Error::GeneratorProtocolMismatch {
+                generator: generator.name().to_owned(),
+                generator_version: info.protocol_version,
+                compiler_version: PROTOCOL_VERSION,
+            };
+            let note = match info.protocol_version < PROTOCOL_VERSION {
+                true => format!("'{}' must be updated to work with this version of slicec", info.name),
+                false => format!("'{}' requires a newer version of slicec", info.name),
+            };
+            Diagnostic::new(error).add_note(note, None).push_into(diagnostics);
+            None
+        }
+
+        Err(io_error) => {
+            let error = Error::GeneratorHandshakeFailed {
+                generator: generator.name().to_owned(),
+                reason: io_error.to_string(),
+            };
+            let note = "this code-generator may have been built for an older version of slicec, \
+                        which didn't send it a 'describe' request";
+            Diagnostic::new(error).add_note(note, None).push_into(diagnostics);
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_util::TempDir;
+
+    /// Returns a plugin whose executable is at the provided path.
+    fn plugin(path: &str) -> Plugin {
+        Plugin {
+            path: path.to_owned(),
+            args: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn cached_handshakes_are_invalidated_when_the_executable_changes() {
+        // Arrange
+        let dir = TempDir::new("handshake-invalidated");
+        let path = dir.write_file("plugin", "version 1").display().to_string();
+        let mut handshake_cache = HandshakeCache::default();
+        handshake_cache.insert(&path);
+        assert!(handshake_cache.contains(&path));
+
+        // Act
+        std::fs::write(&path, "version 2").unwrap();
+
+        // Assert
+        assert!(!handshake_cache.contains(&path));
+    }
+
+    #[test]
+    fn cached_plugins_are_not_described_again() {
+        // Arrange
+        // This file isn't executable, so running it for the handshake would fail with an error.
+        let dir = TempDir::new("handshake-not-described");
+        let path = dir.write_file("plugin", "").display().to_string();
+        let mut handshake_cache = HandshakeCache::default();
+        handshake_cache.insert(&path);
+        let mut diagnostics = Diagnostics::new();
+
+        // Act
+        let results = describe_plugins(
+            &[&plugin(&path)],
+            &mut handshake_cache,
+            None,
+            None,
+            None,
+            &mut diagnostics,
+        );
+
+        // Assert
+        assert_eq!(results, [true]);
+        assert!(diagnostics.is_empty());
+    }
+
+    #[test]
+    fn plugins_with_a_persisted_handshake_are_not_described_again() {
+        // Arrange
+        // This file isn't executable, so running it for the handshake would fail with an error.
+        let dir = TempDir::new("handshake-persisted");
+        let path = dir.write_file("plugin", "").display().to_string();
+        let mut cache = Cache::default();
+        cache.insert_handshake(&path, cache::hash_executable(&path).unwrap());
+        let mut handshake_cache = HandshakeCache::default();
+        let mut diagnostics = Diagnostics::new();
+
+        // Act
+        let plugins = [&plugin(&path)];
+        let results = describe_plugins(
+            &plugins,
+            &mut handshake_cache,
+            Some(&mut cache),
+            None,
+            None,
+            &mut diagnostics,
+        );
+
+        // Assert
+        assert_eq!(results, [true]);
+        assert!(diagnostics.is_empty());
+        assert!(handshake_cache.contains(&path));
+    }
+
+    #[test]
+    fn failed_handshakes_are_not_cached() {
+        // Arrange
+        let dir = TempDir::new("handshake-not-cached");
+        let path = dir.write_file("plugin", "").display().to_string();
+        let mut cache = Cache::default();
+        let mut handshake_cache = HandshakeCache::default();
+        let mut diagnostics = Diagnostics::new();
+
+        // Act
+        let plugins = [&plugin(&path)];
+        let results = describe_plugins(
+            &plugins,
+            &mut handshake_cache,
+            Some(&mut cache),
+            None,
+            None,
+            &mut diagnostics,
+        );
+
+        // Assert
+        assert_eq!(results, [false]);
+        assert!(diagnostics.has_errors());
+        assert!(!handshake_cache.contains(&path));
+        assert!(!cache.has_handshake(&path, &cache::hash_executable(&path).unwrap()));
+    }
+}
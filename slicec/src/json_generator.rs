@@ -0,0 +1,183 @@
+// Copyright (c) ZeroC, Inc.
+
+//! This module implements slicec's built-in JSON code-generator. It serializes the same model that is sent to
+//! code-generator plugins as JSON, for tools and scripts that would rather not decode the Slice encoding.
+//!
+//! It can be run like any other code-generator (with '-G json'), in which case it generates a file named 'ast.json' in
+//! the output directory (a different path can be specified with '-G json,path=<PATH>'). Or the JSON can be written to
+//! an arbitrary file (or to 'stdout' by passing '-') with '--emit-ast-json <FILE>'.
+//!
+//! The JSON document mirrors the `generateCode` request defined in 'slice/Compiler/CodeGenerator.slice'.
+//! Its schema is documented for users in 'slicec/docs/ast-json.md', which must be kept in sync with the definition
+//! types.
+
+use crate::code_generator::CodeGenerator;
+use crate::definition_types::{CompilationContext, Diagnostic, DiagnosticLevel, GeneratedFile, SliceFile};
+use crate::handshake::PROTOCOL_VERSION;
+use serde::Serialize;
+
+/// The path of the file that the built-in JSON code-generator generates, if no path is specified.
+const DEFAULT_PATH: &str = "ast.json";
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct AstDocument<'a> {
+    protocol_version: u32,
+    source_files: &'a [SliceFile],
+    reference_files: &'a [SliceFile],
+    context: &'a CompilationContext,
+}
+
+/// Serializes the provided Slice files (and the context they were compiled in) to a JSON document.
+pub fn generate_ast_json(
+    source_files: &[SliceFile],
+    reference_files: &[SliceFile],
+    context: &CompilationContext,
+) -> String {
+    let document = AstDocument {
+        protocol_version: PROTOCOL_VERSION,
+        source_files,
+        reference_files,
+        context,
+    };
+
+    // Serializing can only fail for maps with non-string keys, which the definition types don't use.
+    let mut json = serde_json::to_string_pretty(&document).expect("failed to serialize Slice files to JSON");
+    json.push('\n');
+    json
+}
+
+/// slicec's built-in JSON code-generator, which generates a single file holding the parsed Slice definitions as JSON.
+#[derive(Debug, Default)]
+pub struct JsonGenerator;
+
+impl CodeGenerator for JsonGenerator {
+    fn name(&self) -> &str {
+        "json"
+    }
+
+    fn generate_code(
+        &self,
+        source_files: &[SliceFile],
+        reference_files: &[SliceFile],
+        context: &CompilationContext,
+        args: &[(String, String)],
+    ) -> (Vec<GeneratedFile>, Vec<Diagnostic>) {
+        let mut path = DEFAULT_PATH.to_owned();
+        let mut diagnostics = Vec::new();
+        for (key, value) in args {
+            match key.as_str() {
+                "path" => path = value.clone(),
+                _ => diagnostics.push(Diagnostic {
+                    level: DiagnosticLevel::Warning,
+                    message: format!("unknown argument '{key}' was ignored"),
+                    source: None,
+                }),
+            }
+        }
+
+        let generated_file = GeneratedFile {
+            path,
+            contents: generate_ast_json(source_files, reference_files, context),
+        };
+        (vec![generated_file], diagnostics)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generation::{convert_slice_files, create_compilation_context};
+    use crate::slice_options::SliceOptions;
+
+    /// Compiles the provided Slice and runs the JSON generator on it, with the provided arguments.
+    fn generate(
+        slice: &str,
+        options: &SliceOptions,
+        args: &[(String, String)],
+    ) -> (Vec<GeneratedFile>, Vec<Diagnostic>) {
+        let compilation_state = crate::compile_from_strings(&[slice], Some(options));
+        let (source_files, reference_files) = convert_slice_files(&compilation_state.files);
+        let context = create_compilation_context(&compilation_state.files, None, options);
+        JsonGenerator.generate_code(&source_files, &reference_files, &context, args)
+    }
+
+    #[test]
+    fn generated_json_mirrors_the_generate_code_request() {
+        // Arrange
+        let options = SliceOptions {
+            defined_symbols: vec!["FOO".to_owned()],
+            ..SliceOptions::default()
+        };
+
+        // Act
+        let (generated_files, diagnostics) = generate("module Test\nstruct S { x: int32 }", &options, &[]);
+
+        // Assert
+        assert!(diagnostics.is_empty());
+        assert_eq!(generated_files.len(), 1);
+        assert_eq!(generated_files[0].path, "ast.json");
+
+        let json = serde_json::from_str::<serde_json::Value>(&generated_files[0].contents).unwrap();
+        assert_eq!(json["protocolVersion"], PROTOCOL_VERSION);
+        assert_eq!(json["sourceFiles"], serde_json::json!([])); // Slice files compiled from strings aren't sources.
+        assert_eq!(json["context"]["definedSymbols"], serde_json::json!(["FOO"]));
+
+        let reference_file = &json["referenceFiles"][0];
+        assert_eq!(reference_file["path"], "string-0");
+        assert_eq!(reference_file["moduleDeclaration"]["identifier"], "Test");
+        let struct_def = &reference_file["contents"][0];
+        assert_eq!(struct_def["kind"], "Struct");
+        assert_eq!(struct_def["value"]["entityInfo"]["identifier"], "S");
+        assert_eq!(struct_def["value"]["fields"][0]["entityInfo"]["identifier"], "x");
+    }
+
+    #[test]
+    fn constants_and_default_values_are_mapped_as_documented() {
+        // Arrange
+        let slice = "
+            module Test
+            enum Color : uint8 { Red }
+            const Max: int32 = -5
+            const Favorite: Color = Color::Red
+            struct S { timeout: uint32 = 30, name: string }
+        ";
+
+        // Act
+        let (generated_files, diagnostics) = generate(slice, &SliceOptions::default(), &[]);
+
+        // Assert
+        assert!(diagnostics.is_empty());
+        let json = serde_json::from_str::<serde_json::Value>(&generated_files[0].contents).unwrap();
+        let contents = &json["referenceFiles"][0]["contents"];
+
+        assert_eq!(contents[1]["kind"], "Constant");
+        assert_eq!(contents[1]["value"]["dataType"]["typeId"], "int32");
+        let expected =
+            serde_json::json!({ "kind": "Integer", "value": { "absoluteValue": 5, "hasNegativeValue": true } });
+        assert_eq!(contents[1]["value"]["value"], expected);
+
+        let expected = serde_json::json!({ "kind": "Enumerator", "value": "Test::Color::Red" });
+        assert_eq!(contents[2]["value"]["value"], expected);
+
+        let fields = &contents[3]["value"]["fields"];
+        let expected =
+            serde_json::json!({ "kind": "Integer", "value": { "absoluteValue": 30, "hasNegativeValue": false } });
+        assert_eq!(fields[0]["defaultValue"], expected);
+        assert_eq!(fields[1]["defaultValue"], serde_json::Value::Null);
+    }
+
+    #[test]
+    fn path_argument_sets_the_generated_file_path() {
+        // Arrange
+        let args = [("path".to_owned(), "nested/definitions.json".to_owned())];
+
+        // Act
+        let (generated_files, diagnostics) = generate("module Test", &SliceOptions::default(), &args);
+
+        // Assert
+        assert!(diagnostics.is_empty());
+        assert_eq!(generated_files[0].path, "nested/definitions.json");
+        assert!(serde_json::from_str::<serde_json::Value>(&generated_files[0].contents).is_ok());
+    }
+}
@@ -1,18 +1,34 @@
 // Copyright (c) ZeroC, Inc.
 
 pub mod ast;
+pub mod code_generator;
 pub mod compilation_state;
+pub mod config_file;
+pub mod definition_types;
 pub mod diagnostic_emitter;
 pub mod diagnostics;
+pub mod driver;
 pub mod grammar;
 pub mod slice_file;
 pub mod slice_options;
 pub mod utils;
 pub mod visitor;
 
+mod cache;
+mod generation;
+mod handshake;
+mod json_generator;
+mod manifest;
 mod parsers;
 mod patchers;
+mod plugin_process;
+mod replay;
+mod reporting;
+mod slice_file_converter;
+mod test_generator;
+mod transform;
 mod validators;
+mod watch;
 
 use compilation_state::CompilationState;
 use slice_file::SliceFile;
@@ -0,0 +1,385 @@
+// Copyright (c) ZeroC, Inc.
+
+use crate::cache;
+use crate::diagnostics::{Diagnostic, Diagnostics, Error};
+use crate::generation::{normalize_relative_path, GenerationState};
+use crate::reporting::{report_generated_file, GeneratedFileStatus};
+use crate::slice_options::SliceOptions;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::io::{ErrorKind, Result};
+use std::path::{Path, PathBuf};
+
+/// The name of the manifest file that slicec stores in the output directory.
+const MANIFEST_FILE_NAME: &str = ".slicec-manifest";
+
+/// A manifest records which files were generated by each code-generator the last time it ran successfully.
+/// It's stored in the output directory, and lets slicec clean up files that code-generators no longer generate.
+#[derive(Debug, Default, Deserialize, Serialize)]
+#[serde(transparent)]
+pub struct Manifest {
+    /// Map of code-generator names to the paths of the files they generated (relative to the output directory).
+    generated_files: BTreeMap<String, BTreeSet<PathBuf>>,
+
+    /// The output directory that this manifest is stored in, and which all the paths in it are relative to.
+    #[serde(skip)]
+    output_dir: PathBuf,
+}
+
+impl Manifest {
+    /// Loads the manifest that's stored in the provided output directory.
+    /// If no manifest exists, this returns an empty manifest instead.
+    ///
+    /// Since the files in the manifest can be deleted, each of their paths is checked the same way that generated file
+    /// paths are, to make sure it's inside the output directory. An error is reported for each path that isn't, and
+    /// it's dropped from the manifest.
+    pub fn load(output_dir: &Option<String>, diagnostics: &mut Diagnostics) -> Result<Self> {
+        let output_dir = PathBuf::from(output_dir.as_deref().unwrap_or_default());
+
+        let mut manifest = match std::fs::read(manifest_path(&output_dir)) {
+            Ok(contents) => serde_json::from_slice::<Manifest>(&contents)?,
+            Err(error) if error.kind() == ErrorKind::NotFound => Manifest::default(),
+            Err(error) => return Err(error),
+        };
+        for paths in manifest.generated_files.values_mut() {
+            *paths = std::mem::take(paths)
+                .into_iter()
+                .filter_map(|path| {
+                    let path_string = path.display().to_string();
+                    match normalize_relative_path(&path_string) {
+                        Ok(normalized_path) => Some(normalized_path),
+                        Err(error) => {
+                            let action = "load manifest entry";
+                            let path = path_string;
+                            Diagnostic::new(Error::IO { action, path, error }).push_into(diagnostics);
+                            None
+                        }
+                    }
+                })
+                .collect();
+        }
+        manifest.generated_files.retain(|_, paths| !paths.is_empty());
+        manifest.output_dir = output_dir;
+        Ok(manifest)
+    }
+
+    /// Returns the path of this manifest's file.
+    pub fn path(&self) -> PathBuf {
+        manifest_path(&self.output_dir)
+    }
+
+    /// Writes this manifest into its output directory. If the manifest is empty, any existing manifest is deleted.
+    pub fn save(&self) -> Result<()> {
+        let path = self.path();
+
+        if self.generated_files.is_empty() {
+            return delete_file(&path);
+        }
+
+        // The output directory won't exist yet if none of the code-generators have generated any files into it.
+        std::fs::create_dir_all(&self.output_dir)?;
+        let mut contents = serde_json::to_string_pretty(self)?;
+        contents.push('\n');
+        std::fs::write(path, contents)
+    }
+
+    /// Returns the names of all the code-generators that have an entry in this manifest.
+    pub fn generator_names(&self) -> Vec<String> {
+        self.generated_files.keys().cloned().collect()
+    }
+
+    /// Replaces the files recorded for the specified code-generator with the provided files.
+    /// This returns the paths of any files which were previously recorded for it, but that aren't anymore.
+    /// Code-generators that didn't generate any files don't have an entry in the manifest.
+    pub fn replace(&mut self, generator_name: &str, generated_paths: Vec<&Path>) -> Vec<PathBuf> {
+        let new_paths = generated_paths
+            .into_iter()
+            .map(|path| self.relativize(path))
+            .collect::<BTreeSet<_>>();
+
+        let old_paths = match new_paths.is_empty() {
+            true => self.generated_files.remove(generator_name),
+            false => self
+                .generated_files
+                .insert(generator_name.to_owned(), new_paths.clone()),
+        };
+        let stale_paths = old_paths
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|path| !new_paths.contains(path));
+        stale_paths.map(|path| self.output_dir.join(path)).collect()
+    }
+
+    /// Removes the entry for the specified code-generator from this manifest.
+    /// This returns the paths of all the files which were recorded for it.
+    pub fn remove(&mut self, generator_name: &str) -> Vec<PathBuf> {
+        let paths = self.generated_files.remove(generator_name).unwrap_or_default();
+        paths.into_iter().map(|path| self.output_dir.join(path)).collect()
+    }
+
+    /// Returns the provided path, relative to the output directory that this manifest is stored in.
+    fn relativize(&self, path: &Path) -> PathBuf {
+        path.strip_prefix(&self.output_dir).unwrap_or(path).to_owned()
+    }
+}
+
+/// Returns the path of the manifest file that's stored in the provided output directory.
+pub fn manifest_path(output_dir: &Path) -> PathBuf {
+    output_dir.join(MANIFEST_FILE_NAME)
+}
+
+/// Deletes the file at the provided path. It isn't an error if the file has already been deleted.
+pub fn delete_file(path: &Path) -> Result<()> {
+    match std::fs::remove_file(path) {
+        Err(error) if error.kind() != ErrorKind::NotFound => Err(error),
+        _ => Ok(()),
+    }
+}
+
+/// Loads the manifest of previously generated files from the output directory.
+/// If the manifest couldn't be loaded, this reports an error and returns `None`.
+pub(crate) fn load_manifest(options: &SliceOptions, diagnostics: &mut Diagnostics) -> Option<Manifest> {
+    match Manifest::load(&options.output_dir, diagnostics) {
+        Ok(manifest) => Some(manifest),
+        Err(error) => {
+            let action = "read manifest";
+            let output_dir = Path::new(options.output_dir.as_deref().unwrap_or_default());
+            let path = manifest_path(output_dir).display().to_string();
+            Diagnostic::new(Error::IO { action, path, error }).push_into(diagnostics);
+            None
+        }
+    }
+}
+
+/// Handles the files which were previously generated, but no longer are, by deleting them from disk.
+/// If this is a dry-run, we instead report that each file is stale, and if we're checking the generated files,
+/// we instead report an error for each stale file that still exists on disk.
+pub(crate) fn handle_stale_files(state: &GenerationState, options: &SliceOptions, diagnostics: &mut Diagnostics) {
+    for (path, generator_name) in &state.stale_paths {
+        // If another generator now generates a file at this path, then it isn't actually stale.
+        if state
+            .generated_paths
+            .iter()
+            .any(|(generated_path, _)| generated_path == path)
+        {
+            continue;
+        }
+
+        if options.dry_run {
+            if path.exists() {
+                let format = options.diagnostic_format;
+                report_generated_file(generator_name, path, GeneratedFileStatus::Stale, format, diagnostics);
+            }
+        } else if options.check {
+            if path.exists() {
+                let error = Error::StaleGeneratedFile {
+                    path: path.display().to_string(),
+                };
+                Diagnostic::new(error).push_into(diagnostics);
+            }
+        } else if let Err(error) = delete_file(path) {
+            let action = "delete stale generated file";
+            let path = path.display().to_string();
+            Diagnostic::new(Error::IO { action, path, error }).push_into(diagnostics);
+        }
+    }
+}
+
+/// Deletes all the files which the manifest records as having been generated by the specified code-generators,
+/// and removes their entries from the manifest and the generation cache. If no generators were specified, all the
+/// recorded files are deleted, and the generation cache is cleared.
+pub(crate) fn clean_generated_files(options: &SliceOptions, diagnostics: &mut Diagnostics) {
+    let Some(mut manifest) = load_manifest(options, diagnostics) else {
+        return;
+    };
+
+    let generator_names = match options.generators.is_empty() {
+        true => manifest.generator_names(),
+        false => options
+            .generators
+            .iter()
+            .map(|generator| generator.name().to_owned())
+            .collect(),
+    };
+    let mut cache = cache::load_cache(options);
+    if options.generators.is_empty() {
+        cache.clear();
+    }
+    for generator_name in generator_names {
+        cache.remove(&generator_name);
+        for path in manifest.remove(&generator_name) {
+            if let Err(error) = delete_file(&path) {
+                let action = "delete generated file";
+                let path = path.display().to_string();
+                Diagnostic::new(Error::IO { action, path, error }).push_into(diagnostics);
+            }
+        }
+    }
+
+    if let Err(error) = manifest.save() {
+        let action = "write manifest";
+        let path = manifest.path().display().to_string();
+        Diagnostic::new(Error::IO { action, path, error }).push_into(diagnostics);
+    }
+    if let Err(error) = cache.save() {
+        let action = "write generation cache";
+        let path = cache.path().display().to_string();
+        Diagnostic::new(Error::IO { action, path, error }).push_into(diagnostics);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generation::handle_generator_response;
+    use crate::utils::test_util::TempDir;
+    use clap::Parser;
+    use slice_codec::encoder::Encoder;
+
+    /// Returns options which run the provided code-generators, and write their files to an 'output' directory within
+    /// the provided directory.
+    fn create_options(dir: &Path, generators: &[&str]) -> SliceOptions {
+        let output_dir = dir.join("output").display().to_string();
+        let generators = generators.iter().map(|generator| ["--generator", generator]);
+        SliceOptions::parse_from(["", "-O", &output_dir].into_iter().chain(generators.flatten()))
+    }
+
+    /// Runs the code-generators in the provided options as if each had responded with the corresponding (empty) files,
+    /// then cleans up any stale files and saves the manifest, the same way that [Driver::run] does.
+    fn generate(options: &SliceOptions, responses: &[&[&str]]) -> Diagnostics {
+        let mut diagnostics = Diagnostics::new();
+        let mut state = GenerationState {
+            manifest: load_manifest(options, &mut diagnostics),
+            ..GenerationState::default()
+        };
+
+        for (generator, file_paths) in options.generators.iter().zip(responses) {
+            let mut response_payload = Vec::new();
+            let mut slice_encoder = Encoder::from(&mut response_payload);
+            slice_encoder.encode_size(file_paths.len()).unwrap();
+            for file_path in *file_paths {
+                slice_encoder.encode(*file_path).unwrap();
+                slice_encoder.encode("").unwrap();
+                slice_encoder.encode_varint(-1).unwrap(); // The tag end marker.
+            }
+            slice_encoder.encode_size(0).unwrap(); // The generator didn't report any diagnostics.
+
+            let generator_diagnostics =
+                handle_generator_response(generator, response_payload, None, &mut None, options, &mut state);
+            diagnostics.extend(generator_diagnostics.unwrap());
+        }
+
+        handle_stale_files(&state, options, &mut diagnostics);
+        state.manifest.unwrap().save().unwrap();
+        diagnostics
+    }
+
+    #[test]
+    fn stale_generated_files_are_deleted() {
+        // Arrange
+        let dir = TempDir::new("driver-stale-files-deleted");
+        let options = create_options(&dir, &["gen"]);
+        generate(&options, &[&["a.rs", "b.rs"]]);
+
+        // Act
+        let diagnostics = generate(&options, &[&["a.rs"]]);
+
+        // Assert
+        assert!(diagnostics.is_empty());
+        assert!(dir.join("output/a.rs").exists());
+        assert!(!dir.join("output/b.rs").exists());
+    }
+
+    #[test]
+    fn stale_files_are_kept_if_another_generator_now_generates_them() {
+        // Arrange
+        let dir = TempDir::new("driver-stale-files-moved");
+        let options = create_options(&dir, &["gen-a", "gen-b"]);
+        generate(&options, &[&["a.rs"], &[]]);
+
+        // Act
+        let diagnostics = generate(&options, &[&[], &["a.rs"]]);
+
+        // Assert
+        assert!(diagnostics.is_empty());
+        assert!(dir.join("output/a.rs").exists());
+    }
+
+    #[test]
+    fn generators_without_files_do_not_need_an_output_dir() {
+        // Arrange
+        let dir = TempDir::new("driver-no-generated-files");
+        let options = create_options(&dir, &["gen"]);
+
+        // Act
+        let diagnostics = generate(&options, &[&[]]);
+
+        // Assert
+        assert!(diagnostics.is_empty());
+        assert!(!manifest_path(&dir.join("output")).exists());
+    }
+
+    #[test]
+    fn clean_deletes_files_from_the_specified_generators() {
+        // Arrange
+        let dir = TempDir::new("driver-clean-specified");
+        generate(&create_options(&dir, &["gen-a", "gen-b"]), &[&["a.rs"], &["b.rs"]]);
+        let options = SliceOptions {
+            clean: true,
+            ..create_options(&dir, &["gen-a"])
+        };
+
+        // Act
+        let mut diagnostics = Diagnostics::new();
+        clean_generated_files(&options, &mut diagnostics);
+
+        // Assert
+        assert!(diagnostics.is_empty());
+        assert!(!dir.join("output/a.rs").exists());
+        assert!(dir.join("output/b.rs").exists());
+    }
+
+    #[test]
+    fn clean_deletes_all_files_and_the_manifest_if_no_generators_are_specified() {
+        // Arrange
+        let dir = TempDir::new("driver-clean-all");
+        generate(&create_options(&dir, &["gen-a", "gen-b"]), &[&["a.rs"], &[
+            "nested/b.rs",
+        ]]);
+        let options = SliceOptions {
+            clean: true,
+            ..create_options(&dir, &[])
+        };
+
+        // Act
+        let mut diagnostics = Diagnostics::new();
+        clean_generated_files(&options, &mut diagnostics);
+
+        // Assert
+        assert!(diagnostics.is_empty());
+        assert!(!dir.join("output/a.rs").exists());
+        assert!(!dir.join("output/nested/b.rs").exists());
+        assert!(!manifest_path(&dir.join("output")).exists());
+    }
+
+    #[test]
+    fn manifest_entries_outside_of_the_output_dir_are_not_deleted() {
+        // Arrange
+        let dir = TempDir::new("manifest-outside-entries");
+        let outside_file = dir.write_file("outside.rs", "");
+        let absolute_path = outside_file.display().to_string();
+        let manifest = serde_json::json!({ "gen": ["../outside.rs", absolute_path, "inside.rs"] });
+        dir.write_file("output/.slicec-manifest", manifest.to_string());
+        let options = create_options(&dir, &["gen"]);
+
+        // Act
+        let diagnostics = generate(&options, &[&[]]);
+
+        // Assert
+        let diagnostics = diagnostics.into_inner();
+        assert_eq!(diagnostics.len(), 2);
+        assert!(diagnostics.iter().all(|diagnostic| diagnostic.code() == "E001"));
+        assert!(outside_file.exists());
+        assert!(!manifest_path(&dir.join("output")).exists());
+    }
+}
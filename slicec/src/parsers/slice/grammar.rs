@@ -112,6 +112,7 @@ pub fn construct_field(
     identifier: Identifier,
     tag: Option<Integer<u32>>,
     data_type: TypeRef,
+    default_value: Option<ConstantValue>,
     span: Span,
 ) -> OwnedPtr<Field> {
     let comment = parse_doc_comment(parser, &identifier.value, raw_comment);
@@ -119,6 +120,7 @@ pub fn construct_field(
         identifier,
         data_type,
         tag,
+        default_value,
         parent: WeakPtr::create_uninitialized(), // Patched by its container.
         scope: parser.current_scope.clone(),
         attributes,
@@ -371,6 +373,26 @@ fn construct_type_alias(
     })
 }
 
+fn construct_constant(
+    parser: &mut Parser,
+    (raw_comment, attributes): (RawDocComment, Vec<WeakPtr<Attribute>>),
+    identifier: Identifier,
+    data_type: TypeRef,
+    value: ConstantValue,
+    span: Span,
+) -> OwnedPtr<Constant> {
+    let comment = parse_doc_comment(parser, &identifier.value, raw_comment);
+    OwnedPtr::new(Constant {
+        identifier,
+        data_type,
+        value,
+        scope: parser.current_scope.clone(),
+        attributes,
+        comment,
+        span,
+    })
+}
+
 fn construct_type_ref(
     parser: &Parser,
     attributes: Vec<WeakPtr<Attribute>>,
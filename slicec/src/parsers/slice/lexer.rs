@@ -195,6 +195,7 @@ where
             "enum" => TokenKind::EnumKeyword,
             "custom" => TokenKind::CustomKeyword,
             "typealias" => TokenKind::TypeAliasKeyword,
+            "const" => TokenKind::ConstKeyword,
             "Result" => TokenKind::ResultKeyword,
             "Sequence" => TokenKind::SequenceKeyword,
             "Dictionary" => TokenKind::DictionaryKeyword,
@@ -36,6 +36,7 @@ pub enum TokenKind<'input> {
     EnumKeyword,      // "enum"
     CustomKeyword,    // "custom"
     TypeAliasKeyword, // "typealias"
+    ConstKeyword,     // "const"
     ResultKeyword,    // "Result"
 
     // Collection keywords
@@ -104,6 +105,7 @@ impl fmt::Display for TokenKind<'_> {
             Self::EnumKeyword => "enum",
             Self::CustomKeyword => "custom",
             Self::TypeAliasKeyword => "typealias",
+            Self::ConstKeyword => "const",
             Self::ResultKeyword => "Result",
             Self::SequenceKeyword => "Sequence",
             Self::DictionaryKeyword => "Dictionary",
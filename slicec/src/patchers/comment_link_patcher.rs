@@ -41,6 +41,7 @@ pub unsafe fn patch_ast(compilation_state: &mut CompilationState) {
             Node::Enumerator(ptr) => patcher.compute_patches_for(ptr.borrow(), &compilation_state.ast),
             Node::CustomType(ptr) => patcher.compute_patches_for(ptr.borrow(), &compilation_state.ast),
             Node::TypeAlias(ptr) => patcher.compute_patches_for(ptr.borrow(), &compilation_state.ast),
+            Node::Constant(ptr) => patcher.compute_patches_for(ptr.borrow(), &compilation_state.ast),
             _ => {} // Skip any elements that don't implement `Commentable`.
         }
     }
@@ -56,6 +57,7 @@ pub unsafe fn patch_ast(compilation_state: &mut CompilationState) {
             Node::Enumerator(ptr) => patch_element!(ptr, patcher),
             Node::CustomType(ptr) => patch_element!(ptr, patcher),
             Node::TypeAlias(ptr) => patch_element!(ptr, patcher),
+            Node::Constant(ptr) => patch_element!(ptr, patcher),
             _ => {} // Skip any elements that don't implement `Commentable`.
         }
     }
@@ -160,6 +162,7 @@ fn convert_node_to_entity_ptr(node: &Node) -> Result<WeakPtr<dyn Entity>, String
         Node::Enumerator(enumerator_ptr) => Ok(downgrade_as!(enumerator_ptr, dyn Entity)),
         Node::CustomType(custom_type_ptr) => Ok(downgrade_as!(custom_type_ptr, dyn Entity)),
         Node::TypeAlias(type_alias_ptr) => Ok(downgrade_as!(type_alias_ptr, dyn Entity)),
+        Node::Constant(constant_ptr) => Ok(downgrade_as!(constant_ptr, dyn Entity)),
 
         Node::Module(_) => Err("modules cannot be linked to".to_owned()),
         Node::Parameter(_) => Err("parameters cannot be linked to".to_owned()), // TODO improve for return members.
@@ -53,6 +53,10 @@ impl TypeRefPatcher<'_> {
                     self.resolve_definition(type_ref, ast)
                         .map(PatchKind::TypeAliasUnderlyingType)
                 }
+                Node::Constant(constant_ptr) => {
+                    let type_ref = &constant_ptr.borrow().data_type;
+                    self.resolve_definition(type_ref, ast).map(PatchKind::ConstantType)
+                }
                 Node::ResultType(result_ptr) => {
                     let result_type = result_ptr.borrow();
                     let success_patch = self.resolve_definition(&result_type.success_type, ast);
@@ -119,6 +123,11 @@ impl TypeRefPatcher<'_> {
                     let type_alias_underlying_type_ref = &mut type_alias_ptr.borrow_mut().underlying;
                     type_alias_underlying_type_ref.patch(type_alias_underlying_type_ptr, attributes);
                 }
+                PatchKind::ConstantType((constant_type_ptr, attributes)) => {
+                    let constant_ptr: &mut OwnedPtr<Constant> = element.try_into().unwrap();
+                    let constant_type_ref = &mut constant_ptr.borrow_mut().data_type;
+                    constant_type_ref.patch(constant_type_ptr, attributes);
+                }
                 PatchKind::ResultTypes(success_patch, failure_patch) => {
                     let result_ptr: &mut OwnedPtr<ResultType> = element.try_into().unwrap();
                     if let Some((success_type_ptr, attributes)) = success_patch {
@@ -300,6 +309,7 @@ enum PatchKind {
     ParameterType(Patch<dyn Type>),
     EnumUnderlyingType(Patch<Primitive>),
     TypeAliasUnderlyingType(Patch<dyn Type>),
+    ConstantType(Patch<dyn Type>),
     ResultTypes(Option<Patch<dyn Type>>, Option<Patch<dyn Type>>),
     SequenceType(Patch<dyn Type>),
     DictionaryTypes(Option<Patch<dyn Type>>, Option<Patch<dyn Type>>),
@@ -0,0 +1,263 @@
+// Copyright (c) ZeroC, Inc.
+
+//! This module is responsible for running code-generator plugins as subprocesses.
+//!
+//! Each plugin's payload is written to its 'stdin' while its 'stdout' and 'stderr' are drained, all concurrently.
+//! So a plugin can start writing its response before it's finished reading its payload without deadlocking slicec.
+
+use std::io::{Error, ErrorKind, Read, Write};
+use std::process::{Child, Command, ExitStatus, Stdio};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// How long to sleep between checks of whether a plugin has exited, when a timeout is being enforced.
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Runs each of the provided plugins with its corresponding payload, and returns their responses (in the same order).
+/// At most `max_jobs` plugins are run at once. If `max_jobs` is `None`, all the plugins are run at once.
+///
+/// Any plugin whose payload is an `Err` isn't run; the error is returned as its response instead.
+pub fn run_plugins(
+    plugins: Vec<(&str, std::io::Result<Vec<u8>>)>,
+    max_jobs: Option<usize>,
+    timeout: Option<Duration>,
+) -> Vec<std::io::Result<Vec<u8>>> {
+    let job_count = max_jobs.unwrap_or(plugins.len()).min(plugins.len());
+
+    // Each slot initially holds a plugin's payload, which is replaced by the plugin's response after it's run.
+    let (plugin_paths, payloads): (Vec<_>, Vec<_>) = plugins.into_iter().unzip();
+    let slots = payloads.into_iter().map(Mutex::new).collect::<Vec<_>>();
+
+    // Spawn a worker thread for each job. Each worker repeatedly takes the next plugin that hasn't been run yet,
+    // and runs it to completion, until there are no plugins left.
+    let next_index = AtomicUsize::new(0);
+    thread::scope(|scope| {
+        for _ in 0..job_count {
+            scope.spawn(|| loop {
+                let index = next_index.fetch_add(1, Ordering::Relaxed);
+                let Some(slot) = slots.get(index) else { break };
+
+                let mut slot = slot.lock().unwrap();
+                if let Ok(payload) = &mut *slot {
+                    let payload = std::mem::take(payload);
+                    *slot = run_plugin(plugin_paths[index], payload, timeout);
+                }
+            });
+        }
+    });
+
+    slots.into_iter().map(|slot| slot.into_inner().unwrap()).collect()
+}
+
+/// Runs the plugin at the provided path, writing the provided payload to its 'stdin', and waits for it to exit.
+/// If the plugin runs successfully, this returns the response that it wrote to 'stdout'.
+///
+/// If a timeout is provided, and the plugin hasn't exited before the timeout elapses, it's killed and an error
+/// returned.
+pub fn run_plugin(plugin_path: &str, plugin_payload: Vec<u8>, timeout: Option<Duration>) -> std::io::Result<Vec<u8>> {
+    // Spawn a new subprocess and set up pipes for all of its streams.
+    let mut subprocess = Command::new(plugin_path)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+
+    let mut stdin = subprocess.stdin.take().ok_or(ErrorKind::BrokenPipe)?;
+    let mut stdout = subprocess.stdout.take().ok_or(ErrorKind::BrokenPipe)?;
+    let mut stderr = subprocess.stderr.take().ok_or(ErrorKind::BrokenPipe)?;
+
+    // Write the payload to the subprocess's 'stdin', while concurrently draining its 'stdout' and 'stderr'.
+    // 'stdin' is closed once the payload has been written, so the subprocess knows there's nothing more to read.
+    let deadline = timeout.map(|timeout| Instant::now() + timeout);
+    let stdin_writer = spawn_with_result(move || stdin.write_all(&plugin_payload));
+    let stdout_reader = spawn_with_result(move || read_to_end(&mut stdout));
+    let stderr_reader = spawn_with_result(move || read_to_end(&mut stderr));
+
+    // Wait until the subprocess finishes, or until it's been killed for taking too long.
+    // If it was killed, we don't wait for the other threads to finish.
+    let status = wait_for_exit(&mut subprocess, timeout)?;
+
+    // Even after the subprocess has exited, any processes that it started may still be holding its pipes open, which
+    // blocks these threads until they exit too. So we only wait for them until the rest of the timeout has elapsed.
+    // Without a timeout, we wait for as long as it takes, the same as we do for the subprocess itself.
+    let write_result = wait_for_result(stdin_writer, deadline)?;
+    let stdout = wait_for_result(stdout_reader, deadline)??;
+    let stderr = wait_for_result(stderr_reader, deadline)??;
+
+    check_plugin_output(status, stdout, stderr, write_result)
+}
+
+/// Runs the provided function on a new thread, and returns a receiver that its result is sent to once it's finished.
+fn spawn_with_result<T: Send + 'static>(function: impl FnOnce() -> T + Send + 'static) -> Receiver<T> {
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || sender.send(function()));
+    receiver
+}
+
+/// Waits for the result of a thread that was started with [spawn_with_result]. If a deadline is provided, and the
+/// thread hasn't finished before it passes, an error is returned instead. The thread is left running in this case.
+fn wait_for_result<T>(receiver: Receiver<T>, deadline: Option<Instant>) -> std::io::Result<T> {
+    let result = match deadline {
+        Some(deadline) => receiver.recv_timeout(deadline.saturating_duration_since(Instant::now())),
+        None => receiver.recv().map_err(RecvTimeoutError::from),
+    };
+    result.map_err(|error| match error {
+        RecvTimeoutError::Timeout => {
+            let message = "timed out waiting for its output to be closed, after it exited";
+            Error::new(ErrorKind::TimedOut, message)
+        }
+        RecvTimeoutError::Disconnected => Error::other("failed to communicate with the code-generator"),
+    })
+}
+
+/// Waits for the provided subprocess to exit. If a timeout is provided, and the subprocess is still running after
+/// the timeout elapses, it's killed and an error is returned.
+fn wait_for_exit(subprocess: &mut Child, timeout: Option<Duration>) -> std::io::Result<ExitStatus> {
+    let Some(timeout) = timeout else {
+        return subprocess.wait();
+    };
+
+    let start_time = Instant::now();
+    loop {
+        if let Some(status) = subprocess.try_wait()? {
+            return Ok(status);
+        }
+
+        if start_time.elapsed() >= timeout {
+            subprocess.kill()?;
+            subprocess.wait()?;
+            let message = format!("timed out after {} second(s)", timeout.as_secs());
+            return Err(Error::new(ErrorKind::TimedOut, message));
+        }
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+/// Checks the output of a plugin that has exited, to determine whether it ran successfully.
+/// If it did, this returns the response that it wrote to 'stdout', otherwise this returns an error.
+fn check_plugin_output(
+    status: ExitStatus,
+    stdout: Vec<u8>,
+    stderr: Vec<u8>,
+    write_result: std::io::Result<()>,
+) -> std::io::Result<Vec<u8>> {
+    // If the subprocess wrote anything to its 'stderr', we consider this a failure and don't generate any code.
+    if !stderr.is_empty() {
+        // Obtain an exclusive handle to this process's 'stderr'.
+        let mut process_stderr = std::io::stderr().lock();
+
+        // Pipe the output from the subprocess's 'stderr' to this process's 'stderr', and then return.
+        let error = match process_stderr.write_all(&stderr) {
+            Ok(_) => Error::other("errors reported on 'stderr'"),
+            Err(err) => Error::new(ErrorKind::BrokenPipe, err),
+        };
+        return Err(error);
+    }
+
+    // Otherwise, check the subprocess's status code to determine success.
+    match status.code() {
+        // If the subprocess exited with a status code of 0, it still must have read its entire payload.
+        // If it did, all is good. We return the encoded response from 'stdout'.
+        Some(0) => write_result.map(|_| stdout),
+
+        // If the subprocess exited with a non-zero status code, we consider this a failure and don't generate any code.
+        Some(code) => Err(Error::other(format!("failed with status code '{code}'"))),
+
+        // If the subprocess did not exit with a status code, it was interrupted; this is also treated as a failure.
+        None => Err(Error::from(ErrorKind::Interrupted)),
+    }
+}
+
+/// Reads all the bytes from the provided pipe, until it's closed.
+fn read_to_end(pipe: &mut impl Read) -> std::io::Result<Vec<u8>> {
+    let mut buffer = Vec::new();
+    pipe.read_to_end(&mut buffer)?;
+    Ok(buffer)
+}
+
+// These tests use standard Unix utilities as plugins: `cat` echoes its payload back, and `sh` runs its payload.
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    /// More than fits in a pipe's buffer on any platform we support, so writing this much blocks until it's read.
+    const LARGE_OUTPUT_SIZE: usize = 256 * 1024;
+
+    #[test]
+    fn plugins_are_killed_after_the_timeout() {
+        // Arrange
+        let payload = b"exec sleep 30".to_vec();
+        let start_time = Instant::now();
+
+        // Act
+        let result = run_plugin("sh", payload, Some(Duration::from_millis(100)));
+
+        // Assert
+        assert_eq!(result.unwrap_err().kind(), ErrorKind::TimedOut);
+        assert!(start_time.elapsed() < Duration::from_secs(10));
+    }
+
+    #[test]
+    fn plugins_whose_pipes_are_held_open_after_exiting_time_out() {
+        // Arrange
+        let payload = b"sleep 30 &".to_vec(); // The background process inherits the plugin's pipes.
+        let start_time = Instant::now();
+
+        // Act
+        let result = run_plugin("sh", payload, Some(Duration::from_millis(500)));
+
+        // Assert
+        assert_eq!(result.unwrap_err().kind(), ErrorKind::TimedOut);
+        assert!(start_time.elapsed() < Duration::from_secs(10));
+    }
+
+    #[test]
+    fn large_payloads_and_responses_do_not_deadlock() {
+        // Arrange
+        let payload = vec![b'x'; LARGE_OUTPUT_SIZE];
+
+        // Act
+        let result = run_plugin("cat", payload.clone(), None);
+
+        // Assert
+        assert_eq!(result.unwrap(), payload);
+    }
+
+    #[test]
+    fn large_outputs_on_stdout_and_stderr_do_not_deadlock() {
+        // Arrange
+        let script = format!("head -c {LARGE_OUTPUT_SIZE} /dev/zero >&2; head -c {LARGE_OUTPUT_SIZE} /dev/zero");
+
+        // Act
+        let result = run_plugin("sh", script.into_bytes(), Some(Duration::from_secs(30)));
+
+        // Assert
+        let error = result.unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::Other);
+        assert_eq!(error.to_string(), "errors reported on 'stderr'");
+    }
+
+    #[test]
+    fn responses_are_returned_in_order() {
+        // Arrange
+        let plugins = vec![
+            ("cat", Ok(b"first".to_vec())),
+            ("cat", Err(Error::other("failed to encode payload"))),
+            ("cat", Ok(b"third".to_vec())),
+        ];
+
+        // Act
+        let responses = run_plugins(plugins, Some(1), None);
+
+        // Assert
+        assert_eq!(responses[0].as_ref().unwrap(), b"first");
+        assert_eq!(
+            responses[1].as_ref().unwrap_err().to_string(),
+            "failed to encode payload"
+        );
+        assert_eq!(responses[2].as_ref().unwrap(), b"third");
+    }
+}
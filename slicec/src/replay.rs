@@ -0,0 +1,128 @@
+// Copyright (c) ZeroC, Inc.
+
+//! This module implements the 'replay-request' command, which sends a request payload that was previously saved with
+//! '--emit-request' to a code-generator, and then prints the generator's decoded response.
+//! This lets plugin authors debug and test their code-generators without needing to run the full compiler.
+
+use crate::code_generator::CodeGenerator;
+use crate::compilation_state::CompilationState;
+use crate::definition_types::{Diagnostic, DiagnosticLevel, GeneratedFile};
+use crate::generation::{decode_generator_response, decode_plugin_payload};
+use crate::plugin_process::run_plugin;
+use crate::slice_options::{DiagnosticFormat, SliceOptions};
+use crate::utils::plugin_util::resolve_plugin_path;
+use serde::Serialize;
+use std::io::Write;
+use std::process::ExitCode;
+use std::time::Duration;
+
+/// Sends the request payload stored in the provided file to a code-generator, and prints its response.
+/// If a registered code-generator is provided, the payload is decoded and passed to it directly. Otherwise, the
+/// code-generator is run as a plugin, and the payload is written to its 'stdin'.
+pub fn replay_request(
+    request_file: &str,
+    generator_path: &str,
+    code_generator: Option<&dyn CodeGenerator>,
+    options: &SliceOptions,
+) -> ExitCode {
+    let mut compilation_state = CompilationState::create();
+    let mut generator_reported_errors = false;
+
+    // Resolve the path of the generator, in case it was specified by name (instead of by path).
+    // Registered code-generators are run in-process, so there's nothing to resolve.
+    let plugin_dirs = &options.plugin_dirs;
+    let generator_path = match code_generator {
+        Some(_) => Some(generator_path.to_owned()),
+        None => resolve_plugin_path(generator_path, plugin_dirs, &mut compilation_state.diagnostics),
+    };
+    let Some(generator_path) = generator_path else {
+        compilation_state.emit_diagnostics(options);
+        return ExitCode::FAILURE;
+    };
+
+    // Read the saved payload, send it to the generator, and then decode the generator's response.
+    let response = std::fs::read(request_file)
+        .map_err(|error| ("read request file", request_file, error))
+        .and_then(|payload| {
+            let result = match code_generator {
+                Some(code_generator) => decode_plugin_payload(&payload).map(|(sources, references, context, args)| {
+                    code_generator.generate_code(&sources, &references, &context, &args)
+                }),
+                None => {
+                    let timeout = options.generator_timeout.map(Duration::from_secs);
+                    run_plugin(&generator_path, payload, timeout)
+                        .and_then(|response_payload| decode_generator_response(&response_payload))
+                }
+            };
+            result.map_err(|error| ("run code-generator", generator_path.as_str(), error))
+        });
+
+    match response {
+        Ok((generated_files, diagnostics)) => {
+            print_response(&generated_files, &diagnostics, options.diagnostic_format)
+                .expect("failed to print response");
+            generator_reported_errors = diagnostics.iter().any(|d| matches!(d.level, DiagnosticLevel::Error));
+        }
+        Err((action, path, error)) => {
+            let path = path.to_owned();
+            let error = crate::diagnostics::Error::IO { action, path, error };
+            crate::diagnostics::Diagnostic::new(error).push_into(&mut compilation_state.diagnostics);
+        }
+    }
+
+    // Emit any diagnostics that occurred while replaying the request.
+    let has_errors = compilation_state.emit_diagnostics(options);
+    match has_errors || generator_reported_errors {
+        false => ExitCode::SUCCESS,
+        true => ExitCode::FAILURE,
+    }
+}
+
+/// Prints the generated files and diagnostics that a generator returned to 'stdout'.
+fn print_response(
+    generated_files: &[GeneratedFile],
+    diagnostics: &[Diagnostic],
+    format: DiagnosticFormat,
+) -> std::io::Result<()> {
+    let mut stdout = console::Term::stdout();
+
+    match format {
+        DiagnosticFormat::Human => {
+            for generated_file in generated_files {
+                let header = format!("generated file '{}':", generated_file.path);
+                writeln!(stdout, "{}", console::style(header).bold())?;
+                write!(stdout, "{}", generated_file.contents)?;
+                if !generated_file.contents.ends_with('\n') {
+                    writeln!(stdout)?;
+                }
+            }
+            for diagnostic in diagnostics {
+                let level = match diagnostic.level {
+                    DiagnosticLevel::Info => console::style("info").cyan().bold(),
+                    DiagnosticLevel::Warning => console::style("warning").yellow().bold(),
+                    DiagnosticLevel::Error => console::style("error").red().bold(),
+                };
+                write!(stdout, "{level}: {}", diagnostic.message)?;
+                if let Some(source) = &diagnostic.source {
+                    write!(stdout, " (reported on '{source}')")?;
+                }
+                writeln!(stdout)?;
+            }
+            Ok(())
+        }
+        DiagnosticFormat::Json => {
+            #[derive(Serialize)]
+            struct Response<'a> {
+                generated_files: &'a [GeneratedFile],
+                diagnostics: &'a [Diagnostic],
+            }
+
+            let response = Response {
+                generated_files,
+                diagnostics,
+            };
+            serde_json::to_writer(&mut stdout, &response)?;
+            writeln!(stdout)
+        }
+    }
+}
@@ -0,0 +1,120 @@
+// Copyright (c) ZeroC, Inc.
+
+//! This module contains the logic for '--dry-run' and '--check', which report how each generated file compares to the
+//! file that's currently on disk, instead of writing it.
+
+use crate::diagnostics::{Diagnostic, Diagnostics, Error};
+use crate::slice_options::DiagnosticFormat;
+use serde::Serialize;
+use std::io::Write;
+use std::path::Path;
+
+/// Describes how a generated file compares to the file that's currently on disk at its output path.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum GeneratedFileStatus {
+    /// No file currently exists at the output path.
+    New,
+    /// A file exists at the output path, but its contents differ from the generated file's contents.
+    Changed,
+    /// A file exists at the output path, and its contents are identical to the generated file's contents.
+    Unchanged,
+    /// The file was previously generated, but isn't anymore, and so would be deleted.
+    Stale,
+}
+
+pub(crate) fn get_generated_file_status(path: &Path, generated_file_bytes: &[u8]) -> GeneratedFileStatus {
+    match std::fs::read(path) {
+        Ok(current_contents) if current_contents == generated_file_bytes => GeneratedFileStatus::Unchanged,
+        Ok(_) => GeneratedFileStatus::Changed,
+        Err(_) => GeneratedFileStatus::New,
+    }
+}
+
+/// Prints a message to 'stdout' describing what would happen to a generated file if it were written to disk.
+/// If the message couldn't be printed, this reports an error instead.
+pub(crate) fn report_generated_file(
+    generator_name: &str,
+    path: &Path,
+    status: GeneratedFileStatus,
+    format: DiagnosticFormat,
+    diagnostics: &mut Diagnostics,
+) {
+    let mut stdout = console::Term::stdout();
+    if let Err(error) = write_report(&mut stdout, generator_name, path, status, format) {
+        let action = "report generated file";
+        let path = path.display().to_string();
+        Diagnostic::new(Error::IO { action, path, error }).push_into(diagnostics);
+    }
+}
+
+fn write_report(
+    writer: &mut impl Write,
+    generator_name: &str,
+    path: &Path,
+    status: GeneratedFileStatus,
+    format: DiagnosticFormat,
+) -> std::io::Result<()> {
+    match format {
+        DiagnosticFormat::Human => {
+            let status = match status {
+                GeneratedFileStatus::New => console::style("new").green().bold(),
+                GeneratedFileStatus::Changed => console::style("changed").yellow().bold(),
+                GeneratedFileStatus::Unchanged => console::style("unchanged").bold(),
+                GeneratedFileStatus::Stale => console::style("stale").red().bold(),
+            };
+            writeln!(writer, "{status}: {} (from '{generator_name}')", path.display())
+        }
+        DiagnosticFormat::Json => {
+            #[derive(Serialize)]
+            struct GeneratedFileReport<'a> {
+                path: String,
+                status: GeneratedFileStatus,
+                generator: &'a str,
+            }
+
+            let report = GeneratedFileReport {
+                path: path.display().to_string(),
+                status,
+                generator: generator_name,
+            };
+            serde_json::to_writer(&mut *writer, &report)?;
+            writeln!(writer) // Separate each report by a newline character.
+        }
+    }
+}
+
+/// Checks whether the provided generated file is up to date with the file that's currently on disk at its output path.
+/// If it is, this returns `None`, otherwise this returns an error describing the problem, optionally with a diff.
+pub(crate) fn check_generated_file(path: &Path, generated_file_bytes: &[u8], show_diff: bool) -> Option<Diagnostic> {
+    let current_contents = std::fs::read(path).ok();
+    let is_missing = match &current_contents {
+        Some(contents) if contents == generated_file_bytes => return None,
+        Some(_) => false,
+        None => true,
+    };
+
+    let path_string = path.display().to_string();
+    let error = Error::GeneratedFileOutOfDate {
+        path: path_string.clone(),
+        is_missing,
+    };
+    let mut diagnostic = Diagnostic::new(error);
+
+    // If requested, attach a unified diff (between the file on disk and the generated file) to the diagnostic.
+    if show_diff {
+        let current_text = String::from_utf8_lossy(current_contents.as_deref().unwrap_or_default());
+        let generated_text = String::from_utf8_lossy(generated_file_bytes);
+        let diff = similar::TextDiff::from_lines(&current_text, &generated_text)
+            .unified_diff()
+            .header(&path_string, &path_string)
+            .to_string();
+        let message = format!(
+            "differences between the file on disk and the generated file:\n{}",
+            diff.trim_end()
+        );
+        diagnostic = diagnostic.add_note(message, None);
+    }
+
+    Some(diagnostic)
+}
@@ -3,31 +3,34 @@
 // Pull in the core 'slicec' types using aliases to disambiguate them from the Slice-compiler definitions.
 // Any type that starts with 'Grammar' is a slicec type, not a Slice-compiler definition type.
 #![cfg_attr(rustfmt, rustfmt_skip)] // Keep the `use ... as ...` one-per-line.
-use slicec::grammar::Attribute as GrammarAttribute;
-use slicec::grammar::CustomType as GrammarCustomType;
-use slicec::grammar::Definition as GrammarDefinition;
-use slicec::grammar::Dictionary as GrammarDictionary;
-use slicec::grammar::DocComment as GrammarDocComment;
-use slicec::grammar::Enum as GrammarEnum;
-use slicec::grammar::Enumerator as GrammarEnumerator;
-use slicec::grammar::Field as GrammarField;
-use slicec::grammar::Identifier as GrammarIdentifier;
-use slicec::grammar::Interface as GrammarInterface;
-use slicec::grammar::MessageComponent as GrammarMessageComponent;
-use slicec::grammar::Operation as GrammarOperation;
-use slicec::grammar::Parameter as GrammarParameter;
-use slicec::grammar::ResultType as GrammarResultType;
-use slicec::grammar::Sequence as GrammarSequence;
-use slicec::grammar::Struct as GrammarStruct;
-use slicec::grammar::Types as GrammarTypes;
-use slicec::grammar::TypeAlias as GrammarTypeAlias;
-use slicec::grammar::TypeRef as GrammarTypeRef;
-use slicec::slice_file::SliceFile as GrammarSliceFile;
+use crate::grammar::Attribute as GrammarAttribute;
+use crate::grammar::Constant as GrammarConstant;
+use crate::grammar::ConstantValue as GrammarConstantValue;
+use crate::grammar::CustomType as GrammarCustomType;
+use crate::grammar::Definition as GrammarDefinition;
+use crate::grammar::Dictionary as GrammarDictionary;
+use crate::grammar::DocComment as GrammarDocComment;
+use crate::grammar::Enum as GrammarEnum;
+use crate::grammar::Enumerator as GrammarEnumerator;
+use crate::grammar::Field as GrammarField;
+use crate::grammar::Identifier as GrammarIdentifier;
+use crate::grammar::Interface as GrammarInterface;
+use crate::grammar::MessageComponent as GrammarMessageComponent;
+use crate::grammar::Operation as GrammarOperation;
+use crate::grammar::Parameter as GrammarParameter;
+use crate::grammar::ResultType as GrammarResultType;
+use crate::grammar::Sequence as GrammarSequence;
+use crate::grammar::Struct as GrammarStruct;
+use crate::grammar::Types as GrammarTypes;
+use crate::grammar::TypeAlias as GrammarTypeAlias;
+use crate::grammar::TypeRef as GrammarTypeRef;
+use crate::slice_file::SliceFile as GrammarSliceFile;
+use crate::slice_file::Span as GrammarSpan;
 
 // Pull in traits from 'slicec' so we can call their functions.
-use slicec::grammar::{Attributable, Commentable, Contained, Entity, Member, NamedSymbol, Type};
+use crate::grammar::{Attributable, Commentable, Contained, Entity, Member, NamedSymbol, Type};
 // Pull in the attribute types without aliases, since they're not ambiguous.
-use slicec::grammar::attributes::{Allow, Compress, Deprecated, Oneway, SlicedFormat, Unparsed};
+use crate::grammar::attributes::{Allow, Compress, Deprecated, Oneway, SlicedFormat, Unparsed};
 
 // Pull in all the mapped Slice-compiler definition types.
 use crate::definition_types::*;
@@ -38,6 +41,7 @@ fn get_entity_info_for(element: &impl Commentable) -> EntityInfo {
         identifier: element.identifier().to_owned(),
         attributes: get_attributes_from(element.attributes()),
         comment: element.comment().map(Into::into),
+        span: element.span().into(),
     }
 }
 
@@ -61,6 +65,30 @@ fn get_doc_comment_for_parameter(parameter: &GrammarParameter) -> Option<DocComm
         })
 }
 
+/// Returns a [`DocComment`] describing the provided return member if one is present.
+///
+/// Like parameters, doc-comments are not allowed on return members. Instead, you would use a named '@returns' tag
+/// applied to an enclosing operation. So, we map the message of each named '@returns' tag onto its return member.
+fn get_doc_comment_for_return_member(return_member: &GrammarParameter) -> Option<DocComment> {
+    let operation_comment = return_member.parent().comment()?;
+
+    operation_comment.returns.iter()
+        .find(|returns_tag| returns_tag.identifier.as_ref().is_some_and(|i| i.value == return_member.identifier()))
+        .map(|returns_tag| returns_tag.message.value.iter().map(Into::into).collect())
+        .map(|message| DocComment {
+            overview: message,
+            see_tags: Vec::new(),
+        })
+}
+
+/// Returns the message of the provided operation's unnamed '@returns' tag, or an empty [`Message`] if there isn't one.
+fn get_returns_message_for(operation: &GrammarOperation) -> Message {
+    let returns_tag = operation.comment().and_then(|comment| {
+        comment.returns.iter().find(|returns_tag| returns_tag.identifier.is_none())
+    });
+    returns_tag.map_or(Vec::new(), |tag| tag.message.value.iter().map(Into::into).collect())
+}
+
 /// Helper function to convert the result of `tag.linked_entity()` into an [`EntityId`].
 fn convert_doc_comment_link(link_result: Result<&dyn Entity, &GrammarIdentifier>) -> EntityId {
     match link_result {
@@ -74,6 +102,7 @@ fn get_attributes_from(attributes: Vec<&GrammarAttribute>) -> Vec<Attribute> {
     attributes.into_iter().map(|attribute| Attribute {
         directive: attribute.kind.directive().to_owned(),
         args: get_attribute_args(attribute),
+        span: (&attribute.span).into(),
     })
     .collect()
 }
@@ -123,6 +152,26 @@ fn get_attribute_args(attribute: &GrammarAttribute) -> Vec<String> {
     panic!("Impossible attribute encountered")
 }
 
+/// Converts a constant's value (or a field's default value), which is used with the provided type.
+fn convert_constant_value(value: &GrammarConstantValue, data_type: &GrammarTypeRef) -> ConstantValue {
+    // Identifiers are either booleans or enumerators, depending on the type.
+    // By this point, the value has already been validated against its type.
+    match value {
+        GrammarConstantValue::Integer(integer) => ConstantValue::Integer {
+            absolute_value: integer.value.unsigned_abs() as u64,
+            has_negative_value: integer.value.is_negative(),
+        },
+        GrammarConstantValue::String(value, _) => ConstantValue::String(value.clone()),
+        GrammarConstantValue::Identifier(_) => match data_type.concrete_type() {
+            GrammarTypes::Enum(enum_def) => {
+                let enumerator = value.as_enumerator(enum_def).unwrap();
+                ConstantValue::Enumerator(enumerator.parser_scoped_identifier())
+            }
+            _ => ConstantValue::Bool(value.as_bool().unwrap()),
+        },
+    }
+}
+
 // =========================== //
 // Direct conversion functions //
 // =========================== //
@@ -164,6 +213,18 @@ impl From<&GrammarDocComment> for DocComment {
     }
 }
 
+impl From<&GrammarSpan> for Span {
+    fn from(span: &GrammarSpan) -> Self {
+        Span {
+            file: span.file.clone(),
+            start_row: span.start.row as u32,
+            start_column: span.start.col as u32,
+            end_row: span.end.row as u32,
+            end_column: span.end.col as u32,
+        }
+    }
+}
+
 impl From<&GrammarMessageComponent> for MessageComponent {
     fn from(component: &GrammarMessageComponent) -> Self {
         match component {
@@ -209,6 +270,7 @@ impl SliceFileContentsConverter {
                 GrammarDefinition::Enum(v) => converter.convert_enum(v.borrow()),
                 GrammarDefinition::CustomType(v) => Symbol::CustomType(converter.convert_custom_type(v.borrow())),
                 GrammarDefinition::TypeAlias(v) => Symbol::TypeAlias(converter.convert_type_alias(v.borrow())),
+                GrammarDefinition::Constant(v) => Symbol::Constant(converter.convert_constant(v.borrow())),
             };
             converter.converted_contents.push(converted);
         }
@@ -222,6 +284,7 @@ impl SliceFileContentsConverter {
             type_id: self.get_type_id_for(type_ref),
             is_optional: type_ref.is_optional,
             type_attributes: get_attributes_from(type_ref.attributes()),
+            span: (&type_ref.span).into(),
         }
     }
 
@@ -238,6 +301,7 @@ impl SliceFileContentsConverter {
             entity_info: get_entity_info_for(field),
             tag: field.tag.as_ref().map(|integer| integer.value as i32),
             data_type: self.convert_type_ref(field.data_type()),
+            default_value: field.default_value.as_ref().map(|v| convert_constant_value(v, field.data_type())),
         }
     }
 
@@ -255,30 +319,37 @@ impl SliceFileContentsConverter {
         Operation {
             entity_info: get_entity_info_for(operation),
             is_idempotent: operation.is_idempotent,
-            parameters: operation.parameters().into_iter().map(|e| self.convert_parameter(e)).collect(),
+            parameters: operation.parameters().into_iter().map(|e| self.convert_parameter(e, false)).collect(),
             has_streamed_parameter: operation
                 .parameters
                 .last()
                 .is_some_and(|parameter| parameter.borrow().is_streamed),
-            return_type: operation.return_members().into_iter().map(|e| self.convert_parameter(e)).collect(),
+            return_type: operation.return_members().into_iter().map(|e| self.convert_parameter(e, true)).collect(),
             has_streamed_return: operation
                 .return_type
                 .last()
                 .is_some_and(|parameter| parameter.borrow().is_streamed),
+            returns_message: get_returns_message_for(operation),
         }
     }
 
-    fn convert_parameter(&mut self, parameter: &GrammarParameter) -> Field {
+    fn convert_parameter(&mut self, parameter: &GrammarParameter, is_return_member: bool) -> Field {
+        let comment = match is_return_member {
+            true => get_doc_comment_for_return_member(parameter),
+            false => get_doc_comment_for_parameter(parameter),
+        };
         let parameter_info = EntityInfo {
             identifier: parameter.identifier().to_owned(),
             attributes: get_attributes_from(parameter.attributes()),
-            comment: get_doc_comment_for_parameter(parameter),
+            comment,
+            span: (&parameter.span).into(),
         };
 
         Field {
             entity_info: parameter_info,
             tag: parameter.tag.as_ref().map(|integer| integer.value as i32),
             data_type: self.convert_type_ref(parameter.data_type()),
+            default_value: None, // Parameters can't have default values.
         }
     }
 
@@ -330,6 +401,14 @@ impl SliceFileContentsConverter {
         }
     }
 
+    fn convert_constant(&mut self, constant: &GrammarConstant) -> Constant {
+        Constant {
+            entity_info: get_entity_info_for(constant),
+            data_type: self.convert_type_ref(&constant.data_type),
+            value: convert_constant_value(&constant.value, &constant.data_type),
+        }
+    }
+
     fn convert_sequence(&mut self, sequence: &GrammarSequence) -> SequenceType {
         SequenceType {
             element_type: self.convert_type_ref(&sequence.element_type),
@@ -379,3 +458,124 @@ impl SliceFileContentsConverter {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Compiles the provided Slice file, and converts it to its Slice representation.
+    fn convert(slice: &str) -> SliceFile {
+        let compilation_state = crate::compile_from_strings(&[slice], None);
+        assert!(!compilation_state.diagnostics.has_errors());
+        SliceFile::from(&compilation_state.files[0])
+    }
+
+    fn assert_span(span: &Span, (start_row, start_column): (u32, u32), (end_row, end_column): (u32, u32)) {
+        assert_eq!(span.file, "string-0");
+        assert_eq!((span.start_row, span.start_column), (start_row, start_column));
+        assert_eq!((span.end_row, span.end_column), (end_row, end_column));
+    }
+
+    #[test]
+    fn spans_are_converted() {
+        // Arrange
+        let slice = "\
+module Test
+
+[deprecated]
+struct S {
+    x: int32?
+}
+";
+
+        // Act
+        let slice_file = convert(slice);
+
+        // Assert
+        let Symbol::Struct(struct_def) = &slice_file.contents[0] else { panic!() };
+        let field = &struct_def.fields[0];
+        assert_span(&struct_def.entity_info.span, (4, 1), (4, 9));
+        assert_span(&struct_def.entity_info.attributes[0].span, (3, 2), (3, 12));
+        assert_span(&field.entity_info.span, (5, 5), (5, 14));
+        assert_span(&field.data_type.span, (5, 8), (5, 14));
+    }
+
+    #[test]
+    fn parameter_spans_are_converted() {
+        // Arrange
+        let slice = "\
+module Test
+
+interface I {
+    op(name: string) -> bool
+}
+";
+
+        // Act
+        let slice_file = convert(slice);
+
+        // Assert
+        let Symbol::Interface(interface_def) = &slice_file.contents[0] else { panic!() };
+        let operation = &interface_def.operations[0];
+        assert_span(&operation.entity_info.span, (4, 5), (4, 29));
+        assert_span(&operation.parameters[0].entity_info.span, (4, 8), (4, 20));
+        assert_span(&operation.parameters[0].data_type.span, (4, 14), (4, 20));
+        assert_span(&operation.return_type[0].data_type.span, (4, 25), (4, 29));
+    }
+
+    /// Returns the text of the provided message, with any links replaced by the IDs of the entities they link to.
+    fn message_text(message: &Message) -> String {
+        let components = message.iter().map(|component| match component {
+            MessageComponent::Text(text) => text.as_str(),
+            MessageComponent::Link(entity_id) => entity_id.as_str(),
+        });
+        components.collect::<String>().trim().to_owned()
+    }
+
+    #[test]
+    fn unnamed_returns_tags_are_converted() {
+        // Arrange
+        let slice = "
+            module Test
+            interface I {
+                /// @returns: the greeting.
+                greet() -> string
+            }
+        ";
+
+        // Act
+        let slice_file = convert(slice);
+
+        // Assert
+        let Symbol::Interface(interface_def) = &slice_file.contents[0] else { panic!() };
+        let operation = &interface_def.operations[0];
+        assert_eq!(message_text(&operation.returns_message), "the greeting.");
+        assert!(operation.return_type[0].entity_info.comment.is_none());
+    }
+
+    #[test]
+    fn named_returns_tags_are_mapped_onto_return_members() {
+        // Arrange
+        let slice = "
+            module Test
+            interface I {
+                /// @returns count: the number of items.
+                list() -> (count: int32, names: Sequence<string>)
+            }
+        ";
+
+        // Act
+        let slice_file = convert(slice);
+
+        // Assert
+        let interface_def = slice_file.contents.iter().find_map(|symbol| match symbol {
+            Symbol::Interface(interface_def) => Some(interface_def),
+            _ => None,
+        });
+        let operation = &interface_def.unwrap().operations[0];
+        assert!(operation.returns_message.is_empty());
+        let count_comment = operation.return_type[0].entity_info.comment.as_ref().unwrap();
+        assert_eq!(message_text(&count_comment.overview), "the number of items.");
+        assert!(operation.return_type[1].entity_info.comment.is_none());
+    }
+}
@@ -2,14 +2,15 @@
 
 use crate::diagnostics::Lint;
 use clap::ArgAction::Append;
-use clap::{Parser, ValueEnum};
+use clap::{Parser, Subcommand, ValueEnum};
+use std::num::NonZeroUsize;
 
 // Note: clap uses the doc-comments of fields to populate the '--help' output of slicec.
 //       boolean flags automatically default to false, and strings automatically default to empty.
 
 /// This struct is responsible for parsing the command line options of the 'slicec' compiler.
 /// The option parsing capabilities are generated on the struct by the `clap::Parser` derive macro.
-#[derive(Debug, Default, Parser)]
+#[derive(Clone, Debug, Default, Parser)]
 #[command(author, version, about, long_about = DESCRIPTION, rename_all = "kebab-case")]
 pub struct SliceOptions {
     /// List of Slice files to compile.
@@ -22,16 +23,77 @@ pub struct SliceOptions {
     /// Specify a code-generator plugin that should be run after parsing and validation complete (if successful).
     ///   Ex: '--generator /path/to/my/generator'
     ///
+    /// Code-generators can also be specified by name, in which case an executable named 'slicec-<NAME>' is searched
+    /// for in any plugin directories, then in the directories listed by 'SLICEC_PLUGIN_PATH', and finally on 'PATH'.
+    /// If no such executable is found, an executable named exactly '<NAME>' is searched for in the same locations.
+    ///   Ex: '--generator cs' runs the first executable named 'slicec-cs' that is found
+    ///
+    /// slicec also has a built-in 'json' code-generator, which writes the parsed Slice definitions to 'ast.json'.
+    ///   Ex: '--generator json,path=my-ast.json'
+    ///
     /// Each code-generator can be provided with arbitrary string arguments using the following syntax:
     ///   '/path/to/my/generator,arg1=value1,arg2 = value2,arg3,...'
     /// Leading and trailing whitespace is stripped from arguments and their values. Argument values are optional.
     #[arg(short = 'G', long = "generator", num_args = 1, action = Append, value_name = "GENERATOR", value_parser = plugin_parser, verbatim_doc_comment)]
     pub generators: Vec<Plugin>,
 
+    /// Specify a transformer plugin, which rewrites the parsed Slice definitions before they're passed to the
+    /// code-generators. Transformers are run in the order they're specified, before any of the code-generators.
+    /// Transformers are found and passed arguments in the same way as code-generators.
+    ///   Ex: '--transform /path/to/my/transformer,arg1=value1'
+    #[arg(long = "transform", num_args = 1, action = Append, value_name = "TRANSFORMER", value_parser = plugin_parser, verbatim_doc_comment)]
+    pub transformers: Vec<Plugin>,
+
+    /// Add a directory to search for code-generators that are specified by name.
+    #[arg(long = "plugin-dir", num_args = 1, action = Append, value_name = "DIRECTORY", global = true)]
+    pub plugin_dirs: Vec<String>,
+
+    /// Kill any code-generator that hasn't finished after the specified number of seconds, and report an error.
+    #[arg(long, value_name = "SECONDS", global = true)]
+    pub generator_timeout: Option<u64>,
+
+    /// Set the maximum number of code-generators that can run at once. By default, they're all run at once.
+    #[arg(short = 'j', long, value_name = "N")]
+    pub jobs: Option<NonZeroUsize>,
+
     /// Set the output directory for the generated code. Defaults to the current working directory.
+    /// If '-' is specified, the generated files are written to stdout instead, as a Slice-encoded sequence of
+    /// 'GeneratedFile's (using the same encoding that code-generators use for their responses).
     #[arg(short = 'O', long, value_name = "DIRECTORY")]
     pub output_dir: Option<String>,
 
+    /// Write all the generated files into a single tar archive at the specified path, instead of the output directory.
+    #[arg(long, value_name = "FILE", conflicts_with_all = ["output_dir", "dry_run", "check", "clean"])]
+    pub output_archive: Option<String>,
+
+    /// Set the directory that the generation cache is stored in. Defaults to the output directory.
+    /// Code-generators whose inputs are unchanged since they last ran, and whose generated files are intact, are
+    /// skipped, and the files they previously generated are reused instead.
+    #[arg(long, value_name = "DIRECTORY")]
+    pub cache_dir: Option<String>,
+
+    /// Always run the code-generators, without reading or updating the generation cache.
+    #[arg(long, conflicts_with = "cache_dir")]
+    pub no_cache: bool,
+
+    /// Write a dependency file to the specified path, listing the Slice files (and configuration file) that each
+    /// generated file depends on.
+    /// This file uses Makefile syntax, which is understood by most build systems (including Make and Ninja).
+    #[arg(long, value_name = "FILE")]
+    pub depfile: Option<String>,
+
+    /// Write the parsed Slice definitions to the specified file as JSON (or to stdout if '-' is specified).
+    /// This can't write to stdout if the generated files are also being written to stdout (with '-O -').
+    /// The JSON's schema is documented at 'https://github.com/icerpc/slicec/blob/main/slicec/docs/ast-json.md'.
+    #[arg(long, value_name = "FILE")]
+    pub emit_ast_json: Option<String>,
+
+    /// Save the exact request payload that is sent to the code-generator to the specified file, for debugging.
+    /// If multiple code-generators are specified, a separate file is saved for each of them,
+    /// with the generator's name inserted before the file's extension (ex: 'request.slicec-cs.bin').
+    #[arg(long, value_name = "FILE")]
+    pub emit_request: Option<String>,
+
     /// Define a preprocessor symbol.
     #[arg(short = 'D', num_args = 1, action = Append, value_name = "SYMBOL")]
     pub defined_symbols: Vec<String>,
@@ -41,17 +103,87 @@ pub struct SliceOptions {
     #[arg(short = 'A', long = "allow", num_args = 1, action = Append, value_name = "LINT_NAME", value_parser = Lint::ALLOWABLE_LINT_IDENTIFIERS, hide_possible_values = true, ignore_case = true)]
     pub allowed_lints: Vec<String>,
 
-    /// Validate input files without generating code for them.
+    /// Instruct the compiler to deny the specified lint, reporting any violations of it as errors.
+    /// Lints which are allowed (by '--allow' or by an 'allow' attribute) are not affected by this.
+    #[arg(long = "deny", num_args = 1, action = Append, value_name = "LINT_NAME", value_parser = Lint::ALLOWABLE_LINT_IDENTIFIERS, hide_possible_values = true, ignore_case = true)]
+    pub denied_lints: Vec<String>,
+
+    /// Load options from the specified configuration file. Options passed on the command line take precedence.
+    /// If this isn't specified, slicec loads the file named 'slice.toml' in the current directory, if there is one.
+    /// Parent directories aren't searched.
+    #[arg(long, value_name = "FILE")]
+    pub config: Option<String>,
+
+    /// Run the code-generators, but don't write any files to disk.
+    /// Instead, report whether each generated file would be new, changed, unchanged, or deleted as stale.
     #[arg(long)]
     pub dry_run: bool,
 
+    /// Run the code-generators, and check that the generated files on disk are up to date, without writing any files.
+    /// An error is reported for each generated file that is missing or out of date.
+    #[arg(long, conflicts_with = "dry_run")]
+    pub check: bool,
+
+    /// Show a unified diff for each generated file that is out of date. Can only be used with '--check'.
+    #[arg(long, requires = "check")]
+    pub diff: bool,
+
+    /// Delete all the files previously generated by the specified code-generators, instead of generating code.
+    /// If no code-generators are specified, the files previously generated by every code-generator are deleted.
+    #[arg(long, conflicts_with_all = ["dry_run", "check"])]
+    pub clean: bool,
+
+    /// Keep running after compiling, and recompile (and rerun the code-generators) whenever a Slice file changes.
+    /// Source files, references (including files added to referenced directories), and the configuration file are
+    /// checked for changes periodically, until slicec is stopped. Files which are added, and match one of the
+    /// configuration file's glob patterns, are also noticed.
+    #[arg(long, conflicts_with = "clean")]
+    pub watch: bool,
+
     /// Set which format to emit errors and warnings with.
-    #[arg(long, value_name = "FORMAT", value_enum, default_value_t = DiagnosticFormat::Human, ignore_case = true)]
+    #[arg(long, value_name = "FORMAT", value_enum, default_value_t = DiagnosticFormat::Human, ignore_case = true, global = true)]
     pub diagnostic_format: DiagnosticFormat,
 
     /// Disable ANSI color codes in diagnostic output.
-    #[arg(long)]
+    #[arg(long, global = true)]
     pub disable_color: bool,
+
+    /// Run a command other than compiling Slice files.
+    #[command(subcommand)]
+    pub command: Option<SlicecCommand>,
+}
+
+/// The commands which slicec can run in place of compiling Slice files.
+#[derive(Clone, Debug, Subcommand)]
+pub enum SlicecCommand {
+    /// Send a request payload (previously saved with '--emit-request') to a code-generator, and print its response.
+    ReplayRequest {
+        /// The file containing the saved request payload.
+        #[arg(value_name = "FILE")]
+        request_file: String,
+
+        /// The code-generator to send the request to. This accepts anything '--generator' does (without arguments).
+        #[arg(short = 'G', long, value_name = "GENERATOR")]
+        generator: String,
+    },
+
+    /// Run a code-generator over a directory of test cases, and check its output against snapshots of the expected
+    /// output. Each subdirectory that contains Slice files is a test case. The files it's expected to generate are
+    /// stored in an 'expected' directory within it, and the diagnostics it's expected to report are stored in an
+    /// 'expected-diagnostics.txt' file.
+    TestGenerator {
+        /// The directory containing the test cases.
+        #[arg(value_name = "DIR")]
+        test_dir: String,
+
+        /// The code-generator to test, along with any arguments to pass to it.
+        #[arg(short = 'G', long, value_name = "GENERATOR", value_parser = plugin_parser)]
+        generator: Plugin,
+
+        /// Update the snapshots of any test cases that fail, to match the code-generator's current output.
+        #[arg(long)]
+        bless: bool,
+    },
 }
 
 /// Short description of slicec that is displayed in its help dialogue.
@@ -139,6 +271,15 @@ pub struct Plugin {
     pub args: Vec<(String, String)>,
 }
 
+impl Plugin {
+    /// Returns the name of this plugin, which is the name of its executable, without any directories or extension.
+    /// Ex: the name of '/path/to/slicec-cs.exe' is 'slicec-cs'.
+    pub fn name(&self) -> &str {
+        let file_stem = std::path::Path::new(&self.path).file_stem();
+        file_stem.and_then(|stem| stem.to_str()).unwrap_or(&self.path)
+    }
+}
+
 /// This enum is used to specify the format for emitted diagnostics.
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, ValueEnum)]
 pub enum DiagnosticFormat {
@@ -0,0 +1,479 @@
+// Copyright (c) ZeroC, Inc.
+
+//! This module implements the 'test-generator' command, which runs a code-generator over a directory of test cases,
+//! and checks its output against snapshots of the output it's expected to generate (often called 'golden files').
+//!
+//! Each subdirectory of the test directory that contains Slice files is a test case, and is laid out like so:
+//! ```text
+//! my_test_case/
+//!     input.slice               <- Any number of Slice files, which are all compiled as source files.
+//!     expected/                 <- The files that the code-generator is expected to generate.
+//!         generated_file.txt
+//!     expected-diagnostics.txt  <- The diagnostics that the code-generator is expected to report, one per line.
+//! ```
+//! Test cases without an 'expected-diagnostics.txt' file are expected not to report any diagnostics.
+//! With '--bless', the snapshots of any failing test cases are overwritten with the code-generator's current output.
+
+use crate::code_generator::CodeGenerator;
+use crate::compilation_state::CompilationState;
+use crate::definition_types::{self, CompilationContext, GeneratedFile};
+use crate::diagnostic_emitter::DiagnosticEmitter;
+use crate::diagnostics::{Diagnostic, Diagnostics, Error};
+use crate::generation::{
+    convert_slice_files, create_compilation_context, decode_generator_response, encode_generate_code_request,
+    encode_plugin_payload, validate_generated_file_path,
+};
+use crate::plugin_process::run_plugin;
+use crate::slice_file::SliceFile;
+use crate::slice_options::{DiagnosticFormat, Plugin, SliceOptions};
+use crate::utils::plugin_util::resolve_plugin_path;
+use crate::{compile_files, handshake};
+use serde::Serialize;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::time::Duration;
+
+/// The name of the directory (within a test case) holding the files that the code-generator is expected to generate.
+const EXPECTED_DIR: &str = "expected";
+
+/// The name of the file (within a test case) holding the diagnostics that the code-generator is expected to report.
+const EXPECTED_DIAGNOSTICS_FILE: &str = "expected-diagnostics.txt";
+
+pub fn test_generator(
+    test_dir: &str,
+    generator: &Plugin,
+    code_generator: Option<&dyn CodeGenerator>,
+    bless: bool,
+    options: &SliceOptions,
+) -> ExitCode {
+    let mut setup_state = CompilationState::create();
+
+    // If the generator isn't run in-process, resolve its path (in case it was specified by name), and then perform a
+    // handshake with it, to check that it uses the same protocol version as us.
+    let generator = match code_generator {
+        Some(_) => Some(generator.clone()),
+        None => resolve_plugin_path(&generator.path, &options.plugin_dirs, &mut setup_state.diagnostics)
+            .map(|path| Plugin {
+                path,
+                args: generator.args.clone(),
+            })
+            .filter(|plugin| perform_handshake(plugin, options, &mut setup_state.diagnostics)),
+    };
+
+    // Find all the test cases in the test directory.
+    let test_cases = find_test_cases(Path::new(test_dir)).unwrap_or_else(|error| {
+        let path = test_dir.to_owned();
+        let action = "read test directory";
+        Diagnostic::new(Error::IO { action, path, error }).push_into(&mut setup_state.diagnostics);
+        Vec::new()
+    });
+
+    let Some(generator) = generator.filter(|_| !setup_state.diagnostics.has_errors()) else {
+        setup_state.emit_diagnostics(options);
+        return ExitCode::FAILURE;
+    };
+
+    // Run each of the test cases, emitting any diagnostics and reporting the status of each case as it finishes.
+    let mut statuses = Vec::new();
+    for test_case in &test_cases {
+        let (status, compilation_state) = run_test_case(test_case, &generator, code_generator, bless, options);
+        emit_diagnostics(compilation_state, options);
+        report_test_case(&test_case.name, status, options.diagnostic_format).expect("failed to report test case");
+        statuses.push(status);
+    }
+
+    let failed_count = statuses
+        .iter()
+        .filter(|status| **status == TestCaseStatus::Failed)
+        .count();
+    let blessed_count = statuses
+        .iter()
+        .filter(|status| **status == TestCaseStatus::Blessed)
+        .count();
+    if options.diagnostic_format == DiagnosticFormat::Human {
+        emit_test_totals(statuses.len(), failed_count, blessed_count).expect("failed to emit totals");
+    }
+
+    match failed_count == 0 {
+        true => ExitCode::SUCCESS,
+        false => ExitCode::FAILURE,
+    }
+}
+
+/// A directory of Slice files to run the code-generator over, along with snapshots of its expected output.
+#[derive(Debug)]
+struct TestCase {
+    /// The name of the test case, which is the name of its directory.
+    name: String,
+    /// The path of the test case's directory.
+    dir: PathBuf,
+    /// The paths of the Slice files in the test case's directory, in alphabetical order.
+    slice_files: Vec<PathBuf>,
+}
+
+/// Describes the outcome of running a test case.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum TestCaseStatus {
+    /// The code-generator's output matched the test case's snapshots.
+    Ok,
+    /// The code-generator's output didn't match the test case's snapshots, so they were updated to match it.
+    Blessed,
+    /// The code-generator's output didn't match the test case's snapshots, or the code-generator couldn't be run.
+    Failed,
+}
+
+/// Returns all the test cases in the provided directory, in alphabetical order.
+/// Each subdirectory that directly contains at least one Slice file is a test case.
+fn find_test_cases(test_dir: &Path) -> std::io::Result<Vec<TestCase>> {
+    let mut test_cases = Vec::new();
+    for entry in std::fs::read_dir(test_dir)? {
+        let dir = entry?.path();
+        if !dir.is_dir() {
+            continue;
+        }
+
+        let mut slice_files = Vec::new();
+        for entry in std::fs::read_dir(&dir)? {
+            let path = entry?.path();
+            if path.is_file() && path.extension().is_some_and(|extension| extension == "slice") {
+                slice_files.push(path);
+            }
+        }
+        if !slice_files.is_empty() {
+            slice_files.sort();
+            let name = dir.file_name().unwrap_or_default().to_string_lossy().into_owned();
+            test_cases.push(TestCase { name, dir, slice_files });
+        }
+    }
+    test_cases.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(test_cases)
+}
+
+/// Sends a 'describe' request to the provided plugin, and returns whether it uses the same protocol version as us.
+fn perform_handshake(plugin: &Plugin, options: &SliceOptions, diagnostics: &mut Diagnostics) -> bool {
+    let timeout = options.generator_timeout.map(Duration::from_secs);
+    let response = handshake::encode_describe_request()
+        .map_err(std::io::Error::from)
+        .and_then(|request| run_plugin(&plugin.path, request, timeout));
+    handshake::check_describe_response(plugin, response, diagnostics).is_some()
+}
+
+/// Runs the code-generator over the provided test case, and checks its output against the test case's snapshots.
+/// This returns the status of the test case, along with the state of its compilation, which holds any diagnostics that
+/// were reported while running it.
+fn run_test_case(
+    test_case: &TestCase,
+    generator: &Plugin,
+    code_generator: Option<&dyn CodeGenerator>,
+    bless: bool,
+    options: &SliceOptions,
+) -> (TestCaseStatus, CompilationState) {
+    let mut state = CompilationState::create();
+
+    // Read the test case's Slice files. Each file's path is relative to the test directory, so that code-generators
+    // which embed the paths of Slice files in their output generate the same output, wherever the tests are run from.
+    for path in &test_case.slice_files {
+        let file_name = path.file_name().unwrap_or_default().to_string_lossy();
+        match std::fs::read_to_string(path) {
+            Ok(raw_text) => {
+                let relative_path = format!("{}/{file_name}", test_case.name);
+                state.files.push(SliceFile::new(relative_path, raw_text, true));
+            }
+            Err(error) => {
+                let path = path.display().to_string();
+                Diagnostic::new(Error::IO {
+                    action: "read",
+                    path,
+                    error,
+                })
+                .push_into(&mut state.diagnostics);
+            }
+        }
+    }
+
+    // Compile the Slice files, and if that succeeds, run the code-generator on them.
+    if !state.diagnostics.has_errors() {
+        compile_files(&mut state, options);
+    }
+    if state.diagnostics.has_errors() {
+        return (TestCaseStatus::Failed, state);
+    }
+    // Since generated files are compared against the test case's 'expected' directory, it's used as the output
+    // directory. The Slice files' paths are relative to the test directory, so they're resolved against it.
+    let expected_dir = test_case.dir.join(EXPECTED_DIR);
+    let context_options = SliceOptions {
+        output_dir: Some(expected_dir.display().to_string()),
+        ..options.clone()
+    };
+    let context = create_compilation_context(&state.files, test_case.dir.parent(), &context_options);
+    let response = run_generator(&state.files, &context, generator, code_generator, options);
+    let (generated_files, generator_diagnostics) = match response {
+        Ok(response) => response,
+        Err(error) => {
+            let path = generator.path.clone();
+            let action = "run code-generator";
+            Diagnostic::new(Error::IO { action, path, error }).push_into(&mut state.diagnostics);
+            return (TestCaseStatus::Failed, state);
+        }
+    };
+
+    // Check the generated files and diagnostics against the test case's snapshots.
+    let mut mismatches = Diagnostics::new();
+    let snapshots = match check_snapshots(test_case, &generated_files, &generator_diagnostics, &mut mismatches) {
+        Ok(snapshots) => snapshots,
+        Err(error) => {
+            let path = expected_dir.display().to_string();
+            let action = "read snapshots";
+            Diagnostic::new(Error::IO { action, path, error }).push_into(&mut state.diagnostics);
+            return (TestCaseStatus::Failed, state);
+        }
+    };
+    if !mismatches.has_errors() {
+        return (TestCaseStatus::Ok, state);
+    }
+
+    // If the output didn't match, either report the mismatches, or if we're blessing, update the snapshots to match.
+    if !bless {
+        state.diagnostics.extend(mismatches);
+        return (TestCaseStatus::Failed, state);
+    }
+    match write_snapshots(test_case, &snapshots) {
+        Ok(()) => (TestCaseStatus::Blessed, state),
+        Err(error) => {
+            let path = test_case.dir.display().to_string();
+            let action = "write snapshots";
+            Diagnostic::new(Error::IO { action, path, error }).push_into(&mut state.diagnostics);
+            (TestCaseStatus::Failed, state)
+        }
+    }
+}
+
+/// Runs the code-generator on the provided files, either in-process, or by sending a 'generateCode' request to it.
+/// Returns the files and diagnostics that the code-generator returned.
+fn run_generator(
+    files: &[SliceFile],
+    context: &CompilationContext,
+    generator: &Plugin,
+    code_generator: Option<&dyn CodeGenerator>,
+    options: &SliceOptions,
+) -> std::io::Result<(Vec<GeneratedFile>, Vec<definition_types::Diagnostic>)> {
+    let (source_files, reference_files) = convert_slice_files(files);
+    if let Some(code_generator) = code_generator {
+        return Ok(code_generator.generate_code(&source_files, &reference_files, context, &generator.args));
+    }
+
+    let request = encode_generate_code_request(&source_files, &reference_files, context)?;
+    let payload = encode_plugin_payload(generator, &request)?;
+    let timeout = options.generator_timeout.map(Duration::from_secs);
+    let response = run_plugin(&generator.path, payload, timeout)?;
+    decode_generator_response(&response)
+}
+
+/// Checks the provided generated files and diagnostics against the test case's snapshots, reporting an error for each
+/// snapshot that doesn't match. This returns the snapshots that match the output, which are written by '--bless'.
+fn check_snapshots(
+    test_case: &TestCase,
+    generated_files: &[GeneratedFile],
+    generator_diagnostics: &[definition_types::Diagnostic],
+    mismatches: &mut Diagnostics,
+) -> std::io::Result<Vec<(PathBuf, String)>> {
+    let mut snapshots = Vec::new();
+
+    // Check that each generated file matches its snapshot.
+    let expected_dir = test_case.dir.join(EXPECTED_DIR);
+    let output_dir = Some(expected_dir.display().to_string());
+    let mut generated_paths = Vec::new();
+    for generated_file in generated_files {
+        let path = match validate_generated_file_path(generated_file, &output_dir, &generated_paths) {
+            Ok(path) => path,
+            Err(error) => {
+                let path = generated_file.path.clone();
+                let action = "check generated file";
+                Diagnostic::new(Error::IO { action, path, error }).push_into(mismatches);
+                continue;
+            }
+        };
+        generated_paths.push((path.clone(), String::new()));
+
+        let relative_path = path.strip_prefix(&test_case.dir).unwrap_or(&path).to_owned();
+        if let Some(mismatch) = check_snapshot(test_case, &relative_path, &generated_file.contents)? {
+            mismatch.push_into(mismatches);
+        }
+        snapshots.push((relative_path, generated_file.contents.clone()));
+    }
+
+    // Check that every snapshot was generated.
+    for snapshot_path in find_files(&expected_dir)? {
+        if !generated_paths.iter().any(|(path, _)| *path == snapshot_path) {
+            let relative_path = snapshot_path.strip_prefix(&test_case.dir).unwrap_or(&snapshot_path);
+            Diagnostic::new(Error::SnapshotNotGenerated {
+                test_case: test_case.name.clone(),
+                path: relative_path.display().to_string(),
+            })
+            .push_into(mismatches);
+        }
+    }
+
+    // Check that the diagnostics match their snapshot. If no diagnostics were reported, there shouldn't be a snapshot.
+    let diagnostics_text = format_generator_diagnostics(generator_diagnostics);
+    let diagnostics_path = PathBuf::from(EXPECTED_DIAGNOSTICS_FILE);
+    let has_diagnostics_snapshot = test_case.dir.join(&diagnostics_path).exists();
+    if !diagnostics_text.is_empty() || has_diagnostics_snapshot {
+        if let Some(mismatch) = check_snapshot(test_case, &diagnostics_path, &diagnostics_text)? {
+            mismatch.push_into(mismatches);
+        }
+    }
+    if !diagnostics_text.is_empty() {
+        snapshots.push((diagnostics_path, diagnostics_text));
+    }
+
+    Ok(snapshots)
+}
+
+/// Checks whether the snapshot at the provided path (relative to the test case's directory) has the provided contents.
+/// If it doesn't, this returns an error, with a unified diff between the snapshot and the contents attached to it.
+fn check_snapshot(test_case: &TestCase, relative_path: &Path, contents: &str) -> std::io::Result<Option<Diagnostic>> {
+    let snapshot = match std::fs::read_to_string(test_case.dir.join(relative_path)) {
+        Ok(snapshot) if snapshot == contents => return Ok(None),
+        Ok(snapshot) => Some(snapshot),
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => None,
+        Err(error) => return Err(error),
+    };
+
+    let path_string = relative_path.display().to_string();
+    let error = Error::SnapshotMismatch {
+        test_case: test_case.name.clone(),
+        path: path_string.clone(),
+        is_missing: snapshot.is_none(),
+    };
+    let diff = similar::TextDiff::from_lines(snapshot.as_deref().unwrap_or_default(), contents)
+        .unified_diff()
+        .header(&path_string, &path_string)
+        .to_string();
+    let message = format!(
+        "differences between the snapshot and the generated output:\n{}",
+        diff.trim_end(),
+    );
+    Ok(Some(Diagnostic::new(error).add_note(message, None)))
+}
+
+/// Replaces the test case's snapshots with the provided snapshots (whose paths are relative to the test case).
+/// Any snapshots that aren't replaced are deleted.
+fn write_snapshots(test_case: &TestCase, snapshots: &[(PathBuf, String)]) -> std::io::Result<()> {
+    let expected_dir = test_case.dir.join(EXPECTED_DIR);
+    if expected_dir.exists() {
+        std::fs::remove_dir_all(&expected_dir)?;
+    }
+    let diagnostics_path = test_case.dir.join(EXPECTED_DIAGNOSTICS_FILE);
+    if diagnostics_path.exists() {
+        std::fs::remove_file(&diagnostics_path)?;
+    }
+
+    for (relative_path, contents) in snapshots {
+        let path = test_case.dir.join(relative_path);
+        if let Some(parent_dir) = path.parent() {
+            std::fs::create_dir_all(parent_dir)?;
+        }
+        std::fs::write(path, contents)?;
+    }
+    Ok(())
+}
+
+/// Returns the paths of all the files in the provided directory, and its subdirectories.
+/// If the directory doesn't exist, this returns an empty list.
+fn find_files(dir: &Path) -> std::io::Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    if !dir.is_dir() {
+        return Ok(files);
+    }
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        match path.is_dir() {
+            true => files.extend(find_files(&path)?),
+            false => files.push(path),
+        }
+    }
+    Ok(files)
+}
+
+/// Formats the diagnostics that a code-generator reported for storing in a snapshot, with one diagnostic per line.
+fn format_generator_diagnostics(diagnostics: &[definition_types::Diagnostic]) -> String {
+    let mut formatted = String::new();
+    for diagnostic in diagnostics {
+        let level = match diagnostic.level {
+            definition_types::DiagnosticLevel::Info => "info",
+            definition_types::DiagnosticLevel::Warning => "warning",
+            definition_types::DiagnosticLevel::Error => "error",
+        };
+        formatted += &format!("{level}: {}", diagnostic.message);
+        if let Some(source) = &diagnostic.source {
+            formatted += &format!(" (reported on '{source}')");
+        }
+        formatted += "\n";
+    }
+    formatted
+}
+
+/// Emits the diagnostics that were reported while running a test case, without emitting their totals.
+fn emit_diagnostics(compilation_state: CompilationState, options: &SliceOptions) {
+    let files = &compilation_state.files;
+    let diagnostics = compilation_state
+        .diagnostics
+        .into_updated(&compilation_state.ast, files, options);
+
+    let mut stderr = console::Term::stderr();
+    let mut emitter = DiagnosticEmitter::new(&mut stderr, options, files);
+    DiagnosticEmitter::emit_diagnostics(&mut emitter, diagnostics).expect("failed to emit diagnostics");
+}
+
+/// Prints the status of a test case to 'stdout'.
+fn report_test_case(name: &str, status: TestCaseStatus, format: DiagnosticFormat) -> std::io::Result<()> {
+    let mut stdout = console::Term::stdout();
+
+    match format {
+        DiagnosticFormat::Human => {
+            let status = match status {
+                TestCaseStatus::Ok => console::style("ok").green().bold(),
+                TestCaseStatus::Blessed => console::style("blessed").yellow().bold(),
+                TestCaseStatus::Failed => console::style("failed").red().bold(),
+            };
+            writeln!(stdout, "{status}: test case '{name}'")
+        }
+        DiagnosticFormat::Json => {
+            #[derive(Serialize)]
+            struct TestCaseReport<'a> {
+                test_case: &'a str,
+                status: TestCaseStatus,
+            }
+
+            let report = TestCaseReport { test_case: name, status };
+            serde_json::to_writer(&mut stdout, &report)?;
+            writeln!(stdout)
+        }
+    }
+}
+
+/// Prints the number of test cases that were run, and how many of them failed or were blessed, to 'stdout'.
+fn emit_test_totals(total_count: usize, failed_count: usize, blessed_count: usize) -> std::io::Result<()> {
+    let mut stdout = console::Term::stdout();
+
+    if blessed_count > 0 {
+        let blessed = console::style("Blessed").yellow().bold();
+        writeln!(
+            stdout,
+            "{blessed}: Updated the snapshots of {blessed_count} test case(s)"
+        )?;
+    }
+    match failed_count {
+        0 => {
+            let passed = console::style("Passed").green().bold();
+            writeln!(stdout, "{passed}: All {total_count} test case(s) passed")
+        }
+        _ => {
+            let failed = console::style("Failed").red().bold();
+            writeln!(stdout, "{failed}: {failed_count} of {total_count} test case(s) failed")
+        }
+    }
+}
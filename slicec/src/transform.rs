@@ -0,0 +1,449 @@
+// Copyright (c) ZeroC, Inc.
+
+//! This module implements transformers, which are plugins that rewrite the parsed Slice definitions before they're
+//! passed to the code-generators (for example, to add attributes, or to remove internal-only operations).
+//!
+//! Transformers use the same handshake as code-generators. After it, each transformer is sent a 'transform' request,
+//! which holds the same Slice files and context as a 'generateCode' request, followed by the transformer's arguments.
+//! It responds with the (possibly modified) source files and reference files, followed by any diagnostics it reported.
+//!
+//! Transformers are run one at a time, in the order they were specified in, with each transformer receiving the files
+//! returned by the previous one. After each transformer runs, slicec checks that the files it returned are still
+//! well-formed, meaning that every type ID resolves to a type, every base interface resolves to an interface, and every
+//! enumerator used by a constant or default value resolves to an enumerator.
+
+use crate::ast::Ast;
+use crate::cache::Cache;
+use crate::definition_types::{self, Arguments, CompilationContext, ConstantValue, SliceFile, Symbol, TypeRef};
+use crate::diagnostics::{Diagnostic, Diagnostics, Error};
+use crate::generation::convert_generator_diagnostic;
+use crate::grammar::Primitive;
+use crate::handshake::{self, HandshakeCache};
+use crate::plugin_process::run_plugin;
+use crate::slice_options::{Plugin, SliceOptions};
+use slice_codec::decoder::Decoder;
+use slice_codec::encoder::Encoder;
+use std::collections::BTreeSet;
+use std::time::Duration;
+
+/// Runs each of the transformers in the provided options over the provided Slice files, replacing them in place.
+///
+/// If a transformer fails to run, reports an error, or returns files which aren't well-formed, errors are reported,
+/// and no more transformers are run. In this case, the provided files are left as the last transformer returned them.
+/// Transformers which already passed their handshake (according to either of the provided caches) aren't described
+/// again.
+#[allow(clippy::too_many_arguments)]
+pub fn run_transformers(
+    source_files: &mut Vec<SliceFile>,
+    reference_files: &mut Vec<SliceFile>,
+    context: &CompilationContext,
+    ast: &Ast,
+    handshake_cache: &mut HandshakeCache,
+    mut cache: Option<&mut Cache>,
+    options: &SliceOptions,
+    diagnostics: &mut Diagnostics,
+) {
+    let timeout = options.generator_timeout.map(Duration::from_secs);
+    for transformer in &options.transformers {
+        // Check that the transformer uses the same protocol version as us, before sending it anything else.
+        let cache = cache.as_deref_mut();
+        if handshake::describe_plugins(&[transformer], handshake_cache, cache, None, timeout, diagnostics) != [true] {
+            return;
+        }
+
+        // Send the files to the transformer, and decode the files it returns.
+        let response = encode_transform_request(source_files, reference_files, context, transformer)
+            .map_err(std::io::Error::from)
+            .and_then(|payload| run_plugin(&transformer.path, payload, timeout))
+            .and_then(|response_payload| decode_transform_response(&response_payload));
+        let (transformed_source_files, transformed_reference_files, transformer_diagnostics) = match response {
+            Ok(response) => response,
+            Err(error) => {
+                let action = "run transformer";
+                let path = transformer.path.clone();
+                Diagnostic::new(Error::IO { action, path, error }).push_into(diagnostics);
+                return;
+            }
+        };
+
+        // Store any diagnostics the transformer reported. If it reported any errors, we don't use the files it
+        // returned.
+        let mut converted_diagnostics = Diagnostics::new();
+        for transformer_diagnostic in transformer_diagnostics {
+            convert_generator_diagnostic(transformer, transformer_diagnostic).push_into(&mut converted_diagnostics);
+        }
+        let has_errors = converted_diagnostics.has_errors();
+        diagnostics.extend(converted_diagnostics);
+        if has_errors {
+            return;
+        }
+
+        // Check that the returned files are still well-formed before using them.
+        let problems = validate_transformed_files(&transformed_source_files, &transformed_reference_files, ast);
+        if !problems.is_empty() {
+            for message in problems {
+                let transformer = transformer.name().to_owned();
+                Diagnostic::new(Error::InvalidTransformedDefinitions { transformer, message }).push_into(diagnostics);
+            }
+            return;
+        }
+
+        *source_files = transformed_source_files;
+        *reference_files = transformed_reference_files;
+    }
+}
+
+/// Encodes a 'transform' request for the provided Slice files, followed by the transformer's encoded arguments.
+fn encode_transform_request(
+    source_files: &[SliceFile],
+    reference_files: &[SliceFile],
+    context: &CompilationContext,
+    transformer: &Plugin,
+) -> Result<Vec<u8>, slice_codec::Error> {
+    let mut encoding_buffer: Vec<u8> = Vec::new();
+    let mut slice_encoder = Encoder::from(&mut encoding_buffer);
+
+    slice_encoder.encode("transform")?;
+    slice_encoder.encode(source_files)?;
+    slice_encoder.encode(reference_files)?;
+    slice_encoder.encode(context)?;
+    slice_encoder.encode(Arguments(transformer.args.clone()))?;
+
+    Ok(encoding_buffer)
+}
+
+/// Decodes a transformer's response. It consists of 3 sequences: the transformed source files, the transformed
+/// reference files, and any diagnostics the transformer reported.
+fn decode_transform_response(
+    response_payload: &[u8],
+) -> std::io::Result<(Vec<SliceFile>, Vec<SliceFile>, Vec<definition_types::Diagnostic>)> {
+    let mut slice_decoder = Decoder::from(response_payload);
+    let source_files = slice_decoder.decode()?;
+    let reference_files = slice_decoder.decode()?;
+    let transformer_diagnostics = slice_decoder.decode()?;
+    Ok((source_files, reference_files, transformer_diagnostics))
+}
+
+/// Checks that the provided Slice files are well-formed, and returns a message describing each problem with them.
+///
+/// Type IDs must be the keyword of a primitive type, the fully scoped identifier of a struct, enum, or custom type,
+/// or the index of an anonymous type (sequence, dictionary, or result) which appears earlier in the same file.
+/// Base interfaces must be the fully scoped identifier of an interface, and the enumerators used by constants and
+/// default values must be the fully scoped identifier of an enumerator.
+fn validate_transformed_files(source_files: &[SliceFile], reference_files: &[SliceFile], ast: &Ast) -> Vec<String> {
+    let files = source_files.iter().chain(reference_files).collect::<Vec<_>>();
+
+    // Collect the identifiers of all the types, interfaces, and enumerators that are defined in the files.
+    let mut types = BTreeSet::new();
+    let mut interfaces = BTreeSet::new();
+    let mut enumerators = BTreeSet::new();
+    for file in &files {
+        let module = &file.module_declaration.identifier;
+        for symbol in &file.contents {
+            let scoped_identifier = |identifier: &str| format!("{module}::{identifier}");
+            match symbol {
+                Symbol::Struct(v) => types.insert(scoped_identifier(&v.entity_info.identifier)),
+                Symbol::BasicEnum(v) => {
+                    let enum_identifier = scoped_identifier(&v.entity_info.identifier);
+                    for enumerator in &v.enumerators {
+                        enumerators.insert(format!("{enum_identifier}::{}", enumerator.entity_info.identifier));
+                    }
+                    types.insert(enum_identifier)
+                }
+                Symbol::VariantEnum(v) => types.insert(scoped_identifier(&v.entity_info.identifier)),
+                Symbol::CustomType(v) => types.insert(scoped_identifier(&v.entity_info.identifier)),
+                Symbol::Interface(v) => interfaces.insert(scoped_identifier(&v.entity_info.identifier)),
+                _ => false,
+            };
+        }
+    }
+
+    let mut problems = Vec::new();
+    for file in &files {
+        for (index, symbol) in file.contents.iter().enumerate() {
+            // Check that each enumerator used as a value resolves.
+            let mut check_value = |value: &ConstantValue| {
+                if let ConstantValue::Enumerator(enumerator) = value {
+                    if !enumerators.contains(enumerator) {
+                        let path = &file.path;
+                        problems.push(format!(
+                            "enumerator '{enumerator}' in '{path}' doesn't refer to any enumerator"
+                        ));
+                    }
+                }
+            };
+            match symbol {
+                Symbol::Struct(v) => v.fields.iter().for_each(|field| {
+                    field.default_value.iter().for_each(&mut check_value);
+                }),
+                Symbol::VariantEnum(v) => v.variants.iter().flat_map(|variant| &variant.fields).for_each(|field| {
+                    field.default_value.iter().for_each(&mut check_value);
+                }),
+                Symbol::Constant(v) => check_value(&v.value),
+                _ => {}
+            }
+
+            // Check that the type ID of each type reference resolves.
+            let mut check_type_ref = |type_ref: &TypeRef| {
+                if !is_type_id_resolvable(&type_ref.type_id, &file.contents[..index], &types, ast) {
+                    let (type_id, path) = (&type_ref.type_id, &file.path);
+                    problems.push(format!("type ID '{type_id}' in '{path}' doesn't refer to any type"));
+                }
+            };
+            match symbol {
+                Symbol::Struct(v) => v.fields.iter().for_each(|field| check_type_ref(&field.data_type)),
+                Symbol::VariantEnum(v) => v.variants.iter().flat_map(|variant| &variant.fields).for_each(|field| {
+                    check_type_ref(&field.data_type);
+                }),
+                Symbol::Interface(v) => {
+                    let operations = v.operations.iter();
+                    let members =
+                        operations.flat_map(|operation| operation.parameters.iter().chain(&operation.return_type));
+                    members.for_each(|field| check_type_ref(&field.data_type));
+                }
+                Symbol::TypeAlias(v) => check_type_ref(&v.underlying_type),
+                Symbol::Constant(v) => check_type_ref(&v.data_type),
+                Symbol::SequenceType(v) => check_type_ref(&v.element_type),
+                Symbol::DictionaryType(v) => {
+                    check_type_ref(&v.key_type);
+                    check_type_ref(&v.value_type);
+                }
+                Symbol::ResultType(v) => {
+                    check_type_ref(&v.success_type);
+                    check_type_ref(&v.failure_type);
+                }
+                Symbol::BasicEnum(_) | Symbol::CustomType(_) => {}
+            }
+
+            // Check that the underlying type of each enum is a primitive, and that each base interface resolves.
+            if let Symbol::BasicEnum(v) = symbol {
+                if ast.find_element::<Primitive>(&v.underlying).is_err() {
+                    let (underlying, path) = (&v.underlying, &file.path);
+                    problems.push(format!(
+                        "underlying type '{underlying}' in '{path}' isn't a primitive type"
+                    ));
+                }
+            }
+            if let Symbol::Interface(v) = symbol {
+                for base in v.bases.iter().filter(|base| !interfaces.contains(*base)) {
+                    let path = &file.path;
+                    problems.push(format!(
+                        "base interface '{base}' in '{path}' doesn't refer to any interface"
+                    ));
+                }
+            }
+        }
+    }
+    problems
+}
+
+/// Returns true if the provided type ID resolves to a type. Anonymous types are referenced by their index within the
+/// current file, and must appear before the symbol which references them (so `preceding_symbols` are all we check).
+fn is_type_id_resolvable(type_id: &str, preceding_symbols: &[Symbol], types: &BTreeSet<String>, ast: &Ast) -> bool {
+    if let Ok(index) = type_id.parse::<usize>() {
+        let symbol = preceding_symbols.get(index);
+        return matches!(
+            symbol,
+            Some(Symbol::SequenceType(_) | Symbol::DictionaryType(_) | Symbol::ResultType(_)),
+        );
+    }
+    types.contains(type_id) || ast.find_element::<Primitive>(type_id).is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::definition_types::DiagnosticLevel;
+    use crate::generation::{convert_slice_files, create_compilation_context};
+    use test_case::test_case;
+
+    /// Compiles the provided Slice, and returns the converted files, along with the AST they were compiled into.
+    /// Slice which is compiled from strings is converted into reference files, so there aren't any source files.
+    fn compile(slice: &str) -> (Vec<SliceFile>, Ast) {
+        let compilation_state = crate::compile_from_strings(&[slice], None);
+        assert!(!compilation_state.diagnostics.has_errors());
+        let (_, reference_files) = convert_slice_files(&compilation_state.files);
+        (reference_files, compilation_state.ast)
+    }
+
+    /// Compiles a Slice file whose contents are converted into the following symbols:
+    /// 0: the enum 'Color', 1: the interface 'Base', 2: the interface 'I' (which inherits from 'Base'),
+    /// 3: the constant 'Favorite', 4: a 'Sequence<int32>', 5: the struct 'S' (whose first field uses the sequence).
+    fn compile_test_file() -> (Vec<SliceFile>, Ast) {
+        let slice = "
+            module Test
+            enum Color : uint8 { Red }
+            interface Base {}
+            interface I : Base {}
+            const Favorite: Color = Color::Red
+            struct S { x: Sequence<int32>, c: Color = Color::Red }
+        ";
+        compile(slice)
+    }
+
+    /// Returns the struct 'S' from the files returned by [compile_test_file].
+    fn get_struct(files: &mut [SliceFile]) -> &mut definition_types::Struct {
+        match &mut files[0].contents[5] {
+            Symbol::Struct(v) => v,
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn unmodified_files_are_valid() {
+        // Arrange
+        let (files, ast) = compile_test_file();
+
+        // Act
+        let problems = validate_transformed_files(&[], &files, &ast);
+
+        // Assert
+        assert_eq!(problems, Vec::<String>::new());
+    }
+
+    #[test]
+    fn unknown_named_type_ids_are_reported() {
+        // Arrange
+        let (mut files, ast) = compile_test_file();
+        get_struct(&mut files).fields[0].data_type.type_id = "Test::Missing".to_owned();
+
+        // Act
+        let problems = validate_transformed_files(&[], &files, &ast);
+
+        // Assert
+        assert_eq!(problems, [
+            "type ID 'Test::Missing' in 'string-0' doesn't refer to any type"
+        ]);
+    }
+
+    #[test_case("5"; "forward")]
+    #[test_case("99"; "past_the_end")]
+    #[test_case("0"; "non_anonymous")]
+    fn anonymous_type_ids_must_refer_to_a_preceding_anonymous_type(type_id: &str) {
+        // Arrange
+        let (mut files, ast) = compile_test_file();
+        get_struct(&mut files).fields[0].data_type.type_id = type_id.to_owned();
+
+        // Act
+        let problems = validate_transformed_files(&[], &files, &ast);
+
+        // Assert
+        let expected = format!("type ID '{type_id}' in 'string-0' doesn't refer to any type");
+        assert_eq!(problems, [expected]);
+    }
+
+    #[test]
+    fn unknown_base_interfaces_are_reported() {
+        // Arrange
+        let (mut files, ast) = compile_test_file();
+        let Symbol::Interface(interface) = &mut files[0].contents[2] else { unreachable!() };
+        interface.bases = vec!["Test::Missing".to_owned()];
+
+        // Act
+        let problems = validate_transformed_files(&[], &files, &ast);
+
+        // Assert
+        assert_eq!(problems, [
+            "base interface 'Test::Missing' in 'string-0' doesn't refer to any interface"
+        ]);
+    }
+
+    #[test]
+    fn non_primitive_enum_underlying_types_are_reported() {
+        // Arrange
+        let (mut files, ast) = compile_test_file();
+        let Symbol::BasicEnum(basic_enum) = &mut files[0].contents[0] else { unreachable!() };
+        basic_enum.underlying = "Test::S".to_owned();
+
+        // Act
+        let problems = validate_transformed_files(&[], &files, &ast);
+
+        // Assert
+        assert_eq!(problems, [
+            "underlying type 'Test::S' in 'string-0' isn't a primitive type"
+        ]);
+    }
+
+    #[test]
+    fn unknown_enumerators_are_reported() {
+        // Arrange
+        let (mut files, ast) = compile_test_file();
+        let missing = || ConstantValue::Enumerator("Test::Color::Blue".to_owned());
+        let Symbol::Constant(constant) = &mut files[0].contents[3] else { unreachable!() };
+        constant.value = missing();
+        get_struct(&mut files).fields[1].default_value = Some(missing());
+
+        // Act
+        let problems = validate_transformed_files(&[], &files, &ast);
+
+        // Assert
+        let expected = "enumerator 'Test::Color::Blue' in 'string-0' doesn't refer to any enumerator";
+        assert_eq!(problems, [expected, expected]);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn transformer_errors_stop_the_remaining_transformers() {
+        use crate::utils::test_util::TempDir;
+        use std::os::unix::fs::PermissionsExt;
+
+        // Arrange
+        let (mut files, ast) = compile_test_file();
+        let original_files = serde_json::to_value(&files).unwrap();
+        let options = SliceOptions::default();
+        let context = create_compilation_context(&[], None, &options);
+        let dir = TempDir::new("transform-errors");
+
+        // The first transformer returns an empty file, along with an error. The second records that it was run.
+        let diagnostic = definition_types::Diagnostic {
+            level: DiagnosticLevel::Error,
+            message: "something went wrong".to_owned(),
+            source: None,
+        };
+        let mut response = Vec::new();
+        let mut encoder = Encoder::from(&mut response);
+        encoder.encode(&Vec::<SliceFile>::new()).unwrap();
+        encoder.encode(&Vec::<SliceFile>::new()).unwrap();
+        encoder.encode(&vec![diagnostic]).unwrap();
+        let response_path = dir.write_file("response.bin", response);
+        let marker_path = dir.join("ran");
+        let scripts = [
+            format!("#!/bin/sh\ncat > /dev/null\ncat '{}'\n", response_path.display()),
+            format!("#!/bin/sh\ncat > /dev/null\ntouch '{}'\n", marker_path.display()),
+        ];
+
+        // Both transformers are treated as having passed their handshake, since these scripts can't respond to it.
+        let mut handshake_cache = HandshakeCache::default();
+        let mut transformers = Vec::new();
+        for (i, script) in scripts.iter().enumerate() {
+            let path = dir.write_file(format!("transformer-{i}"), script);
+            std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755)).unwrap();
+            let path = path.display().to_string();
+            handshake_cache.insert(&path);
+            transformers.push(Plugin { path, args: Vec::new() });
+        }
+        let options = SliceOptions { transformers, ..options };
+        let mut diagnostics = Diagnostics::new();
+
+        // Act
+        let (mut sources, references) = (Vec::new(), &mut files);
+        run_transformers(
+            &mut sources,
+            references,
+            &context,
+            &ast,
+            &mut handshake_cache,
+            None,
+            &options,
+            &mut diagnostics,
+        );
+
+        // Assert
+        let diagnostics = diagnostics.into_inner();
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].message(), "something went wrong");
+        assert!(!marker_path.exists());
+        assert!(sources.is_empty());
+        assert_eq!(serde_json::to_value(&files).unwrap(), original_files);
+    }
+}
@@ -1,5 +1,9 @@
 // Copyright (c) ZeroC, Inc.
 
 pub mod file_util;
+pub mod plugin_util;
 pub mod ptr_util;
 pub mod string_util;
+
+#[cfg(test)]
+pub(crate) mod test_util;
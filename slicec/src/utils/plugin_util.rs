@@ -0,0 +1,148 @@
+// Copyright (c) ZeroC, Inc.
+
+use crate::diagnostics::{Diagnostic, Diagnostics, Error};
+use crate::slice_options::SliceOptions;
+use std::path::{Path, PathBuf};
+
+/// The prefix that's added to a code-generator's name to get the name of its executable.
+/// Ex: the code-generator named 'cs' is resolved to an executable named 'slicec-cs'.
+pub const PLUGIN_EXECUTABLE_PREFIX: &str = "slicec-";
+
+/// The environment variable that specifies additional directories to search for code-generators in.
+/// Like 'PATH', it holds a list of directories, separated by the platform's path separator.
+pub const PLUGIN_PATH_VARIABLE: &str = "SLICEC_PLUGIN_PATH";
+
+/// Resolves the paths of all the code-generators and transformers in the provided options, replacing any that were
+/// specified by name with the path of their executable. An error is reported for each one that can't be found,
+/// and it's removed from the options. Code-generators named in `in_process_generators` are left as-is,
+/// since they're run in-process instead of as plugins.
+///
+/// The manifest and generation cache identify code-generators by name, so an error is also reported for each
+/// code-generator that has the same name as a previous one (ex: 'a/slicec-cs' and 'b/slicec-cs').
+pub fn resolve_generators(options: &mut SliceOptions, in_process_generators: &[&str], diagnostics: &mut Diagnostics) {
+    let plugin_dirs = &options.plugin_dirs;
+    options.generators.retain_mut(|generator| {
+        if in_process_generators.contains(&generator.path.as_str()) {
+            return true;
+        }
+
+        match resolve_plugin_path(&generator.path, plugin_dirs, diagnostics) {
+            Some(path) => {
+                generator.path = path;
+                true
+            }
+            None => false,
+        }
+    });
+
+    for (i, generator) in options.generators.iter().enumerate() {
+        let previous_generators = &options.generators[..i];
+        if let Some(other) = previous_generators
+            .iter()
+            .find(|other| other.name() == generator.name())
+        {
+            Diagnostic::new(Error::DuplicateGeneratorName {
+                name: generator.name().to_owned(),
+                first_path: other.path.clone(),
+                second_path: generator.path.clone(),
+            })
+            .push_into(diagnostics);
+        }
+    }
+
+    options.transformers.retain_mut(|transformer| {
+        match resolve_plugin_path(&transformer.path, plugin_dirs, diagnostics) {
+            Some(path) => {
+                transformer.path = path;
+                true
+            }
+            None => false,
+        }
+    });
+}
+
+/// Resolves the provided plugin path to the path of an executable.
+///
+/// If the plugin path contains any directories (ex: './my-generator'), it's returned as-is. Otherwise it's treated
+/// as the name of a code-generator, and we search for an executable named 'slicec-<name>' in the provided plugin
+/// directories, then in the directories listed by 'SLICEC_PLUGIN_PATH', and finally in the directories on 'PATH'.
+/// If none is found, we search the same directories again for an executable named exactly '<name>', so that plugins
+/// without the 'slicec-' prefix can still be run by name. If the name already starts with 'slicec-', we only search
+/// for an executable with exactly that name. On unix, files without execute permissions are skipped.
+///
+/// If no executable can be found, this reports an error that lists every location which was searched,
+/// and returns `None`.
+pub fn resolve_plugin_path(plugin_path: &str, plugin_dirs: &[String], diagnostics: &mut Diagnostics) -> Option<String> {
+    if Path::new(plugin_path).components().count() != 1 {
+        return Some(plugin_path.to_owned());
+    }
+
+    let executables = match plugin_path.starts_with(PLUGIN_EXECUTABLE_PREFIX) {
+        true => vec![plugin_path.to_owned()],
+        false => vec![
+            format!("{PLUGIN_EXECUTABLE_PREFIX}{plugin_path}"),
+            plugin_path.to_owned(),
+        ],
+    };
+
+    let search_locations = get_search_locations(plugin_dirs);
+    for executable in &executables {
+        let file_name = format!("{executable}{}", std::env::consts::EXE_SUFFIX);
+        for (dir, _) in &search_locations {
+            let candidate = dir.join(&file_name);
+            if is_executable(&candidate) {
+                return Some(candidate.display().to_string());
+            }
+        }
+    }
+
+    // If we get here, no executable could be found, so we report an error, listing every location that was searched.
+    let error = Error::GeneratorNotFound {
+        name: plugin_path.to_owned(),
+        executables,
+    };
+    let note = match search_locations.is_empty() {
+        true => format!("no locations were searched; use '--plugin-dir' or '{PLUGIN_PATH_VARIABLE}' to add some"),
+        false => {
+            let locations = search_locations
+                .iter()
+                .map(|(dir, origin)| format!("    {} (from {origin})", dir.display()))
+                .collect::<Vec<_>>();
+            format!("searched the following locations:\n{}", locations.join("\n"))
+        }
+    };
+    Diagnostic::new(error).add_note(note, None).push_into(diagnostics);
+    None
+}
+
+/// Returns true if the provided path is a file that can be executed.
+/// On unix, this checks whether any of its execute permission bits are set.
+fn is_executable(path: &Path) -> bool {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let metadata = std::fs::metadata(path);
+        metadata.is_ok_and(|metadata| metadata.is_file() && metadata.permissions().mode() & 0o111 != 0)
+    }
+    #[cfg(not(unix))]
+    {
+        path.is_file()
+    }
+}
+
+/// Returns the directories that should be searched for code-generators (in order), along with where each came from.
+fn get_search_locations(plugin_dirs: &[String]) -> Vec<(PathBuf, String)> {
+    let mut search_locations = Vec::new();
+
+    for plugin_dir in plugin_dirs {
+        search_locations.push((PathBuf::from(plugin_dir), "'--plugin-dir'".to_owned()));
+    }
+    for variable in [PLUGIN_PATH_VARIABLE, "PATH"] {
+        if let Some(value) = std::env::var_os(variable) {
+            let dirs = std::env::split_paths(&value).filter(|dir| !dir.as_os_str().is_empty());
+            search_locations.extend(dirs.map(|dir| (dir, format!("'{variable}'"))));
+        }
+    }
+
+    search_locations
+}
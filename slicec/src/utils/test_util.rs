@@ -0,0 +1,46 @@
+// Copyright (c) ZeroC, Inc.
+
+//! This module contains helpers which are shared between the unit tests of different modules.
+//! It's also included by the integration tests' 'test_helpers' module, so it can only use the standard library.
+
+use std::path::{Path, PathBuf};
+
+/// A directory in the system's temporary directory, which is deleted (along with its contents) when it's dropped.
+/// It dereferences to its path, so it can be used anywhere a [`Path`] is expected.
+pub struct TempDir {
+    path: PathBuf,
+}
+
+impl TempDir {
+    /// Creates an empty directory with the provided name. If a directory with this name already exists (left over from
+    /// a previous test run that was interrupted), it's deleted first. Names must be unique within each test binary.
+    pub fn new(name: &str) -> Self {
+        let path = std::env::temp_dir().join(format!("slicec-{name}-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&path);
+        std::fs::create_dir_all(&path).unwrap();
+        TempDir { path }
+    }
+
+    /// Writes a file at the provided path (relative to this directory), creating any missing parent directories.
+    /// This returns the full path of the written file.
+    pub fn write_file(&self, relative_path: impl AsRef<Path>, contents: impl AsRef<[u8]>) -> PathBuf {
+        let path = self.path.join(relative_path);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, contents).unwrap();
+        path
+    }
+}
+
+impl std::ops::Deref for TempDir {
+    type Target = Path;
+
+    fn deref(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.path);
+    }
+}
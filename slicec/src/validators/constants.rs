@@ -0,0 +1,95 @@
+// Copyright (c) ZeroC, Inc.
+
+use crate::diagnostics::{Diagnostic, Diagnostics, Error};
+use crate::grammar::*;
+
+pub fn validate_constant(constant: &Constant, diagnostics: &mut Diagnostics) {
+    let constant_identifier = constant.identifier().to_owned();
+    let diagnostic = match check_value(&constant.data_type, &constant.value) {
+        ValueCheck::Valid => return,
+        ValueCheck::UnsupportedType => {
+            let error = Error::ConstantTypeNotSupported {
+                constant_identifier,
+                kind: constant.data_type.type_string(),
+            };
+            let mut diagnostic = Diagnostic::new(error).set_span(constant.span());
+            if constant.data_type.is_optional {
+                diagnostic = diagnostic.add_note(
+                    "try removing the trailing `?` modifier from its type",
+                    Some(constant.data_type.span()),
+                );
+            }
+            diagnostic
+        }
+        ValueCheck::TypeMismatch => {
+            let error = Error::ConstantValueTypeMismatch {
+                constant_identifier,
+                type_string: constant.data_type.type_string(),
+                value: constant.value.to_string(),
+            };
+            Diagnostic::new(error).set_span(constant.value.span())
+        }
+        ValueCheck::OutOfBounds { value, min, max } => {
+            let error = Error::ConstantValueOutOfBounds {
+                constant_identifier,
+                value,
+                min,
+                max,
+            };
+            Diagnostic::new(error).set_span(constant.value.span())
+        }
+    };
+    diagnostic.push_into(diagnostics);
+}
+
+/// The result of checking a value against the type that it's used with.
+pub(super) enum ValueCheck {
+    Valid,
+    /// Values can only be given for non-optional integral types, bools, strings, and enums without fields.
+    UnsupportedType,
+    /// The value isn't a valid value for the type (ex: a string literal for an integral type).
+    TypeMismatch,
+    /// The value is an integer outside the bounds of the (integral) type.
+    OutOfBounds {
+        value: i128,
+        min: i128,
+        max: i128,
+    },
+}
+
+/// Checks whether the provided value is a valid value of the provided type.
+/// This is used for checking the values of constants, and the default values of fields.
+pub(super) fn check_value(data_type: &TypeRef, value: &ConstantValue) -> ValueCheck {
+    if data_type.is_optional {
+        return ValueCheck::UnsupportedType;
+    }
+
+    match (data_type.concrete_type(), value) {
+        (Types::Primitive(Primitive::Bool), _) => match value.as_bool() {
+            Some(_) => ValueCheck::Valid,
+            None => ValueCheck::TypeMismatch,
+        },
+        (Types::Primitive(Primitive::String), ConstantValue::String(..)) => ValueCheck::Valid,
+        (Types::Primitive(primitive), ConstantValue::Integer(integer)) if primitive.is_integral() => {
+            let (min, max) = primitive.numeric_bounds().unwrap(); // All integral types have bounds.
+            match integer.value < min || integer.value > max {
+                true => ValueCheck::OutOfBounds {
+                    value: integer.value,
+                    min,
+                    max,
+                },
+                false => ValueCheck::Valid,
+            }
+        }
+        (Types::Primitive(primitive), _) if primitive.is_integral() || *primitive == Primitive::String => {
+            ValueCheck::TypeMismatch
+        }
+        (Types::Enum(enum_def), _) if enum_def.enumerators().iter().all(|e| e.fields.is_none()) => {
+            match value.as_enumerator(enum_def) {
+                Some(_) => ValueCheck::Valid,
+                None => ValueCheck::TypeMismatch,
+            }
+        }
+        _ => ValueCheck::UnsupportedType,
+    }
+}
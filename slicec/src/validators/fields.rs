@@ -0,0 +1,37 @@
+// Copyright (c) ZeroC, Inc.
+
+use super::constants::{check_value, ValueCheck};
+use crate::diagnostics::{Diagnostic, Diagnostics, Error};
+use crate::grammar::*;
+
+pub fn validate_field(field: &Field, diagnostics: &mut Diagnostics) {
+    if let Some(default_value) = &field.default_value {
+        default_value_matches_type(field, default_value, diagnostics);
+    }
+}
+
+/// Validate that the field's default value is a valid value for the field's type.
+fn default_value_matches_type(field: &Field, default_value: &ConstantValue, diagnostics: &mut Diagnostics) {
+    let field_identifier = field.identifier().to_owned();
+    let error = match check_value(&field.data_type, default_value) {
+        ValueCheck::Valid => return,
+        ValueCheck::UnsupportedType => Error::DefaultValueTypeNotSupported {
+            field_identifier,
+            kind: field.data_type.type_string(),
+        },
+        ValueCheck::TypeMismatch => Error::DefaultValueTypeMismatch {
+            field_identifier,
+            type_string: field.data_type.type_string(),
+            value: default_value.to_string(),
+        },
+        ValueCheck::OutOfBounds { value, min, max } => Error::DefaultValueOutOfBounds {
+            field_identifier,
+            value,
+            min,
+            max,
+        },
+    };
+    Diagnostic::new(error)
+        .set_span(default_value.span())
+        .push_into(diagnostics);
+}
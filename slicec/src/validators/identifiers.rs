@@ -84,6 +84,9 @@ impl<'a> RedefinitionChecker<'a> {
                 Entities::TypeAlias(type_alias) => {
                     self.check_if_redefined(type_alias, &mut seen_definitions);
                 }
+                Entities::Constant(constant) => {
+                    self.check_if_redefined(constant, &mut seen_definitions);
+                }
 
                 // No need to check `Field`, `Enumerator`, `Operation`, or `Parameter`; We just check their containers.
                 Entities::Field(_) | Entities::Enumerator(_) | Entities::Operation(_) | Entities::Parameter(_) => {}
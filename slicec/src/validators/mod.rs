@@ -2,9 +2,11 @@
 
 mod attribute;
 mod comments;
+mod constants;
 mod cycle_detection;
 mod dictionary;
 mod enums;
+mod fields;
 mod identifiers;
 mod members;
 mod operations;
@@ -20,8 +22,10 @@ use crate::visitor::Visitor;
 
 use attribute::validate_attributes;
 use comments::validate_common_doc_comments;
+use constants::validate_constant;
 use dictionary::validate_dictionary;
 use enums::validate_enum;
+use fields::validate_field;
 use identifiers::validate_inherited_identifiers;
 use members::validate_members;
 use operations::validate_operation;
@@ -128,6 +132,8 @@ impl<'a> Visitor for ValidatorVisitor<'a> {
     fn visit_field(&mut self, field: &Field) {
         validate_common_doc_comments(field, self.diagnostics);
         validate_attributes(field, self.diagnostics);
+
+        validate_field(field, self.diagnostics);
     }
 
     fn visit_type_alias(&mut self, type_alias: &TypeAlias) {
@@ -137,6 +143,13 @@ impl<'a> Visitor for ValidatorVisitor<'a> {
         validate_type_alias(type_alias, self.diagnostics);
     }
 
+    fn visit_constant(&mut self, constant: &Constant) {
+        validate_common_doc_comments(constant, self.diagnostics);
+        validate_attributes(constant, self.diagnostics);
+
+        validate_constant(constant, self.diagnostics);
+    }
+
     fn visit_type_ref(&mut self, type_ref: &TypeRef) {
         validate_attributes(type_ref, self.diagnostics);
 
@@ -66,6 +66,11 @@ pub trait Visitor {
     /// This shouldn't be called by users. To visit a type alias, use `[TypeAlias::visit_with]`.
     fn visit_type_alias(&mut self, type_alias: &TypeAlias) {}
 
+    /// This function is called by the visitor when it visits a [Constant],
+    ///
+    /// This shouldn't be called by users. To visit a constant, use `[Constant::visit_with]`.
+    fn visit_constant(&mut self, constant: &Constant) {}
+
     /// This function is called by the visitor when it visits a [Field],
     ///
     /// This shouldn't be called by users. To visit a field, use `[Field::visit_with]`.
@@ -107,6 +112,7 @@ impl SliceFile {
                 Definition::Enum(enum_def) => enum_def.borrow().visit_with(visitor),
                 Definition::CustomType(custom_type) => custom_type.borrow().visit_with(visitor),
                 Definition::TypeAlias(type_alias) => type_alias.borrow().visit_with(visitor),
+                Definition::Constant(constant) => constant.borrow().visit_with(visitor),
             }
         }
     }
@@ -195,6 +201,16 @@ impl TypeAlias {
     }
 }
 
+impl Constant {
+    /// Visits the [Constant] with the provided `visitor`.
+    ///
+    /// This function delegates to `visitor.visit_constant`.
+    pub fn visit_with(&self, visitor: &mut impl Visitor) {
+        visitor.visit_constant(self);
+        self.data_type.visit_with(visitor);
+    }
+}
+
 impl Field {
     /// Visits the [Field] with the provided `visitor`.
     ///
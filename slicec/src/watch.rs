@@ -0,0 +1,193 @@
+// Copyright (c) ZeroC, Inc.
+
+//! This module implements the file watching used by '--watch'. Instead of relying on platform-specific services,
+//! it periodically takes a snapshot of the files being watched, and compares it against the previous snapshot.
+
+use crate::config_file;
+use crate::diagnostics::Diagnostics;
+use crate::slice_options::SliceOptions;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+/// How long to wait between checking the watched files for changes.
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// The state of all the files being watched at a single point in time.
+/// Each watched path is mapped to its last modification time and size, or `None` if it didn't exist.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Snapshot(BTreeMap<PathBuf, Option<(SystemTime, u64)>>);
+
+impl Snapshot {
+    /// Takes a snapshot of the files that the provided options would compile: the source files, the references
+    /// (including every Slice file in referenced directories), and the configuration file (if there is one).
+    pub fn take(options: &SliceOptions) -> Self {
+        let mut snapshot = Snapshot(BTreeMap::new());
+
+        let config_file = options.config.iter();
+        for path in config_file.chain(&options.sources).chain(&options.references) {
+            snapshot.add_path(Path::new(path));
+        }
+        snapshot
+    }
+
+    /// Returns the number of files in this snapshot.
+    pub fn file_count(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns the path of a file which changed between the provided snapshot and this one, if any file changed.
+    /// Files which were added or removed count as having changed.
+    pub fn find_change(&self, previous: &Snapshot) -> Option<PathBuf> {
+        let changed = self.0.iter().find(|(path, state)| previous.0.get(*path) != Some(state));
+        let removed = || previous.0.keys().find(|path| !self.0.contains_key(*path));
+        changed.map(|(path, _)| path).or_else(removed).cloned()
+    }
+
+    /// Adds the provided path to this snapshot. If it's a directory, every Slice file within it is added instead.
+    fn add_path(&mut self, path: &Path) {
+        if path.is_dir() {
+            let Ok(entries) = std::fs::read_dir(path) else {
+                return;
+            };
+            for entry in entries.filter_map(Result::ok) {
+                let entry_path = entry.path();
+                if entry_path.is_dir() || entry_path.extension().is_some_and(|ext| ext == "slice") {
+                    self.add_path(&entry_path);
+                }
+            }
+        } else {
+            let metadata = std::fs::metadata(path).ok();
+            let state = metadata.and_then(|metadata| Some((metadata.modified().ok()?, metadata.len())));
+            self.0.insert(path.to_owned(), state);
+        }
+    }
+}
+
+/// Blocks until a file in the provided snapshot changes, or until a file is added to a watched directory.
+/// This returns the path of the changed file.
+///
+/// The provided options must be the options from the command line, before the configuration file was applied to them.
+/// The configuration file is re-applied before each check, so that files which newly match its glob patterns are
+/// noticed.
+pub fn wait_for_change(options: &SliceOptions, previous: &Snapshot) -> PathBuf {
+    loop {
+        std::thread::sleep(POLL_INTERVAL);
+
+        // Any errors in the configuration file are reported when slicec reruns, so we can ignore them here.
+        let mut current_options = options.clone();
+        config_file::apply_config_file(&mut current_options, &mut Diagnostics::new());
+        if let Some(path) = Snapshot::take(&current_options).find_change(previous) {
+            return path;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_util::TempDir;
+
+    /// Creates a directory containing a Slice file named 'a.slice', and returns options which reference it.
+    fn create_watched_dir(name: &str) -> (TempDir, SliceOptions) {
+        let dir = TempDir::new(&format!("watch-{name}"));
+        dir.write_file("a.slice", "module A");
+        let options = SliceOptions {
+            references: vec![dir.display().to_string()],
+            ..SliceOptions::default()
+        };
+        (dir, options)
+    }
+
+    #[test]
+    fn unchanged_files_are_not_reported() {
+        // Arrange
+        let (_dir, options) = create_watched_dir("unchanged");
+        let previous = Snapshot::take(&options);
+
+        // Act
+        let change = Snapshot::take(&options).find_change(&previous);
+
+        // Assert
+        assert_eq!(change, None);
+        assert_eq!(previous.file_count(), 1);
+    }
+
+    #[test]
+    fn modified_files_are_reported() {
+        // Arrange
+        let (dir, options) = create_watched_dir("modified");
+        let previous = Snapshot::take(&options);
+
+        // Act
+        let path = dir.write_file("a.slice", "module A\nstruct S {}");
+        let change = Snapshot::take(&options).find_change(&previous);
+
+        // Assert
+        assert_eq!(change, Some(path));
+    }
+
+    #[test]
+    fn added_files_are_reported() {
+        // Arrange
+        let (dir, options) = create_watched_dir("added");
+        let previous = Snapshot::take(&options);
+
+        // Act
+        let path = dir.write_file("nested/b.slice", "module B");
+        let change = Snapshot::take(&options).find_change(&previous);
+
+        // Assert
+        assert_eq!(change, Some(path));
+    }
+
+    #[test]
+    fn removed_files_are_reported() {
+        // Arrange
+        let (dir, options) = create_watched_dir("removed");
+        let previous = Snapshot::take(&options);
+
+        // Act
+        std::fs::remove_file(dir.join("a.slice")).unwrap();
+        let change = Snapshot::take(&options).find_change(&previous);
+
+        // Assert
+        assert_eq!(change, Some(dir.join("a.slice")));
+    }
+
+    #[test]
+    fn non_slice_files_in_watched_directories_are_ignored() {
+        // Arrange
+        let (dir, options) = create_watched_dir("ignored");
+        let previous = Snapshot::take(&options);
+
+        // Act
+        dir.write_file("notes.txt", "not a Slice file");
+        let change = Snapshot::take(&options).find_change(&previous);
+
+        // Assert
+        assert_eq!(change, None);
+    }
+
+    #[test]
+    fn files_which_newly_match_config_patterns_are_reported() {
+        // Arrange
+        let dir = TempDir::new("watch-config-patterns");
+        let config_path = dir.write_file("slice.toml", "sources = [\"*.slice\"]");
+        dir.write_file("a.slice", "module A");
+        let options = SliceOptions {
+            config: Some(config_path.display().to_string()),
+            ..SliceOptions::default()
+        };
+        let mut applied_options = options.clone();
+        config_file::apply_config_file(&mut applied_options, &mut Diagnostics::new());
+        let previous = Snapshot::take(&applied_options);
+
+        // Act
+        let path = dir.write_file("b.slice", "module B");
+        let change = wait_for_change(&options, &previous);
+
+        // Assert
+        assert_eq!(change, path);
+    }
+}
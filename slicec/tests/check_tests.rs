@@ -0,0 +1,106 @@
+// Copyright (c) ZeroC, Inc.
+
+mod test_helpers;
+
+use test_helpers::{create_generated_dir, read_dir_contents, run_slicec};
+
+#[test]
+fn check_passes_if_all_files_are_up_to_date() {
+    // Arrange
+    let dir = create_generated_dir("check-up-to-date");
+
+    // Act
+    let output = run_slicec(&dir, &[
+        "test.slice",
+        "-G",
+        "json,path=a.json",
+        "-O",
+        "output",
+        "--check",
+    ]);
+
+    // Assert
+    assert!(output.status.success());
+    assert!(output.stderr.is_empty());
+}
+
+#[test]
+fn check_reports_out_of_date_files_with_a_diff() {
+    // Arrange
+    let dir = create_generated_dir("check-out-of-date");
+    dir.write_file("output/a.json", "{}\n");
+    let contents = read_dir_contents(&dir.join("output"));
+    let args = [
+        "test.slice",
+        "-G",
+        "json,path=a.json",
+        "-O",
+        "output",
+        "--check",
+        "--diff",
+    ];
+
+    // Act
+    let output = run_slicec(&dir, &args);
+
+    // Assert
+    assert_eq!(output.status.code(), Some(1));
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.starts_with("error [E037]: generated file 'output/a.json' is out of date\n"));
+    assert!(stderr.contains("note: differences between the file on disk and the generated file:\n"));
+    assert!(stderr.contains("--- output/a.json\n+++ output/a.json\n"));
+    assert!(stderr.contains("\n-{}\n+{\n"));
+    assert_eq!(read_dir_contents(&dir.join("output")), contents);
+}
+
+#[test]
+fn check_reports_missing_files() {
+    // Arrange
+    let dir = create_generated_dir("check-missing");
+    std::fs::remove_file(dir.join("output/a.json")).unwrap();
+
+    // Act
+    let output = run_slicec(&dir, &[
+        "test.slice",
+        "-G",
+        "json,path=a.json",
+        "-O",
+        "output",
+        "--check",
+    ]);
+
+    // Assert
+    assert_eq!(output.status.code(), Some(1));
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert_eq!(stderr, "error [E037]: generated file 'output/a.json' is missing\n");
+    assert!(!dir.join("output/a.json").exists());
+}
+
+#[test]
+fn check_reports_stale_files() {
+    // Arrange
+    let dir = create_generated_dir("check-stale");
+    let contents = read_dir_contents(&dir.join("output"));
+
+    // Act
+    let output = run_slicec(&dir, &[
+        "test.slice",
+        "-G",
+        "json,path=b.json",
+        "-O",
+        "output",
+        "--check",
+    ]);
+
+    // Assert
+    assert_eq!(output.status.code(), Some(1));
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert_eq!(
+        stderr,
+        concat!(
+            "error [E037]: generated file 'output/b.json' is missing\n",
+            "error [E038]: generated file 'output/a.json' is no longer generated, and should be removed\n",
+        )
+    );
+    assert_eq!(read_dir_contents(&dir.join("output")), contents);
+}
@@ -4,7 +4,7 @@ use std::error::Error;
 
 use clap::error::ErrorKind;
 use clap::Parser;
-use slicec::slice_options::SliceOptions;
+use slicec::slice_options::{SliceOptions, SlicecCommand};
 
 use test_case::test_case;
 
@@ -28,6 +28,32 @@ fn plugins_can_have_no_arguments(value: &str) {
     assert!(generator_plugin.args.is_empty());
 }
 
+#[test]
+fn transformers_are_parsed_in_order() {
+    // Arrange
+    let input = [
+        "",
+        "--transform",
+        "first,key=value",
+        "--transform",
+        "second",
+        "-G",
+        "cs",
+    ];
+
+    // Act
+    let result = SliceOptions::try_parse_from(input);
+
+    // Assert
+    let parsed_options = result.unwrap();
+    let transformers = &parsed_options.transformers;
+    assert_eq!(transformers.len(), 2);
+    assert_eq!(transformers[0].path, "first");
+    assert_eq!(transformers[0].args, [("key".to_owned(), "value".to_owned())]);
+    assert_eq!(transformers[1].path, "second");
+    assert_eq!(parsed_options.generators.len(), 1);
+}
+
 #[test]
 fn equals_sign_has_no_special_meaning_in_plugin_path() {
     // Arrange
@@ -245,3 +271,154 @@ fn double_equals_is_disallowed() {
         "'=' can only appear once per argument (for a literal '=' character, use '\\=')",
     );
 }
+
+#[test]
+fn diff_requires_check() {
+    // Arrange
+    let input = ["", "--diff"];
+
+    // Act
+    let result = SliceOptions::try_parse_from(input);
+
+    // Assert
+    let parsing_error = result.unwrap_err();
+    assert_eq!(parsing_error.kind(), ErrorKind::MissingRequiredArgument);
+}
+
+#[test]
+fn check_conflicts_with_dry_run() {
+    // Arrange
+    let input = ["", "--check", "--dry-run"];
+
+    // Act
+    let result = SliceOptions::try_parse_from(input);
+
+    // Assert
+    let parsing_error = result.unwrap_err();
+    assert_eq!(parsing_error.kind(), ErrorKind::ArgumentConflict);
+}
+
+#[test]
+fn clean_conflicts_with_check() {
+    // Arrange
+    let input = ["", "--clean", "--check"];
+
+    // Act
+    let result = SliceOptions::try_parse_from(input);
+
+    // Assert
+    let parsing_error = result.unwrap_err();
+    assert_eq!(parsing_error.kind(), ErrorKind::ArgumentConflict);
+}
+
+#[test]
+fn watch_conflicts_with_clean() {
+    // Arrange
+    let input = ["", "--watch", "--clean"];
+
+    // Act
+    let result = SliceOptions::try_parse_from(input);
+
+    // Assert
+    let parsing_error = result.unwrap_err();
+    assert_eq!(parsing_error.kind(), ErrorKind::ArgumentConflict);
+}
+
+#[test]
+fn output_archive_conflicts_with_output_dir() {
+    // Arrange
+    let input = ["", "--output-archive", "generated.tar", "-O", "generated"];
+
+    // Act
+    let result = SliceOptions::try_parse_from(input);
+
+    // Assert
+    let parsing_error = result.unwrap_err();
+    assert_eq!(parsing_error.kind(), ErrorKind::ArgumentConflict);
+}
+
+#[test]
+fn no_cache_conflicts_with_cache_dir() {
+    // Arrange
+    let input = ["", "--no-cache", "--cache-dir", "cache"];
+
+    // Act
+    let result = SliceOptions::try_parse_from(input);
+
+    // Assert
+    let parsing_error = result.unwrap_err();
+    assert_eq!(parsing_error.kind(), ErrorKind::ArgumentConflict);
+}
+
+#[test]
+fn replay_request_subcommand_is_parsed() {
+    // Arrange
+    let input = ["", "replay-request", "request.bin", "--generator", "/path/to/generator"];
+
+    // Act
+    let options = SliceOptions::try_parse_from(input).unwrap();
+
+    // Assert
+    let Some(SlicecCommand::ReplayRequest { request_file, generator }) = options.command else {
+        panic!("expected a 'replay-request' command");
+    };
+    assert_eq!(request_file, "request.bin");
+    assert_eq!(generator, "/path/to/generator");
+    assert!(options.sources.is_empty());
+}
+
+#[test]
+fn replay_request_requires_a_generator() {
+    // Arrange
+    let input = ["", "replay-request", "request.bin"];
+
+    // Act
+    let result = SliceOptions::try_parse_from(input);
+
+    // Assert
+    let parsing_error = result.unwrap_err();
+    assert_eq!(parsing_error.kind(), ErrorKind::MissingRequiredArgument);
+}
+
+#[test]
+fn test_generator_subcommand_is_parsed() {
+    // Arrange
+    let input = [
+        "",
+        "test-generator",
+        "tests/golden",
+        "-G",
+        "my-generator,key=value",
+        "--bless",
+    ];
+
+    // Act
+    let options = SliceOptions::try_parse_from(input).unwrap();
+
+    // Assert
+    let Some(SlicecCommand::TestGenerator {
+        test_dir,
+        generator,
+        bless,
+    }) = options.command
+    else {
+        panic!("expected a 'test-generator' command");
+    };
+    assert_eq!(test_dir, "tests/golden");
+    assert_eq!(generator.path, "my-generator");
+    assert_eq!(generator.args, [("key".to_owned(), "value".to_owned())]);
+    assert!(bless);
+}
+
+#[test]
+fn jobs_must_be_positive() {
+    // Arrange
+    let input = ["", "-j", "0"];
+
+    // Act
+    let result = SliceOptions::try_parse_from(input);
+
+    // Assert
+    let parsing_error = result.unwrap_err();
+    assert_eq!(parsing_error.kind(), ErrorKind::ValueValidation);
+}
@@ -0,0 +1,187 @@
+// Copyright (c) ZeroC, Inc.
+
+mod test_helpers;
+
+use clap::Parser;
+use slicec::config_file::apply_config_file;
+use slicec::diagnostics::{Diagnostics, Error};
+use slicec::slice_options::SliceOptions;
+use std::path::Path;
+use test_helpers::{run_slicec, TempDir};
+
+/// Creates an empty temporary directory with the provided name, containing a 'slice.toml' file with the provided
+/// contents, and a 'slice' directory containing 2 empty Slice files: 'a.slice' and 'b.slice'.
+/// The directory is deleted once it's dropped.
+fn create_config_dir(name: &str, config: &str) -> TempDir {
+    let dir = TempDir::new(&format!("config-{name}"));
+    dir.write_file("slice.toml", config);
+    dir.write_file("slice/a.slice", "");
+    dir.write_file("slice/b.slice", "");
+    dir
+}
+
+/// Parses the provided command line arguments into slice options, which load the 'slice.toml' file in the provided
+/// directory.
+fn create_options(dir: &Path, args: &[&str]) -> SliceOptions {
+    let config = dir.join("slice.toml").display().to_string();
+    let input = ["", "--config", &config].into_iter().chain(args.iter().copied());
+    SliceOptions::parse_from(input)
+}
+
+#[test]
+fn options_are_loaded_from_config_file() {
+    // Arrange
+    let config = r#"
+        sources = ["slice/*.slice"]
+        references = ["slice"]
+        defined-symbols = ["FOO"]
+        output-dir = "generated"
+
+        [lints]
+        allow = ["Deprecated"]
+        deny = ["brokendoclink"]
+
+        [[generators]]
+        name = "cs"
+        args = { rpc = "icerpc" }
+    "#;
+    let dir = create_config_dir("loaded", config);
+    let mut options = create_options(&dir, &[]);
+    let mut diagnostics = Diagnostics::new();
+
+    // Act
+    let config_file = apply_config_file(&mut options, &mut diagnostics);
+
+    // Assert
+    assert!(config_file.is_some());
+    assert!(diagnostics.is_empty());
+
+    let relative_path = |path: &str| dir.join(path).display().to_string();
+    assert_eq!(options.sources, [
+        relative_path("slice/a.slice"),
+        relative_path("slice/b.slice")
+    ]);
+    assert_eq!(options.references, [relative_path("slice")]);
+    assert_eq!(options.defined_symbols, ["FOO"]);
+    assert_eq!(options.output_dir, Some(relative_path("generated")));
+    assert_eq!(options.allowed_lints, ["Deprecated"]);
+    assert_eq!(options.denied_lints, ["BrokenDocLink"]);
+
+    assert_eq!(options.generators.len(), 1);
+    assert_eq!(options.generators[0].path, "cs");
+    assert_eq!(options.generators[0].args, [("rpc".to_owned(), "icerpc".to_owned())]);
+}
+
+#[test]
+fn command_line_options_take_precedence() {
+    // Arrange
+    let config = r#"
+        sources = ["slice/*.slice"]
+        defined-symbols = ["FOO"]
+        output-dir = "generated"
+
+        [lints]
+        allow = ["Deprecated"]
+        deny = ["BrokenDocLink"]
+
+        [[generators]]
+        name = "cs"
+    "#;
+    let dir = create_config_dir("precedence", config);
+    let args = [
+        "other.slice",
+        "-D",
+        "BAR",
+        "-O",
+        "out",
+        "-G",
+        "json",
+        "--deny",
+        "Deprecated",
+    ];
+    let mut options = create_options(&dir, &args);
+    let mut diagnostics = Diagnostics::new();
+
+    // Act
+    apply_config_file(&mut options, &mut diagnostics);
+
+    // Assert
+    assert!(diagnostics.is_empty());
+    assert_eq!(options.sources, ["other.slice"]);
+    assert_eq!(options.defined_symbols, ["FOO", "BAR"]);
+    assert_eq!(options.output_dir.as_deref(), Some("out"));
+    assert_eq!(options.generators.len(), 1);
+    assert_eq!(options.generators[0].path, "json");
+    assert!(options.allowed_lints.is_empty());
+    assert_eq!(options.denied_lints, ["BrokenDocLink", "Deprecated"]);
+}
+
+#[test]
+fn errors_point_into_config_file() {
+    // Arrange
+    let config = "sources = [\"slice/*.slice\"]\n\n[lints]\nallow = [\"Fake\"]\n";
+    let dir = create_config_dir("errors", config);
+    let mut options = create_options(&dir, &[]);
+    let mut diagnostics = Diagnostics::new();
+
+    // Act
+    let config_file = apply_config_file(&mut options, &mut diagnostics);
+
+    // Assert
+    let config_file = config_file.unwrap();
+    let diagnostics = diagnostics.into_inner();
+    assert_eq!(diagnostics.len(), 1);
+
+    let expected = Error::InvalidConfigFile {
+        message: "unknown lint 'Fake'".to_owned(),
+    };
+    assert_eq!(diagnostics[0].message(), expected.message());
+
+    let span = diagnostics[0].span().unwrap();
+    assert_eq!(span.file, config_file.relative_path);
+    assert_eq!((span.start.row, span.start.col), (4, 10));
+    assert_eq!((span.end.row, span.end.col), (4, 16));
+}
+
+#[test]
+fn syntax_errors_point_into_config_file() {
+    // Arrange
+    let dir = create_config_dir("syntax-errors", "sources = [\"slice/*.slice\"\noutput-dir = 5\n");
+    let mut options = create_options(&dir, &[]);
+    let mut diagnostics = Diagnostics::new();
+
+    // Act
+    apply_config_file(&mut options, &mut diagnostics);
+
+    // Assert
+    let diagnostics = diagnostics.into_inner();
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].code(), "E042");
+    assert_eq!(diagnostics[0].span().unwrap().start.row, 2);
+}
+
+#[test]
+fn config_file_in_the_current_directory_is_loaded() {
+    // Arrange
+    let dir = create_config_dir("discovered", "sources = [\"slice/*.slice\"]\n\"unknown\" = 1");
+
+    // Act
+    let output = run_slicec(&dir, &[]);
+
+    // Assert
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("unknown"));
+}
+
+#[test]
+fn config_files_in_parent_directories_are_not_loaded() {
+    // Arrange
+    let dir = create_config_dir("not-discovered", "\"unknown\" = 1");
+    dir.write_file("slice/a.slice", "module Test");
+
+    // Act
+    let output = run_slicec(&dir.join("slice"), &["a.slice"]);
+
+    // Assert
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+}
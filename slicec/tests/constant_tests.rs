@@ -0,0 +1,219 @@
+// Copyright (c) ZeroC, Inc.
+
+mod test_helpers;
+
+mod constants {
+
+    use crate::test_helpers::*;
+    use slicec::definition_types::{self, ConstantValue as ConvertedValue, SliceFile, Symbol};
+    use slicec::diagnostics::{Diagnostic, Error};
+    use slicec::grammar::*;
+    use test_case::test_case;
+
+    #[test_case("uint32", "500"; "integers")]
+    #[test_case("int8", "-128"; "negative integers")]
+    #[test_case("varuint62", "0x10"; "hex integers")]
+    #[test_case("bool", "false"; "bools")]
+    #[test_case("string", "\"hello\""; "strings")]
+    #[test_case("E", "B"; "enumerators")]
+    #[test_case("E", "E::B"; "qualified enumerators")]
+    #[test_case("E", "::Test::E::B"; "globally qualified enumerators")]
+    #[test_case("A", "B"; "enumerators through type aliases")]
+    fn can_have_constant_of(type_string: &str, value: &str) {
+        // Arrange
+        let slice = format!(
+            "
+                module Test
+                enum E : uint8 {{ A, B }}
+                typealias A = E
+                const C: {type_string} = {value}
+            "
+        );
+
+        // Act/Assert
+        assert_parses(slice);
+    }
+
+    #[test]
+    fn is_resolvable() {
+        // Arrange
+        let slice = "
+            module Test
+
+            /// The maximum number of items in a page.
+            const MaxPageSize: uint32 = 500
+        ";
+
+        // Act
+        let ast = parse_for_ast(slice);
+
+        // Assert
+        let constant = ast.find_element::<Constant>("Test::MaxPageSize").unwrap();
+        assert_eq!(constant.identifier(), "MaxPageSize");
+        assert!(constant.comment().is_some());
+        assert!(matches!(
+            constant.data_type.concrete_type(),
+            Types::Primitive(Primitive::UInt32),
+        ));
+        assert!(matches!(&constant.value, ConstantValue::Integer(integer) if integer.value == 500));
+    }
+
+    #[test]
+    fn enumerator_values_are_resolved() {
+        // Arrange
+        let slice = "
+            module Test
+            enum E : int32 { A = 3, B = 7 }
+            const C: E = E::B
+        ";
+
+        // Act
+        let ast = parse_for_ast(slice);
+
+        // Assert
+        let constant = ast.find_element::<Constant>("Test::C").unwrap();
+        let enumerator = constant.enumerator().unwrap();
+        assert_eq!(enumerator.identifier(), "B");
+        assert_eq!(enumerator.value(), 7);
+    }
+
+    #[test_case("uint8", "256", 0, u8::MAX as i128; "uint8")]
+    #[test_case("int16", "-32769", i16::MIN as i128, i16::MAX as i128; "int16")]
+    #[test_case("varuint32", "-1", 0, u32::MAX as i128; "varuint32")]
+    fn integer_values_must_be_within_bounds(type_string: &str, value: &str, min: i128, max: i128) {
+        // Arrange
+        let slice = format!(
+            "
+                module Test
+                const C: {type_string} = {value}
+            "
+        );
+
+        // Act
+        let diagnostics = parse_for_diagnostics(slice);
+
+        // Assert
+        let expected = Diagnostic::new(Error::ConstantValueOutOfBounds {
+            constant_identifier: "C".to_owned(),
+            value: value.parse().unwrap(),
+            min,
+            max,
+        });
+        check_diagnostics(diagnostics, [expected]);
+    }
+
+    #[test_case("uint8", "\"5\""; "string for integer")]
+    #[test_case("string", "5"; "integer for string")]
+    #[test_case("bool", "1"; "integer for bool")]
+    #[test_case("bool", "yes"; "identifier for bool")]
+    #[test_case("E", "C"; "missing enumerator")]
+    #[test_case("E", "F::A"; "enumerator of another enum")]
+    #[test_case("E", "0"; "integer for enum")]
+    fn values_must_match_their_type(type_string: &str, value: &str) {
+        // Arrange
+        let slice = format!(
+            "
+                module Test
+                enum E : uint8 {{ A, B }}
+                enum F : uint8 {{ A }}
+                const C: {type_string} = {value}
+            "
+        );
+
+        // Act
+        let diagnostics = parse_for_diagnostics(slice);
+
+        // Assert
+        let expected = Diagnostic::new(Error::ConstantValueTypeMismatch {
+            constant_identifier: "C".to_owned(),
+            type_string: type_string.to_owned(),
+            value: value.to_owned(),
+        });
+        check_diagnostics(diagnostics, [expected]);
+    }
+
+    #[test_case("float64", "5"; "floats")]
+    #[test_case("S", "5"; "structs")]
+    #[test_case("Shape", "Square"; "enums with fields")]
+    #[test_case("Sequence<uint8>", "5"; "sequences")]
+    #[test_case("uint8?", "5"; "optional types")]
+    fn unsupported_types_are_rejected(type_string: &str, value: &str) {
+        // Arrange
+        let slice = format!(
+            "
+                module Test
+                struct S {{}}
+                enum Shape {{ Circle(radius: float32), Square }}
+                const C: {type_string} = {value}
+            "
+        );
+
+        // Act
+        let diagnostics = parse_for_diagnostics(slice);
+
+        // Assert
+        let expected = Diagnostic::new(Error::ConstantTypeNotSupported {
+            constant_identifier: "C".to_owned(),
+            kind: type_string.to_owned(),
+        });
+        check_diagnostics(diagnostics, [expected]);
+    }
+
+    #[test]
+    fn cannot_redefine_constants() {
+        // Arrange
+        let slice = "
+            module Test
+            const C: bool = true
+            const C: bool = false
+        ";
+
+        // Act
+        let diagnostics = parse_for_diagnostics(slice);
+
+        // Assert
+        let expected = Diagnostic::new(Error::Redefinition {
+            identifier: "C".to_owned(),
+        });
+        check_diagnostics(diagnostics, [expected]);
+    }
+
+    #[test]
+    fn constants_are_converted_to_symbols() {
+        // Arrange
+        let slice = "
+            module Test
+            enum E : uint8 { A, B }
+            const Offset: int64 = -5
+            const Enabled: bool = true
+            const Greeting: string = \"hi\"
+            const Default: E = B
+        ";
+        let compilation_state = parse(slice, None);
+        assert!(!compilation_state.diagnostics.has_errors());
+
+        // Act
+        let slice_file = SliceFile::from(&compilation_state.files[0]);
+
+        // Assert
+        let values = slice_file
+            .contents
+            .iter()
+            .filter_map(|symbol| match symbol {
+                Symbol::Constant(definition_types::Constant { value, .. }) => Some(value),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        let ConvertedValue::Integer {
+            absolute_value,
+            has_negative_value,
+        } = values[0]
+        else {
+            panic!()
+        };
+        assert_eq!((*absolute_value, *has_negative_value), (5, true));
+        assert!(matches!(values[1], ConvertedValue::Bool(true)));
+        assert!(matches!(values[2], ConvertedValue::String(v) if v == "hi"));
+        assert!(matches!(values[3], ConvertedValue::Enumerator(v) if v == "Test::E::B"));
+    }
+}
@@ -0,0 +1,151 @@
+// Copyright (c) ZeroC, Inc.
+
+mod test_helpers;
+
+mod default_values {
+
+    use crate::test_helpers::*;
+    use slicec::definition_types::{ConstantValue as ConvertedValue, SliceFile, Symbol};
+    use slicec::diagnostics::{Diagnostic, Error};
+    use slicec::grammar::*;
+    use test_case::test_case;
+
+    #[test_case("uint32", "30"; "integers")]
+    #[test_case("int64", "-1"; "negative integers")]
+    #[test_case("bool", "true"; "bools")]
+    #[test_case("string", "\"localhost\""; "strings")]
+    #[test_case("E", "B"; "enumerators")]
+    #[test_case("E", "Test::E::B"; "qualified enumerators")]
+    fn fields_can_have_default_values_of(type_string: &str, value: &str) {
+        // Arrange
+        let slice = format!(
+            "
+                module Test
+                enum E : uint8 {{ A, B }}
+                struct S {{ f: {type_string} = {value} }}
+                enum V {{ Variant(f: {type_string} = {value}) }}
+            "
+        );
+
+        // Act/Assert
+        assert_parses(slice);
+    }
+
+    #[test]
+    fn default_values_are_stored_on_fields() {
+        // Arrange
+        let slice = "
+            module Test
+            struct S {
+                timeout: uint32 = 30
+                name: string
+            }
+        ";
+
+        // Act
+        let ast = parse_for_ast(slice);
+
+        // Assert
+        let timeout = ast.find_element::<Field>("Test::S::timeout").unwrap();
+        let name = ast.find_element::<Field>("Test::S::name").unwrap();
+        assert!(matches!(&timeout.default_value, Some(ConstantValue::Integer(integer)) if integer.value == 30));
+        assert!(name.default_value.is_none());
+    }
+
+    #[test]
+    fn default_values_must_be_within_bounds() {
+        // Arrange
+        let slice = "
+            module Test
+            struct S { f: int8 = 128 }
+        ";
+
+        // Act
+        let diagnostics = parse_for_diagnostics(slice);
+
+        // Assert
+        let expected = Diagnostic::new(Error::DefaultValueOutOfBounds {
+            field_identifier: "f".to_owned(),
+            value: 128,
+            min: i8::MIN as i128,
+            max: i8::MAX as i128,
+        });
+        check_diagnostics(diagnostics, [expected]);
+    }
+
+    #[test_case("uint8", "true"; "bool for integer")]
+    #[test_case("bool", "\"true\""; "string for bool")]
+    #[test_case("E", "C"; "missing enumerator")]
+    #[test_case("E", "F::A"; "enumerator of another enum")]
+    fn default_values_must_match_their_type(type_string: &str, value: &str) {
+        // Arrange
+        let slice = format!(
+            "
+                module Test
+                enum E : uint8 {{ A, B }}
+                enum F : uint8 {{ A }}
+                struct S {{ f: {type_string} = {value} }}
+            "
+        );
+
+        // Act
+        let diagnostics = parse_for_diagnostics(slice);
+
+        // Assert
+        let expected = Diagnostic::new(Error::DefaultValueTypeMismatch {
+            field_identifier: "f".to_owned(),
+            type_string: type_string.to_owned(),
+            value: value.to_owned(),
+        });
+        check_diagnostics(diagnostics, [expected]);
+    }
+
+    #[test_case("uint8?", "5"; "optional types")]
+    #[test_case("float32", "5"; "floats")]
+    #[test_case("Sequence<uint8>", "5"; "sequences")]
+    fn unsupported_types_cannot_have_default_values(type_string: &str, value: &str) {
+        // Arrange
+        let slice = format!(
+            "
+                module Test
+                struct S {{ f: {type_string} = {value} }}
+            "
+        );
+
+        // Act
+        let diagnostics = parse_for_diagnostics(slice);
+
+        // Assert
+        let expected = Diagnostic::new(Error::DefaultValueTypeNotSupported {
+            field_identifier: "f".to_owned(),
+            kind: type_string.to_owned(),
+        });
+        check_diagnostics(diagnostics, [expected]);
+    }
+
+    #[test]
+    fn default_values_are_converted() {
+        // Arrange
+        let slice = "
+            module Test
+            enum E : uint8 { A, B }
+            struct S {
+                e: E = B
+                b: bool = false
+                n: int32
+            }
+        ";
+        let compilation_state = parse(slice, None);
+        assert!(!compilation_state.diagnostics.has_errors());
+
+        // Act
+        let slice_file = SliceFile::from(&compilation_state.files[0]);
+
+        // Assert
+        let Some(Symbol::Struct(struct_def)) = slice_file.contents.last() else { panic!() };
+        let default_values = struct_def.fields.iter().map(|f| &f.default_value).collect::<Vec<_>>();
+        assert!(matches!(default_values[0], Some(ConvertedValue::Enumerator(v)) if v == "Test::E::B"));
+        assert!(matches!(default_values[1], Some(ConvertedValue::Bool(false))));
+        assert!(default_values[2].is_none());
+    }
+}
@@ -0,0 +1,57 @@
+// Copyright (c) ZeroC, Inc.
+
+mod test_helpers;
+
+use slice_codec::decoder::Decoder;
+use slice_codec::encoder::Encoder;
+use slicec::definition_types::SliceFile;
+use test_helpers::parse;
+
+#[test]
+fn slice_files_can_be_decoded_after_being_encoded() {
+    // Arrange
+    let slice = "
+        [[allow(Deprecated)]]
+        module Test
+
+        /// A struct.
+        compact struct Point { x: int32, y: int32 }
+
+        struct S {
+            points: Sequence<Point>
+            tag(1) lookup: Dictionary<string, Point?>?
+            timeout: uint32 = 30
+            e: E = E::A
+        }
+
+        unchecked enum E : uint8 { A = 1, B = 2 }
+
+        enum Shape { Circle(radius: float32), Square }
+
+        interface I {
+            /// Does something.
+            /// @returns: the result.
+            op(s: S) -> Result<bool, string>
+        }
+
+        custom C
+        typealias T = Sequence<C>
+
+        /// A constant.
+        const Default: E = E::B
+        const Offset: varint62 = -5
+    ";
+    let compilation_state = parse(slice, None);
+    assert!(!compilation_state.diagnostics.has_errors());
+    let slice_file = SliceFile::from(&compilation_state.files[0]);
+
+    // Act
+    let mut encoded_bytes: Vec<u8> = Vec::new();
+    Encoder::from(&mut encoded_bytes).encode(&slice_file).unwrap();
+    let decoded_file = Decoder::from(encoded_bytes.as_slice()).decode::<SliceFile>().unwrap();
+
+    // Assert
+    let expected = serde_json::to_value(&slice_file).unwrap();
+    let actual = serde_json::to_value(&decoded_file).unwrap();
+    assert_eq!(actual, expected);
+}
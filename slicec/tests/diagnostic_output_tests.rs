@@ -5,6 +5,7 @@ mod test_helpers;
 mod output {
     use crate::test_helpers::parse;
     use slicec::diagnostic_emitter::DiagnosticEmitter;
+    use slicec::diagnostics::{Diagnostic, DiagnosticLevel, GeneratorDiagnostic};
     use slicec::slice_options::{DiagnosticFormat, SliceOptions};
 
     #[test]
@@ -178,6 +179,45 @@ error [E008]: invalid enum 'E': enums must contain at least one enumerator
         assert_eq!(expected, String::from_utf8(output).unwrap());
     }
 
+    #[test]
+    fn deny_specific_lint_flag() {
+        let slice = "
+            module Foo
+
+            interface I {
+                /// {@link Fake}
+                /// @param x: this is an x
+                op()
+            }
+        ";
+
+        // Set the output format to JSON.
+        let options = SliceOptions {
+            diagnostic_format: DiagnosticFormat::Json,
+            denied_lints: vec!["IncorrectDocComment".to_owned()],
+            ..Default::default()
+        };
+
+        // Parse the Slice file.
+        let state = parse(slice, Some(&options));
+        let diagnostics = state.diagnostics.into_updated(&state.ast, &state.files, &options);
+
+        let mut output: Vec<u8> = Vec::new();
+        let mut emitter = DiagnosticEmitter::new(&mut output, &options, &state.files);
+
+        // Act
+        emitter.emit_diagnostics(diagnostics).unwrap();
+
+        // Assert: Only the denied lint should be reported as an error.
+        let expected = concat!(
+            r#"{"message":"no element named 'Fake' exists in scope","severity":"warning","span":{"start":{"row":5,"col":28},"end":{"row":5,"col":32},"file":"string-0"},"notes":[],"error_code":"BrokenDocLink"}"#,
+            "\n",
+            r#"{"message":"comment has a 'param' tag for 'x', but operation 'op' has no parameter with that name","severity":"error","span":{"start":{"row":6,"col":21},"end":{"row":6,"col":29},"file":"string-0"},"notes":[],"error_code":"IncorrectDocComment"}"#,
+            "\n",
+        );
+        assert_eq!(expected, String::from_utf8(output).unwrap());
+    }
+
     #[test]
     fn crlf_line_endings() {
         let slice = "module Foo \r\n   enum\r\n E\r : uint8\r\n{}\r\n\r";
@@ -210,4 +250,115 @@ error [E008]: invalid enum 'E': enums must contain at least one enumerator
 ";
         assert_eq!(expected, String::from_utf8(output).unwrap());
     }
+
+    #[test]
+    fn generator_diagnostics_are_resolved_to_their_source() {
+        let slice = "
+            module Foo
+
+            struct S {
+                x: int32
+            }
+        ";
+
+        // Disable ANSI color codes.
+        let options = SliceOptions {
+            disable_color: true,
+            ..Default::default()
+        };
+
+        // Parse the Slice file, and report a diagnostic on one of its fields, as a code-generator would.
+        let mut state = parse(slice, Some(&options));
+        Diagnostic::new(GeneratorDiagnostic {
+            generator: "slicec-test".to_owned(),
+            level: DiagnosticLevel::Error,
+            message: "int32 fields are not supported".to_owned(),
+            source: Some("Foo::S::x".to_owned()),
+        })
+        .push_into(&mut state.diagnostics);
+        assert!(state.diagnostics.has_errors());
+        let diagnostics = state.diagnostics.into_updated(&state.ast, &state.files, &options);
+
+        let mut output: Vec<u8> = Vec::new();
+        let mut emitter = DiagnosticEmitter::new(&mut output, &options, &state.files);
+
+        // Act
+        emitter.emit_diagnostics(diagnostics).unwrap();
+
+        // Assert
+        let expected = "\
+error [slicec-test]: int32 fields are not supported
+ --> string-0:5:17
+  |
+5 |                 x: int32
+  |                 --------
+  |
+";
+        assert_eq!(expected, String::from_utf8(output).unwrap());
+    }
+
+    #[test]
+    fn generator_diagnostics_with_unknown_sources_have_a_note() {
+        // Set the output format to JSON.
+        let options = SliceOptions {
+            diagnostic_format: DiagnosticFormat::Json,
+            ..Default::default()
+        };
+
+        // Parse the Slice file, and report a diagnostic on a non-existent element, as a code-generator would.
+        let mut state = parse("module Foo", Some(&options));
+        Diagnostic::new(GeneratorDiagnostic {
+            generator: "slicec-test".to_owned(),
+            level: DiagnosticLevel::Info,
+            message: "hello".to_owned(),
+            source: Some("Foo::Fake".to_owned()),
+        })
+        .push_into(&mut state.diagnostics);
+        assert!(!state.diagnostics.has_errors());
+        let diagnostics = state.diagnostics.into_updated(&state.ast, &state.files, &options);
+
+        let mut output: Vec<u8> = Vec::new();
+        let mut emitter = DiagnosticEmitter::new(&mut output, &options, &state.files);
+
+        // Act
+        emitter.emit_diagnostics(diagnostics).unwrap();
+
+        // Assert
+        let expected = concat!(
+            r#"{"message":"hello","severity":"info","span":null,"notes":[{"message":"reported on 'Foo::Fake', which could not be found","span":null}],"error_code":"slicec-test"}"#,
+            "\n",
+        );
+        assert_eq!(expected, String::from_utf8(output).unwrap());
+    }
+
+    #[test]
+    fn generator_warnings_can_be_allowed_by_attributes() {
+        let slice = "
+            module Foo
+
+            [allow(All)]
+            struct S {}
+        ";
+
+        let options = SliceOptions::default();
+
+        // Parse the Slice file, and report a warning and an error on the struct, as a code-generator would.
+        let mut state = parse(slice, Some(&options));
+        for level in [DiagnosticLevel::Warning, DiagnosticLevel::Error] {
+            Diagnostic::new(GeneratorDiagnostic {
+                generator: "slicec-test".to_owned(),
+                level,
+                message: "message".to_owned(),
+                source: Some("Foo::S".to_owned()),
+            })
+            .push_into(&mut state.diagnostics);
+        }
+
+        // Act
+        let diagnostics = state.diagnostics.into_updated(&state.ast, &state.files, &options);
+
+        // Assert: only the warning should be allowed; errors can never be allowed.
+        let levels = diagnostics.iter().map(Diagnostic::level).collect::<Vec<_>>();
+        assert_eq!(levels, [DiagnosticLevel::Allowed, DiagnosticLevel::Error]);
+    }
 }
@@ -0,0 +1,177 @@
+// Copyright (c) ZeroC, Inc.
+
+mod test_helpers;
+
+use clap::Parser;
+use slicec::driver::Driver;
+use slicec::slice_options::SliceOptions;
+use std::io::Read;
+use std::path::Path;
+use std::process::ExitCode;
+use test_helpers::{create_test_dir, run_slicec, StructListGenerator};
+
+/// Parses the provided generator option into slice options, which compile the 'test.slice' file in the provided
+/// directory, and write any generated files to an 'output' directory within it.
+fn create_options(dir: &Path, generator: &str) -> SliceOptions {
+    let slice_file = dir.join("test.slice").display().to_string();
+    let output_dir = dir.join("output").display().to_string();
+    SliceOptions::parse_from(["", &slice_file, "-G", generator, "-O", &output_dir])
+}
+
+#[test]
+fn registered_generators_are_run_in_process() {
+    // Arrange
+    let dir = create_test_dir(
+        "driver-run-in-process",
+        "test.slice",
+        "module Test\nstruct A {}\nstruct B {}",
+    );
+    let options = create_options(&dir, "struct-list");
+    let mut driver = Driver::new();
+    driver.register_generator(StructListGenerator);
+
+    // Act
+    let exit_code = driver.run(options);
+
+    // Assert
+    assert_eq!(exit_code, ExitCode::SUCCESS);
+    let contents = std::fs::read_to_string(dir.join("output/structs/list.txt")).unwrap();
+    assert_eq!(contents, "A\nB\n");
+}
+
+#[test]
+fn errors_reported_by_registered_generators_fail_the_build() {
+    // Arrange
+    let dir = create_test_dir("driver-reported-errors", "test.slice", "module Test\nstruct A {}");
+    let options = create_options(&dir, "struct-list,error=something went wrong");
+    let mut driver = Driver::new();
+    driver.register_generator(StructListGenerator);
+
+    // Act
+    let exit_code = driver.run(options);
+
+    // Assert
+    assert_eq!(exit_code, ExitCode::FAILURE);
+    assert!(!dir.join("output/structs/list.txt").exists());
+}
+
+#[test]
+fn denying_all_lints_does_not_affect_warnings_reported_by_generators() {
+    // Arrange
+    let dir = create_test_dir("driver-deny-all", "test.slice", "module Test\nstruct A {}");
+    let options = SliceOptions {
+        denied_lints: vec!["All".to_owned()],
+        ..create_options(&dir, "struct-list,warning=something looks off")
+    };
+    let mut driver = Driver::new();
+    driver.register_generator(StructListGenerator);
+
+    // Act
+    let exit_code = driver.run(options);
+
+    // Assert
+    assert_eq!(exit_code, ExitCode::SUCCESS);
+    assert!(dir.join("output/structs/list.txt").exists());
+}
+
+#[test]
+fn denied_lints_prevent_code_generation() {
+    // Arrange
+    let slice = "module Test\n/// See {@link Missing}.\nstruct A {}";
+    let dir = create_test_dir("driver-denied-lint", "test.slice", slice);
+    let options = SliceOptions {
+        denied_lints: vec!["BrokenDocLink".to_owned()],
+        ..create_options(&dir, "struct-list")
+    };
+    let mut driver = Driver::new();
+    driver.register_generator(StructListGenerator);
+
+    // Act
+    let exit_code = driver.run(options);
+
+    // Assert
+    assert_eq!(exit_code, ExitCode::FAILURE);
+    assert!(!dir.join("output").exists());
+}
+
+#[test]
+fn denied_lints_that_are_allowed_do_not_prevent_code_generation() {
+    // Arrange
+    let slice = "module Test\n/// See {@link Missing}.\n[allow(BrokenDocLink)]\nstruct A {}";
+    let dir = create_test_dir("driver-denied-allowed-lint", "test.slice", slice);
+    let options = SliceOptions {
+        denied_lints: vec!["BrokenDocLink".to_owned()],
+        ..create_options(&dir, "struct-list")
+    };
+    let mut driver = Driver::new();
+    driver.register_generator(StructListGenerator);
+
+    // Act
+    let exit_code = driver.run(options);
+
+    // Assert
+    assert_eq!(exit_code, ExitCode::SUCCESS);
+    assert!(dir.join("output/structs/list.txt").exists());
+}
+
+#[test]
+fn builtin_json_generator_is_registered_by_default() {
+    // Arrange
+    let dir = create_test_dir("driver-builtin-json", "test.slice", "module Test\nstruct A {}");
+    let options = create_options(&dir, "json");
+
+    // Act
+    let exit_code = Driver::new().run(options);
+
+    // Assert
+    assert_eq!(exit_code, ExitCode::SUCCESS);
+    let contents = std::fs::read_to_string(dir.join("output").join("ast.json")).unwrap();
+    let json = serde_json::from_str::<serde_json::Value>(&contents).unwrap();
+    assert_eq!(json["sourceFiles"][0]["contents"][0]["kind"], "Struct");
+}
+
+#[test]
+fn generated_files_can_be_bundled_into_an_archive() {
+    // Arrange
+    let dir = create_test_dir("driver-output-archive", "test.slice", "module Test\nstruct A {}");
+    let archive_path = dir.join("generated.tar");
+    let slice_file = dir.join("test.slice").display().to_string();
+    let archive_arg = archive_path.display().to_string();
+    let options = SliceOptions::parse_from(["", &slice_file, "-G", "struct-list", "--output-archive", &archive_arg]);
+    let mut driver = Driver::new();
+    driver.register_generator(StructListGenerator);
+
+    // Act
+    let exit_code = driver.run(options);
+
+    // Assert
+    assert_eq!(exit_code, ExitCode::SUCCESS);
+    let mut archive = tar::Archive::new(std::fs::File::open(&archive_path).unwrap());
+    let entries = archive
+        .entries()
+        .unwrap()
+        .map(|entry| {
+            let mut entry = entry.unwrap();
+            let mut contents = String::new();
+            entry.read_to_string(&mut contents).unwrap();
+            (entry.path().unwrap().display().to_string(), contents)
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(entries, [("structs/list.txt".to_owned(), "A\n".to_owned())]);
+    assert!(!Path::new("structs").exists());
+}
+
+#[test]
+fn generated_files_and_ast_json_cannot_both_be_written_to_stdout() {
+    // Arrange
+    let dir = create_test_dir("driver-stdout-twice", "test.slice", "module Test\nstruct A {}");
+
+    // Act
+    let output = run_slicec(&dir, &["test.slice", "-G", "json", "-O", "-", "--emit-ast-json", "-"]);
+
+    // Assert
+    assert!(!output.status.success());
+    assert!(output.stdout.is_empty());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("error [E053]"), "{stderr}");
+}
@@ -0,0 +1,72 @@
+// Copyright (c) ZeroC, Inc.
+
+mod test_helpers;
+
+use test_case::test_case;
+use test_helpers::{create_generated_dir, read_dir_contents, run_slicec};
+
+#[test_case("unchanged", "json,path=a.json", false, &["unchanged: output/a.json (from 'json')"])]
+#[test_case("changed", "json,path=a.json", true, &["changed: output/a.json (from 'json')"])]
+#[test_case("new-and-stale", "json,path=b.json", false, &[
+    "new: output/b.json (from 'json')",
+    "stale: output/a.json (from 'json')",
+])]
+fn dry_run_reports_the_status_of_each_file(name: &str, generator: &str, edit_file: bool, expected: &[&str]) {
+    // Arrange
+    let dir = create_generated_dir(&format!("dry-run-human-{name}"));
+    if edit_file {
+        dir.write_file("output/a.json", "{}");
+    }
+    let contents = read_dir_contents(&dir.join("output"));
+
+    // Act
+    let output = run_slicec(&dir, &["test.slice", "-G", generator, "-O", "output", "--dry-run"]);
+
+    // Assert
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(stdout.lines().collect::<Vec<_>>(), expected);
+    assert_eq!(read_dir_contents(&dir.join("output")), contents);
+}
+
+#[test_case("unchanged", "json,path=a.json", false, &[("output/a.json", "unchanged")])]
+#[test_case("changed", "json,path=a.json", true, &[("output/a.json", "changed")])]
+#[test_case("new-and-stale", "json,path=b.json", false, &[("output/b.json", "new"), ("output/a.json", "stale")])]
+fn dry_run_reports_the_status_of_each_file_as_json(
+    name: &str,
+    generator: &str,
+    edit_file: bool,
+    expected: &[(&str, &str)],
+) {
+    // Arrange
+    let dir = create_generated_dir(&format!("dry-run-json-{name}"));
+    if edit_file {
+        dir.write_file("output/a.json", "{}");
+    }
+    let contents = read_dir_contents(&dir.join("output"));
+    let args = [
+        "test.slice",
+        "-G",
+        generator,
+        "-O",
+        "output",
+        "--dry-run",
+        "--diagnostic-format",
+        "json",
+    ];
+
+    // Act
+    let output = run_slicec(&dir, &args);
+
+    // Assert
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let reports = stdout
+        .lines()
+        .map(|line| serde_json::from_str::<serde_json::Value>(line).unwrap());
+    let expected = expected
+        .iter()
+        .map(|(path, status)| serde_json::json!({ "path": path, "status": status, "generator": "json" }));
+    assert_eq!(reports.collect::<Vec<_>>(), expected.collect::<Vec<_>>());
+    assert_eq!(read_dir_contents(&dir.join("output")), contents);
+}
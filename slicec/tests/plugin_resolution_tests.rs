@@ -0,0 +1,176 @@
+// Copyright (c) ZeroC, Inc.
+
+use clap::Parser;
+use slicec::diagnostics::Diagnostics;
+use slicec::slice_options::SliceOptions;
+use slicec::utils::plugin_util::{resolve_generators, resolve_plugin_path};
+use std::path::Path;
+use test_helpers::TempDir;
+
+mod test_helpers;
+
+/// Creates an empty temporary directory with the provided name, containing an empty executable file for each of the
+/// provided file names.
+fn create_plugin_dir(name: &str, file_names: &[&str]) -> TempDir {
+    let dir = TempDir::new(name);
+    for file_name in file_names {
+        let path = dir.write_file(format!("{file_name}{}", std::env::consts::EXE_SUFFIX), "");
+        set_executable(&path, true);
+    }
+    dir
+}
+
+/// Sets or clears the execute permissions of the provided file. This does nothing on platforms other than unix.
+fn set_executable(path: &Path, is_executable: bool) {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mode = if is_executable { 0o755 } else { 0o644 };
+        std::fs::set_permissions(path, std::fs::Permissions::from_mode(mode)).unwrap();
+    }
+    #[cfg(not(unix))]
+    let _ = (path, is_executable);
+}
+
+#[test]
+fn plugins_specified_by_path_are_not_resolved() {
+    // Arrange
+    let mut diagnostics = Diagnostics::new();
+
+    // Act
+    let resolved_path = resolve_plugin_path("./path/to/generator", &[], &mut diagnostics);
+
+    // Assert
+    assert_eq!(resolved_path.as_deref(), Some("./path/to/generator"));
+    assert!(diagnostics.is_empty());
+}
+
+#[test]
+fn plugins_specified_by_name_are_resolved_from_plugin_dirs() {
+    // Arrange
+    let plugin_dir = create_plugin_dir("resolved-from-plugin-dirs", &["slicec-test-generator"]);
+    let plugin_dirs = [plugin_dir.display().to_string()];
+    let mut diagnostics = Diagnostics::new();
+
+    // Act
+    let resolved_path = resolve_plugin_path("test-generator", &plugin_dirs, &mut diagnostics);
+
+    // Assert
+    let expected = plugin_dir.join(format!("slicec-test-generator{}", std::env::consts::EXE_SUFFIX));
+    assert_eq!(resolved_path, Some(expected.display().to_string()));
+    assert!(diagnostics.is_empty());
+}
+
+#[test]
+fn plugin_dirs_are_searched_in_order() {
+    // Arrange
+    let first_dir = create_plugin_dir("searched-in-order-1", &[]);
+    let second_dir = create_plugin_dir("searched-in-order-2", &["slicec-test-generator"]);
+    let third_dir = create_plugin_dir("searched-in-order-3", &["slicec-test-generator"]);
+    let plugin_dirs = [&first_dir, &second_dir, &third_dir].map(|dir| dir.display().to_string());
+    let mut diagnostics = Diagnostics::new();
+
+    // Act
+    let resolved_path = resolve_plugin_path("slicec-test-generator", &plugin_dirs, &mut diagnostics);
+
+    // Assert
+    let expected = second_dir.join(format!("slicec-test-generator{}", std::env::consts::EXE_SUFFIX));
+    assert_eq!(resolved_path, Some(expected.display().to_string()));
+}
+
+#[test]
+fn plugins_without_the_prefix_are_resolved_by_their_exact_name() {
+    // Arrange
+    let first_dir = create_plugin_dir("resolved-by-exact-name-1", &["test-generator"]);
+    let second_dir = create_plugin_dir("resolved-by-exact-name-2", &["slicec-other-generator"]);
+    let plugin_dirs = [&first_dir, &second_dir].map(|dir| dir.display().to_string());
+    let mut diagnostics = Diagnostics::new();
+
+    // Act
+    let resolved_path = resolve_plugin_path("test-generator", &plugin_dirs, &mut diagnostics);
+    let prefixed_path = resolve_plugin_path("other-generator", &plugin_dirs, &mut diagnostics);
+
+    // Assert
+    let expected = first_dir.join(format!("test-generator{}", std::env::consts::EXE_SUFFIX));
+    assert_eq!(resolved_path, Some(expected.display().to_string()));
+    let expected = second_dir.join(format!("slicec-other-generator{}", std::env::consts::EXE_SUFFIX));
+    assert_eq!(prefixed_path, Some(expected.display().to_string()));
+    assert!(diagnostics.is_empty());
+}
+
+#[cfg(unix)]
+#[test]
+fn files_which_are_not_executable_are_skipped() {
+    // Arrange
+    let first_dir = create_plugin_dir("not-executable-1", &["slicec-test-generator"]);
+    let second_dir = create_plugin_dir("not-executable-2", &["slicec-test-generator"]);
+    set_executable(&first_dir.join("slicec-test-generator"), false);
+    let plugin_dirs = [&first_dir, &second_dir].map(|dir| dir.display().to_string());
+    let mut diagnostics = Diagnostics::new();
+
+    // Act
+    let resolved_path = resolve_plugin_path("test-generator", &plugin_dirs, &mut diagnostics);
+
+    // Assert
+    let expected = second_dir.join("slicec-test-generator");
+    assert_eq!(resolved_path, Some(expected.display().to_string()));
+}
+
+#[test]
+fn missing_plugins_report_the_searched_locations() {
+    // Arrange
+    let plugin_dir = create_plugin_dir("missing-plugins", &["slicec-other-generator"]);
+    let plugin_dirs = [plugin_dir.display().to_string()];
+    let mut diagnostics = Diagnostics::new();
+
+    // Act
+    let resolved_path = resolve_plugin_path("missing-generator", &plugin_dirs, &mut diagnostics);
+
+    // Assert
+    assert_eq!(resolved_path, None);
+    let diagnostics = diagnostics.into_inner();
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].code(), "E039");
+    assert_eq!(
+        diagnostics[0].message(),
+        "unable to find code-generator 'missing-generator': no executable named 'slicec-missing-generator' or 'missing-generator' was found",
+    );
+
+    let note = &diagnostics[0].notes()[0].message;
+    assert!(note.starts_with("searched the following locations:\n"));
+    assert!(note.contains(&format!("    {} (from '--plugin-dir')", plugin_dir.display())));
+}
+
+#[test]
+fn in_process_generators_are_not_resolved() {
+    // Arrange
+    let mut options = SliceOptions::parse_from(["", "-G", "json,path=ast.json"]);
+    let mut diagnostics = Diagnostics::new();
+
+    // Act
+    resolve_generators(&mut options, &["json"], &mut diagnostics);
+
+    // Assert
+    assert_eq!(options.generators.len(), 1);
+    assert_eq!(options.generators[0].path, "json");
+    assert!(diagnostics.is_empty());
+}
+
+#[test]
+fn generators_with_the_same_name_are_rejected() {
+    // Arrange
+    let mut options = SliceOptions::parse_from(["", "-G", "a/slicec-cs", "-G", "b/slicec-cs.exe", "-G", "json"]);
+    let mut diagnostics = Diagnostics::new();
+
+    // Act
+    resolve_generators(&mut options, &["json"], &mut diagnostics);
+
+    // Assert
+    let diagnostics = diagnostics.into_inner();
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].code(), "E052");
+    assert_eq!(
+        diagnostics[0].message(),
+        "code-generators 'a/slicec-cs' and 'b/slicec-cs.exe' are both named 'slicec-cs'",
+    );
+}
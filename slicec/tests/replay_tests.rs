@@ -0,0 +1,65 @@
+// Copyright (c) ZeroC, Inc.
+
+mod test_helpers;
+
+use test_helpers::{create_test_dir, run_slicec};
+
+#[test]
+fn emitted_requests_can_be_replayed() {
+    // Arrange
+    let dir = create_test_dir("replay-emitted-request", "test.slice", "module Test\nstruct A {}");
+    let generator = "json,path=defs.json,unknown=value";
+    let args = [
+        "test.slice",
+        "-G",
+        generator,
+        "-O",
+        "output",
+        "--emit-request",
+        "request.bin",
+    ];
+    let output = run_slicec(&dir, &args);
+    assert!(output.status.success());
+
+    // Act
+    let args = [
+        "replay-request",
+        "request.bin",
+        "-G",
+        "json",
+        "--diagnostic-format",
+        "json",
+    ];
+    let output = run_slicec(&dir, &args);
+
+    // Assert
+    assert!(output.status.success());
+    let response = serde_json::from_slice::<serde_json::Value>(&output.stdout).unwrap();
+    let generated_contents = std::fs::read_to_string(dir.join("output/defs.json")).unwrap();
+    assert_eq!(
+        response["generated_files"],
+        serde_json::json!([
+            { "path": "defs.json", "contents": generated_contents }
+        ])
+    );
+    assert_eq!(
+        response["diagnostics"],
+        serde_json::json!([
+            { "level": "warning", "message": "unknown argument 'unknown' was ignored", "source": null }
+        ])
+    );
+}
+
+#[test]
+fn invalid_requests_fail_to_replay() {
+    // Arrange
+    let dir = create_test_dir("replay-invalid-request", "request.bin", "not a request");
+
+    // Act
+    let output = run_slicec(&dir, &["replay-request", "request.bin", "-G", "json"]);
+
+    // Assert
+    assert_eq!(output.status.code(), Some(1));
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.starts_with("error [E001]: unable to run code-generator 'json': "));
+}
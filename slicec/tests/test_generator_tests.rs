@@ -0,0 +1,135 @@
+// Copyright (c) ZeroC, Inc.
+
+mod test_helpers;
+
+use clap::Parser;
+use slicec::driver::Driver;
+use slicec::slice_options::SliceOptions;
+use std::path::Path;
+use std::process::ExitCode;
+use test_helpers::{create_test_dir, StructListGenerator};
+
+/// Runs the 'test-generator' command over the provided test directory with the 'struct-list' code-generator.
+fn test_generator(dir: &Path, generator: &str, bless: bool) -> ExitCode {
+    let test_dir = dir.display().to_string();
+    let mut args = vec!["", "test-generator", &test_dir, "-G", generator];
+    if bless {
+        args.push("--bless");
+    }
+    let mut driver = Driver::new();
+    driver.register_generator(StructListGenerator);
+    driver.run(SliceOptions::parse_from(args))
+}
+
+#[test]
+fn blessed_snapshots_match_later_runs() {
+    // Arrange
+    let dir = create_test_dir(
+        "test-generator-bless",
+        "case/test.slice",
+        "module Test\nstruct A {}\nstruct B {}",
+    );
+
+    // Act
+    let blessed_exit_code = test_generator(&dir, "struct-list", true);
+    let exit_code = test_generator(&dir, "struct-list", false);
+
+    // Assert
+    assert_eq!(blessed_exit_code, ExitCode::SUCCESS);
+    assert_eq!(exit_code, ExitCode::SUCCESS);
+    let snapshot = std::fs::read_to_string(dir.join("case/expected/structs/list.txt")).unwrap();
+    assert_eq!(snapshot, "A\nB\n");
+    assert!(!dir.join("case/expected-diagnostics.txt").exists());
+}
+
+#[test]
+fn output_that_does_not_match_its_snapshot_fails() {
+    // Arrange
+    let dir = create_test_dir("test-generator-mismatch", "case/test.slice", "module Test\nstruct A {}");
+    std::fs::create_dir_all(dir.join("case/expected/structs")).unwrap();
+    std::fs::write(dir.join("case/expected/structs/list.txt"), "B\n").unwrap();
+
+    // Act
+    let exit_code = test_generator(&dir, "struct-list", false);
+
+    // Assert
+    assert_eq!(exit_code, ExitCode::FAILURE);
+    let snapshot = std::fs::read_to_string(dir.join("case/expected/structs/list.txt")).unwrap();
+    assert_eq!(snapshot, "B\n");
+}
+
+#[test]
+fn missing_snapshots_fail() {
+    // Arrange
+    let dir = create_test_dir("test-generator-missing", "case/test.slice", "module Test\nstruct A {}");
+
+    // Act
+    let exit_code = test_generator(&dir, "struct-list", false);
+
+    // Assert
+    assert_eq!(exit_code, ExitCode::FAILURE);
+    assert!(!dir.join("case/expected").exists());
+}
+
+#[test]
+fn snapshots_which_are_not_generated_fail_and_are_deleted_when_blessing() {
+    // Arrange
+    let dir = create_test_dir(
+        "test-generator-not-generated",
+        "case/test.slice",
+        "module Test\nstruct A {}",
+    );
+    std::fs::create_dir_all(dir.join("case/expected/structs")).unwrap();
+    std::fs::write(dir.join("case/expected/structs/list.txt"), "A\n").unwrap();
+    std::fs::write(dir.join("case/expected/old.txt"), "").unwrap();
+
+    // Act
+    let exit_code = test_generator(&dir, "struct-list", false);
+    let blessed_exit_code = test_generator(&dir, "struct-list", true);
+
+    // Assert
+    assert_eq!(exit_code, ExitCode::FAILURE);
+    assert_eq!(blessed_exit_code, ExitCode::SUCCESS);
+    assert!(!dir.join("case/expected/old.txt").exists());
+    assert!(dir.join("case/expected/structs/list.txt").exists());
+}
+
+#[test]
+fn diagnostics_are_checked_against_their_snapshot() {
+    // Arrange
+    let dir = create_test_dir(
+        "test-generator-diagnostics",
+        "case/test.slice",
+        "module Test\nstruct A {}",
+    );
+    test_generator(&dir, "struct-list,warning=watch out", true);
+
+    // Act
+    let exit_code = test_generator(&dir, "struct-list,warning=watch out", false);
+    let changed_exit_code = test_generator(&dir, "struct-list,warning=something else", false);
+    let removed_exit_code = test_generator(&dir, "struct-list", false);
+
+    // Assert
+    let snapshot = std::fs::read_to_string(dir.join("case/expected-diagnostics.txt")).unwrap();
+    assert_eq!(snapshot, "warning: watch out (reported on 'Test::A')\n");
+    assert_eq!(exit_code, ExitCode::SUCCESS);
+    assert_eq!(changed_exit_code, ExitCode::FAILURE);
+    assert_eq!(removed_exit_code, ExitCode::FAILURE);
+}
+
+#[test]
+fn test_cases_with_invalid_slice_fail() {
+    // Arrange
+    let dir = create_test_dir(
+        "test-generator-invalid-slice",
+        "case/test.slice",
+        "module Test\nstruct A { b: Missing }",
+    );
+
+    // Act
+    let exit_code = test_generator(&dir, "struct-list", true);
+
+    // Assert
+    assert_eq!(exit_code, ExitCode::FAILURE);
+    assert!(!dir.join("case/expected").exists());
+}
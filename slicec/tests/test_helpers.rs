@@ -6,10 +6,19 @@
 #![allow(dead_code)]
 
 use slicec::ast::Ast;
+use slicec::code_generator::CodeGenerator;
 use slicec::compilation_state::CompilationState;
 use slicec::compile_from_strings;
+use slicec::definition_types::{self, CompilationContext, GeneratedFile, SliceFile, Symbol};
 use slicec::diagnostics::{Diagnostic, DiagnosticLevel};
 use slicec::slice_options::SliceOptions;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+// The unit tests and the integration tests share a single implementation of `TempDir`.
+#[path = "../src/utils/test_util.rs"]
+mod test_util;
+pub use test_util::TempDir;
 
 /// This function parses the provided Slice file.
 /// It is the lowest level test helper function, returning a full [`CompilationState`] instead of only part of it.
@@ -165,3 +174,93 @@ pub fn check_diagnostics<const L: usize>(diagnostics: Vec<Diagnostic>, expected:
         }
     }
 }
+
+/// Creates an empty temporary directory with the provided name, containing a Slice file at the provided path (relative
+/// to the directory) with the provided contents. The directory is deleted once it's dropped.
+pub fn create_test_dir(name: &str, slice_path: &str, slice: &str) -> TempDir {
+    let dir = TempDir::new(name);
+    dir.write_file(slice_path, slice);
+    dir
+}
+
+/// Runs the 'slicec' executable with the provided arguments, from within the provided directory, and returns its
+/// output.
+pub fn run_slicec(dir: &Path, args: &[&str]) -> Output {
+    let mut command = Command::new(env!("CARGO_BIN_EXE_slicec"));
+    command.current_dir(dir).args(args).output().unwrap()
+}
+
+/// Creates a temporary directory containing a Slice file, and runs the JSON generator on it (with the 'slicec'
+/// executable), so that the directory's 'output' directory contains a generated 'a.json' file, and a manifest.
+pub fn create_generated_dir(name: &str) -> TempDir {
+    let dir = create_test_dir(name, "test.slice", "module Test\nstruct A {}");
+    let output = run_slicec(&dir, &["test.slice", "-G", "json,path=a.json", "-O", "output"]);
+    assert!(output.status.success());
+    dir
+}
+
+/// Returns the contents of every file in the provided directory (recursively), keyed by their paths.
+pub fn read_dir_contents(dir: &Path) -> Vec<(PathBuf, Vec<u8>)> {
+    let mut contents = Vec::new();
+    for entry in std::fs::read_dir(dir).unwrap() {
+        let path = entry.unwrap().path();
+        match path.is_dir() {
+            true => contents.extend(read_dir_contents(&path)),
+            false => contents.push((path.clone(), std::fs::read(&path).unwrap())),
+        }
+    }
+    contents.sort();
+    contents
+}
+
+/// A code-generator which generates a file named 'structs/list.txt', listing the identifiers of the structs in each
+/// source file. If it's passed an 'error' argument, it reports an error instead. If it's passed a 'warning' argument,
+/// it reports a warning on each struct, along with the generated file.
+pub struct StructListGenerator;
+
+impl CodeGenerator for StructListGenerator {
+    fn name(&self) -> &str {
+        "struct-list"
+    }
+
+    fn generate_code(
+        &self,
+        source_files: &[SliceFile],
+        _: &[SliceFile],
+        _: &CompilationContext,
+        args: &[(String, String)],
+    ) -> (Vec<GeneratedFile>, Vec<definition_types::Diagnostic>) {
+        let find_arg = |name: &str| args.iter().find(|(key, _)| key == name).map(|(_, value)| value);
+        if let Some(message) = find_arg("error") {
+            let diagnostic = definition_types::Diagnostic {
+                level: definition_types::DiagnosticLevel::Error,
+                message: message.clone(),
+                source: None,
+            };
+            return (Vec::new(), vec![diagnostic]);
+        }
+
+        let mut contents = String::new();
+        let mut diagnostics = Vec::new();
+        for source_file in source_files {
+            for symbol in &source_file.contents {
+                if let Symbol::Struct(struct_def) = symbol {
+                    contents += &format!("{}\n", struct_def.entity_info.identifier);
+                    if let Some(message) = find_arg("warning") {
+                        let module = &source_file.module_declaration.identifier;
+                        diagnostics.push(definition_types::Diagnostic {
+                            level: definition_types::DiagnosticLevel::Warning,
+                            message: message.clone(),
+                            source: Some(format!("{module}::{}", struct_def.entity_info.identifier)),
+                        });
+                    }
+                }
+            }
+        }
+        let generated_file = GeneratedFile {
+            path: "structs/list.txt".to_owned(),
+            contents,
+        };
+        (vec![generated_file], diagnostics)
+    }
+}